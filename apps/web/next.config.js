@@ -31,6 +31,11 @@ const nextConfig = {
       net: false,
       tls: false,
     };
+    // Curve math comes from the SDK's WASM build of the program's math crate
+    config.experiments = {
+      ...config.experiments,
+      asyncWebAssembly: true,
+    };
     return config;
   },
   experimental: {
@@ -244,7 +244,7 @@ export default function TokenDetailPage() {
           </CardTitle>
         </CardHeader>
         <CardContent>
-          <TradeHistory trades={trades || []} />
+          <TradeHistory trades={trades || []} decimals={token.decimals} />
         </CardContent>
       </Card>
     </div>
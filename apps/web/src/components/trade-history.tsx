@@ -7,9 +7,10 @@ import { ArrowUpRight, ArrowDownRight, ExternalLink } from "lucide-react";
 
 interface TradeHistoryProps {
   trades: Trade[];
+  decimals: number;
 }
 
-export function TradeHistory({ trades }: TradeHistoryProps) {
+export function TradeHistory({ trades, decimals }: TradeHistoryProps) {
   if (trades.length === 0) {
     return (
       <div className="text-center py-8 text-dark-400">
@@ -59,7 +60,7 @@ export function TradeHistory({ trades }: TradeHistoryProps) {
                 {formatSol(trade.solAmount)} SOL
               </td>
               <td className="py-3 font-mono text-sm">
-                {formatNumber(Number(trade.tokenAmount) / 1e6)}
+                {formatNumber(Number(trade.tokenAmount) / Math.pow(10, decimals))}
               </td>
               <td className="py-3 font-mono text-sm">
                 {trade.price.toFixed(8)}
@@ -1,8 +1,16 @@
 "use client";
 
 import { useState } from "react";
-import { useWallet, useConnection } from "@solana/wallet-adapter-react";
+import { useWallet } from "@solana/wallet-adapter-react";
+import { BN } from "@coral-xyz/anchor";
+import {
+  calculatePriceImpact,
+  calculateSolOut,
+  calculateTokensOut,
+  totalFeeBps,
+} from "@claw/sdk";
 import { Token } from "@/hooks/use-tokens";
+import { useCurve } from "@/hooks/use-curve";
 import { Button } from "@/components/ui/button";
 import { Input } from "@/components/ui/input";
 import { Card, CardHeader, CardTitle, CardContent } from "@/components/ui/card";
@@ -17,48 +25,57 @@ type TradeMode = "buy" | "sell";
 
 export function TradingPanel({ token }: TradingPanelProps) {
   const { connected, publicKey } = useWallet();
-  const { connection } = useConnection();
+  const { data: onChain } = useCurve(token.mint);
 
   const [mode, setMode] = useState<TradeMode>("buy");
   const [amount, setAmount] = useState("");
   const [isLoading, setIsLoading] = useState(false);
 
-  // Calculate estimated output
+  // Calculate estimated output with the program's own curve math, against the curve
+  // and fees as they are on-chain
   const inputAmount = parseFloat(amount) || 0;
-  const virtualSol = Number(token.virtualSolReserves);
-  const virtualTokens = Number(token.virtualTokenReserves);
+  const tokenUnit = Math.pow(10, token.decimals);
+  const feeBps = onChain ? totalFeeBps(onChain.config.feeBps, onChain.curve) : 0;
 
   let estimatedOutput = 0;
+  let estimatedFee = 0;
   let priceImpact = 0;
-  const feeBps = 100; // 1%
-
-  if (mode === "buy" && inputAmount > 0) {
-    // Calculate tokens out
-    const fee = (inputAmount * feeBps) / 10000;
-    const solAfterFee = inputAmount - fee;
-    const k = virtualSol * virtualTokens;
-    const newSol = virtualSol + solAfterFee * 1e9;
-    const newTokens = k / newSol;
-    estimatedOutput = (virtualTokens - newTokens) / 1e6;
-
-    // Price impact
-    const priceBefore = virtualSol / virtualTokens;
-    const priceAfter = newSol / newTokens;
-    priceImpact = ((priceAfter - priceBefore) / priceBefore) * 100;
-  } else if (mode === "sell" && inputAmount > 0) {
-    // Calculate SOL out
-    const tokenAmount = inputAmount * 1e6;
-    const k = virtualSol * virtualTokens;
-    const newTokens = virtualTokens + tokenAmount;
-    const newSol = k / newTokens;
-    const solOutGross = (virtualSol - newSol) / 1e9;
-    const fee = (solOutGross * feeBps) / 10000;
-    estimatedOutput = solOutGross - fee;
-
-    // Price impact
-    const priceBefore = virtualSol / virtualTokens;
-    const priceAfter = newSol / newTokens;
-    priceImpact = ((priceBefore - priceAfter) / priceBefore) * 100;
+
+  if (onChain && inputAmount > 0) {
+    try {
+      const { curve } = onChain;
+      const { migrationThreshold } = onChain.config;
+      // Buys are entered in SOL, sells in whole tokens. Base units of 9-decimal tokens
+      // overflow `BN`'s number constructor, so go through a string.
+      const input = new BN(
+        BigInt(
+          Math.floor(inputAmount * (mode === "buy" ? 1e9 : tokenUnit))
+        ).toString()
+      );
+      if (mode === "buy") {
+        const { tokensOut, fee } = calculateTokensOut(
+          curve,
+          input,
+          feeBps,
+          migrationThreshold
+        );
+        estimatedOutput = Number(tokensOut.toString()) / tokenUnit;
+        estimatedFee = fee.toNumber();
+      } else {
+        const { solOut, fee } = calculateSolOut(curve, input, feeBps);
+        estimatedOutput = solOut.toNumber() / 1e9;
+        estimatedFee = fee.toNumber();
+      }
+      priceImpact = calculatePriceImpact(
+        curve,
+        input,
+        feeBps,
+        migrationThreshold,
+        mode === "buy"
+      );
+    } catch {
+      // More than the curve can fill, leave the estimate empty
+    }
   }
 
   const handleTrade = async () => {
@@ -182,12 +199,9 @@ export function TradingPanel({ token }: TradingPanelProps) {
               </span>
             </div>
             <div className="flex justify-between text-sm">
-              <span className="text-dark-400">Fee (1%)</span>
+              <span className="text-dark-400">Fee ({feeBps / 100}%)</span>
               <span className="font-mono text-dark-300">
-                {mode === "buy"
-                  ? formatSol((inputAmount * 0.01 * 1e9).toString())
-                  : formatNumber(inputAmount * 0.01)}{" "}
-                {mode === "buy" ? "SOL" : token.symbol}
+                {formatSol(estimatedFee)} SOL
               </span>
             </div>
           </div>
@@ -0,0 +1,28 @@
+"use client";
+
+import { useQuery } from "@tanstack/react-query";
+import { useConnection } from "@solana/wallet-adapter-react";
+import { PublicKey } from "@solana/web3.js";
+import { OpenClawClient } from "@claw/sdk";
+import { OPENCLAW_PROGRAM_ID } from "@/lib/constants";
+
+/**
+ * A token's bonding curve and the global config it trades under, read from chain
+ */
+export function useCurve(mint: string) {
+  const { connection } = useConnection();
+
+  return useQuery({
+    queryKey: ["curve", mint],
+    queryFn: async () => {
+      const client = new OpenClawClient(connection, OPENCLAW_PROGRAM_ID);
+      const [config, curve] = await Promise.all([
+        client.getGlobalConfig(),
+        client.getBondingCurve(new PublicKey(mint)),
+      ]);
+      if (!config || !curve) throw new Error("Bonding curve not found");
+      return { config, curve };
+    },
+    enabled: !!mint,
+  });
+}
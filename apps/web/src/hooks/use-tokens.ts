@@ -12,6 +12,7 @@ export interface Token {
   image?: string;
   creator: string;
   bondingCurve: string;
+  decimals: number;
   virtualSolReserves: string;
   virtualTokenReserves: string;
   realSolReserves: string;
@@ -1,21 +1,12 @@
 import { PublicKey } from "@solana/web3.js";
 
-// Platform Configuration
-export const PLATFORM_FEE_BPS = 100; // 1%
-export const MIGRATION_THRESHOLD_SOL = 85; // ~$69K at $800/SOL
-
-// Token Configuration
-export const TOTAL_TOKEN_SUPPLY = BigInt(1_000_000_000) * BigInt(10 ** 6); // 1 billion with 6 decimals
-export const INITIAL_VIRTUAL_SOL_RESERVES = BigInt(30) * BigInt(10 ** 9); // 30 SOL in lamports
-export const INITIAL_VIRTUAL_TOKEN_RESERVES = TOTAL_TOKEN_SUPPLY;
-
 // Solana
 export const SOLANA_RPC_URL = process.env.NEXT_PUBLIC_SOLANA_RPC_URL || "https://api.mainnet-beta.solana.com";
 export const SOLANA_WS_URL = process.env.NEXT_PUBLIC_SOLANA_WS_URL || "wss://api.mainnet-beta.solana.com";
 
 // Program IDs (update after deployment)
 export const OPENCLAW_PROGRAM_ID = new PublicKey(
-  process.env.NEXT_PUBLIC_OPENCLAW_PROGRAM_ID || "11111111111111111111111111111111"
+  process.env.NEXT_PUBLIC_OPENCLAW_PROGRAM_ID || "CLAWMASi45ub7Qe4ZE36UT5G6cU4ud8Fhhe4deS4F3cw"
 );
 
 // Token Program
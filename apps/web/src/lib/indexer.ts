@@ -1,10 +1,18 @@
 import { Connection, PublicKey, Logs, Context } from "@solana/web3.js";
 import { prisma } from "./db";
 import { publishPriceUpdate, publishTradeUpdate, publishNewToken } from "./websocket-server";
+import {
+  OpenClawClient,
+  TradeEvent,
+  calculateMarketCap,
+  calculatePrice,
+  parseTradeEvents,
+} from "@claw/sdk";
+import { BN } from "@coral-xyz/anchor";
 import { SOLANA_RPC_URL, SOLANA_WS_URL } from "./constants";
 
 const PROGRAM_ID = new PublicKey(
-  process.env.NEXT_PUBLIC_OPENCLAW_PROGRAM_ID || "11111111111111111111111111111111"
+  process.env.NEXT_PUBLIC_OPENCLAW_PROGRAM_ID || "CLAWMASi45ub7Qe4ZE36UT5G6cU4ud8Fhhe4deS4F3cw"
 );
 
 let connection: Connection | null = null;
@@ -56,14 +64,20 @@ async function processLogs(logs: Logs, ctx: Context) {
     // Parse instruction logs
     if (log.includes("Instruction: CreateToken")) {
       await processTokenCreation(signature, logs.logs);
-    } else if (log.includes("Buy:")) {
-      await processBuy(signature, log);
-    } else if (log.includes("Sell:")) {
-      await processSell(signature, log);
     } else if (log.includes("Token") && log.includes("migrated")) {
       await processMigration(signature, log);
     }
   }
+
+  // Trades come from the program's `Trade` events, whose layout doesn't change with the
+  // wording of its log messages
+  for (const event of parseTradeEvents(logs.logs)) {
+    if (event.isBuy) {
+      await processBuy(signature, event);
+    } else {
+      await processSell(signature, event);
+    }
+  }
 }
 
 async function processTokenCreation(signature: string, logs: string[]) {
@@ -103,6 +117,9 @@ async function processTokenCreation(signature: string, logs: string[]) {
       PROGRAM_ID
     );
 
+    const snapshot = await curveSnapshot(new PublicKey(mint));
+    if (!snapshot) return;
+
     // Create token in database
     const token = await prisma.token.create({
       data: {
@@ -111,6 +128,9 @@ async function processTokenCreation(signature: string, logs: string[]) {
         symbol,
         creator,
         bondingCurve: bondingCurve.toString(),
+        decimals: snapshot.decimals,
+        ...snapshot.reserves,
+        marketCapSol: snapshot.marketCapSol,
       },
     });
 
@@ -128,7 +148,7 @@ async function processTokenCreation(signature: string, logs: string[]) {
         name,
         symbol,
         creator,
-        marketCapSol: 0,
+        marketCapSol: snapshot.marketCapSol,
       },
     });
 
@@ -138,36 +158,12 @@ async function processTokenCreation(signature: string, logs: string[]) {
   }
 }
 
-async function processBuy(signature: string, log: string) {
+async function processBuy(signature: string, event: TradeEvent) {
   try {
-    // Log format: "Buy: {solAmount} lamports -> {tokenAmount} tokens (fee: {fee} lamports)"
-    const match = log.match(/Buy: (\d+) lamports -> (\d+) tokens \(fee: (\d+) lamports\)/);
-    if (!match) return;
-
-    const [, solAmount, tokenAmount, fee] = match;
-
-    // Get transaction details
-    if (!connection) return;
-
-    const tx = await connection.getTransaction(signature, {
-      maxSupportedTransactionVersion: 0,
-    });
-
-    if (!tx) return;
-
-    const accountKeys = tx.transaction.message.staticAccountKeys;
-    const trader = accountKeys[0].toString();
-
-    // Find the token mint from accounts
-    // Account layout: [buyer, config, bondingCurve, curveTokenVault, buyerTokenAccount, ...]
-    const buyerTokenAccount = accountKeys[4];
-
-    // Get token info from ATA
-    const ataInfo = await connection.getAccountInfo(buyerTokenAccount);
-    if (!ataInfo) return;
-
-    // Parse ATA to get mint (bytes 0-32)
-    const mint = new PublicKey(ataInfo.data.subarray(0, 32)).toString();
+    const mint = event.mint.toString();
+    const trader = event.user.toString();
+    const solAmount = event.solAmount.toString();
+    const tokenAmount = event.tokenAmount.toString();
 
     // Get token from database
     const token = await prisma.token.findUnique({
@@ -198,24 +194,14 @@ async function processBuy(signature: string, log: string) {
       },
     });
 
-    // Update token reserves
-    const updatedToken = await prisma.token.update({
-      where: { mint },
-      data: {
-        virtualSolReserves: { increment: solAmountNum - BigInt(fee) },
-        virtualTokenReserves: { decrement: tokenAmountNum },
-        realSolReserves: { increment: solAmountNum - BigInt(fee) },
-        realTokenReserves: { decrement: tokenAmountNum },
-      },
-    });
-
-    // Calculate new market cap
-    const newPrice = Number(updatedToken.virtualSolReserves) / Number(updatedToken.virtualTokenReserves);
-    const marketCapSol = (newPrice * 1e15) / 1e9; // Total supply is 1B tokens
+    // Mirror the curve as the trade left it
+    const snapshot = await curveSnapshot(event.mint);
+    if (!snapshot) return;
+    const { newPrice, marketCapSol } = snapshot;
 
-    await prisma.token.update({
+    const updatedToken = await prisma.token.update({
       where: { mint },
-      data: { marketCapSol },
+      data: { ...snapshot.reserves, marketCapSol },
     });
 
     // Update platform stats
@@ -256,32 +242,12 @@ async function processBuy(signature: string, log: string) {
   }
 }
 
-async function processSell(signature: string, log: string) {
+async function processSell(signature: string, event: TradeEvent) {
   try {
-    // Log format: "Sell: {tokenAmount} tokens -> {solAmount} lamports (fee: {fee} lamports)"
-    const match = log.match(/Sell: (\d+) tokens -> (\d+) lamports \(fee: (\d+) lamports\)/);
-    if (!match) return;
-
-    const [, tokenAmount, solAmount, fee] = match;
-
-    // Get transaction details
-    if (!connection) return;
-
-    const tx = await connection.getTransaction(signature, {
-      maxSupportedTransactionVersion: 0,
-    });
-
-    if (!tx) return;
-
-    const accountKeys = tx.transaction.message.staticAccountKeys;
-    const trader = accountKeys[0].toString();
-
-    // Find the token mint from accounts
-    const sellerTokenAccount = accountKeys[4];
-    const ataInfo = await connection.getAccountInfo(sellerTokenAccount);
-    if (!ataInfo) return;
-
-    const mint = new PublicKey(ataInfo.data.subarray(0, 32)).toString();
+    const mint = event.mint.toString();
+    const trader = event.user.toString();
+    const solAmount = event.solAmount.toString();
+    const tokenAmount = event.tokenAmount.toString();
 
     // Get token from database
     const token = await prisma.token.findUnique({
@@ -295,7 +261,7 @@ async function processSell(signature: string, log: string) {
 
     const solAmountNum = BigInt(solAmount);
     const tokenAmountNum = BigInt(tokenAmount);
-    const feeNum = BigInt(fee);
+    const feeNum = BigInt(event.fee.add(event.creatorFee).toString());
     const price = Number(solAmountNum) / Number(tokenAmountNum);
 
     // Create trade record
@@ -312,24 +278,14 @@ async function processSell(signature: string, log: string) {
       },
     });
 
-    // Update token reserves
-    const updatedToken = await prisma.token.update({
-      where: { mint },
-      data: {
-        virtualSolReserves: { decrement: solAmountNum + feeNum },
-        virtualTokenReserves: { increment: tokenAmountNum },
-        realSolReserves: { decrement: solAmountNum + feeNum },
-        realTokenReserves: { increment: tokenAmountNum },
-      },
-    });
-
-    // Calculate new market cap
-    const newPrice = Number(updatedToken.virtualSolReserves) / Number(updatedToken.virtualTokenReserves);
-    const marketCapSol = (newPrice * 1e15) / 1e9;
+    // Mirror the curve as the trade left it
+    const snapshot = await curveSnapshot(event.mint);
+    if (!snapshot) return;
+    const { newPrice, marketCapSol } = snapshot;
 
-    await prisma.token.update({
+    const updatedToken = await prisma.token.update({
       where: { mint },
-      data: { marketCapSol },
+      data: { ...snapshot.reserves, marketCapSol },
     });
 
     // Update platform stats
@@ -397,3 +353,31 @@ async function processMigration(signature: string, log: string) {
     console.error("Error processing migration:", error);
   }
 }
+
+/**
+ * A token's curve as it is on-chain, with its spot price (in lamports per base unit, like
+ * trade prices) and market cap (in SOL) over the mint's supply
+ */
+async function curveSnapshot(mint: PublicKey) {
+  if (!connection) return null;
+
+  const client = new OpenClawClient(connection, PROGRAM_ID);
+  const [curve, supply] = await Promise.all([
+    client.getBondingCurve(mint),
+    connection.getTokenSupply(mint),
+  ]);
+  if (!curve) return null;
+
+  const { decimals, amount } = supply.value;
+  return {
+    decimals,
+    reserves: {
+      virtualSolReserves: BigInt(curve.virtualSolReserves.toString()),
+      virtualTokenReserves: BigInt(curve.virtualTokenReserves.toString()),
+      realSolReserves: BigInt(curve.realSolReserves.toString()),
+      realTokenReserves: BigInt(curve.realTokenReserves.toString()),
+    },
+    newPrice: (calculatePrice(curve, decimals) * 1e9) / Math.pow(10, decimals),
+    marketCapSol: calculateMarketCap(curve, new BN(amount)),
+  };
+}
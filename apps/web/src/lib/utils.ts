@@ -61,20 +61,3 @@ export function sleep(ms: number): Promise<void> {
 export function isValidSolanaAddress(address: string): boolean {
   return /^[1-9A-HJ-NP-Za-km-z]{32,44}$/.test(address);
 }
-
-export function calculatePrice(
-  virtualSolReserves: bigint,
-  virtualTokenReserves: bigint
-): number {
-  if (virtualTokenReserves === BigInt(0)) return 0;
-  return Number(virtualSolReserves) / Number(virtualTokenReserves);
-}
-
-export function calculateMarketCap(
-  virtualSolReserves: bigint,
-  virtualTokenReserves: bigint,
-  totalSupply: bigint
-): number {
-  const price = calculatePrice(virtualSolReserves, virtualTokenReserves);
-  return price * Number(totalSupply);
-}
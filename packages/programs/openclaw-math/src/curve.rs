@@ -0,0 +1,128 @@
+use crate::{to_u64, MathError, Result, CURVE_PRICE_SCALE, MAX_DOUBLINGS};
+
+/// Price dynamics of a bonding curve
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Curve {
+    /// x * y = k over the virtual reserves
+    #[default]
+    ConstantProduct,
+    /// Price rises in a straight line from `start_price` to `end_price` as the supply sells
+    Linear { start_price: u64, end_price: u64 },
+    /// Price starts at `start_price` and doubles `doublings` times as the supply sells,
+    /// rising linearly between doublings
+    Exponential { start_price: u64, doublings: u8 },
+}
+
+impl Curve {
+    /// Check the parameters describe a rising price
+    pub fn validate(&self) -> Result<()> {
+        let valid = match *self {
+            Curve::ConstantProduct => true,
+            Curve::Linear {
+                start_price,
+                end_price,
+            } => start_price > 0 && end_price >= start_price,
+            Curve::Exponential {
+                start_price,
+                doublings,
+            } => start_price > 0 && doublings > 0 && doublings <= MAX_DOUBLINGS,
+        };
+        if !valid {
+            return Err(MathError::InvalidCurve);
+        }
+        Ok(())
+    }
+
+    /// Whether prices follow the tokens sold out of `supply` rather than virtual reserves
+    pub fn is_supply_based(&self) -> bool {
+        !matches!(self, Curve::ConstantProduct)
+    }
+
+    /// Lamports paid into the curve for the first `sold` tokens of `supply`, rounded down.
+    /// Non-decreasing in `sold`, so trading between two points always costs the difference.
+    pub fn cost(&self, supply: u64, sold: u64) -> Result<u64> {
+        let (numerator, denominator) = match *self {
+            Curve::ConstantProduct => return Err(MathError::InvalidCurve),
+            Curve::Linear {
+                start_price,
+                end_price,
+            } => {
+                // start * s + (end - start) * s^2 / 2S
+                if supply == 0 {
+                    return Err(MathError::InsufficientLiquidity);
+                }
+                let supply = supply as u128;
+                let sold = sold as u128;
+                let numerator = (start_price as u128)
+                    .checked_mul(2 * supply)
+                    .and_then(|v| v.checked_mul(sold))
+                    .and_then(|v| {
+                        (end_price.saturating_sub(start_price) as u128)
+                            .checked_mul(sold * sold)
+                            .and_then(|w| v.checked_add(w))
+                    });
+                (numerator, 2 * supply * CURVE_PRICE_SCALE)
+            }
+            Curve::Exponential {
+                start_price,
+                doublings,
+            } => {
+                // Full segments: 1.5 * d * start * (2^k - 1)
+                // Current segment: start * 2^k * (t + t^2 / 2d)
+                let (step, k, t) = exponential_segment(supply, doublings, sold)?;
+                let numerator = 1u128.checked_shl(k).and_then(|pow| {
+                    let full = step
+                        .checked_mul(step)?
+                        .checked_mul(3)?
+                        .checked_mul(pow - 1)?;
+                    let partial = step
+                        .checked_mul(2 * t)?
+                        .checked_add(t * t)?
+                        .checked_mul(pow)?;
+                    full.checked_add(partial)?.checked_mul(start_price as u128)
+                });
+                (numerator, 2 * step * CURVE_PRICE_SCALE)
+            }
+        };
+        to_u64(numerator.ok_or(MathError::Overflow)? / denominator)
+    }
+
+    /// Marginal price after `sold` tokens of `supply`, scaled by `CURVE_PRICE_SCALE`
+    pub fn price(&self, supply: u64, sold: u64) -> Result<u128> {
+        let price = match *self {
+            Curve::ConstantProduct => return Err(MathError::InvalidCurve),
+            Curve::Linear {
+                start_price,
+                end_price,
+            } => {
+                if supply == 0 {
+                    return Err(MathError::InsufficientLiquidity);
+                }
+                (end_price.saturating_sub(start_price) as u128 * sold as u128 / supply as u128)
+                    .checked_add(start_price as u128)
+            }
+            Curve::Exponential {
+                start_price,
+                doublings,
+            } => {
+                let (step, k, t) = exponential_segment(supply, doublings, sold)?;
+                1u128
+                    .checked_shl(k)
+                    .and_then(|pow| (start_price as u128).checked_mul(pow))
+                    .and_then(|p| p.checked_mul(step + t))
+                    .map(|p| p / step)
+            }
+        };
+        price.ok_or(MathError::Overflow)
+    }
+}
+
+/// Segment length, index and offset within it of `sold` on an exponential curve
+fn exponential_segment(supply: u64, doublings: u8, sold: u64) -> Result<(u128, u32, u128)> {
+    let step = supply / doublings.max(1) as u64;
+    if step == 0 {
+        return Err(MathError::InvalidCurve);
+    }
+    let k = u32::try_from(sold / step).map_err(|_| MathError::Overflow)?;
+    Ok((step as u128, k, (sold % step) as u128))
+}
@@ -0,0 +1,32 @@
+use crate::{to_u64, MathError, Result, BPS_DENOMINATOR};
+
+/// Fee in lamports on `amount`, rounded down
+pub fn fee_on(amount: u64, fee_bps: u16) -> Result<u64> {
+    if fee_bps as u64 > BPS_DENOMINATOR {
+        return Err(MathError::InvalidFee);
+    }
+    to_u64(amount as u128 * fee_bps as u128 / BPS_DENOMINATOR as u128)
+}
+
+/// Smallest amount that still leaves `net` after the fee is taken out of it.
+/// The fee is whatever is left over, so rounding always goes to the protocol.
+pub fn gross_up(net: u64, fee_bps: u16) -> Result<u64> {
+    if fee_bps as u64 >= BPS_DENOMINATOR {
+        return Err(MathError::InvalidFee);
+    }
+    let denominator = BPS_DENOMINATOR as u128 - fee_bps as u128;
+    to_u64((net as u128 * BPS_DENOMINATOR as u128).div_ceil(denominator))
+}
+
+/// Split a fee charged at `platform_bps + creator_bps` into the platform's and the
+/// creator's share. Rounding leftovers go to the platform.
+pub fn split_fee(fee: u64, platform_bps: u16, creator_bps: u16) -> Result<(u64, u64)> {
+    let total_bps = platform_bps
+        .checked_add(creator_bps)
+        .ok_or(MathError::Overflow)?;
+    if total_bps == 0 {
+        return Ok((fee, 0));
+    }
+    let creator_fee = to_u64(fee as u128 * creator_bps as u128 / total_bps as u128)?;
+    Ok((fee - creator_fee, creator_fee))
+}
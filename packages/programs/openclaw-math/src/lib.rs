@@ -0,0 +1,42 @@
+//! Bonding curve math for OpenClaw.
+//!
+//! This is the one implementation of the curve: the program prices trades with it and the
+//! web app runs the same code compiled to WASM through `openclaw-math-wasm`. It has no
+//! Solana or Anchor dependencies and builds without `std`.
+#![no_std]
+
+mod curve;
+mod fees;
+mod state;
+
+pub use curve::Curve;
+pub use fees::{fee_on, gross_up, split_fee};
+pub use state::CurveState;
+
+/// Prices of supply-based curves are lamports per base unit, scaled by this
+pub const CURVE_PRICE_SCALE: u128 = 1_000_000_000;
+
+/// Most times an exponential curve may double its price over its supply
+pub const MAX_DOUBLINGS: u8 = 16;
+
+/// Basis points in a whole
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Why a curve calculation failed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MathError {
+    /// A result didn't fit its type
+    Overflow,
+    /// The curve can't fill the trade
+    InsufficientLiquidity,
+    /// The curve parameters can't price anything
+    InvalidCurve,
+    /// A fee above 100%
+    InvalidFee,
+}
+
+pub type Result<T> = core::result::Result<T, MathError>;
+
+pub(crate) fn to_u64(value: u128) -> Result<u64> {
+    u64::try_from(value).map_err(|_| MathError::Overflow)
+}
@@ -0,0 +1,294 @@
+use crate::fees::{fee_on, gross_up};
+use crate::{to_u64, Curve, MathError, Result, BPS_DENOMINATOR, CURVE_PRICE_SCALE};
+
+/// Snapshot of a bonding curve's reserves, enough to price any trade on it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CurveState {
+    /// Price dynamics
+    pub curve: Curve,
+    /// Virtual SOL reserves (in lamports)
+    pub virtual_sol_reserves: u64,
+    /// Virtual token reserves
+    pub virtual_token_reserves: u64,
+    /// SOL paid into the curve (in lamports)
+    pub real_sol_reserves: u64,
+    /// Tokens still for sale
+    pub real_token_reserves: u64,
+    /// Tokens sold so far
+    pub tokens_sold: u64,
+}
+
+impl CurveState {
+    /// Tokens the curve sells in total: those sold so far plus those still for sale
+    pub fn curve_supply(&self) -> Result<u64> {
+        self.tokens_sold
+            .checked_add(self.real_token_reserves)
+            .ok_or(MathError::Overflow)
+    }
+
+    /// Check the curve can price its whole supply without overflowing
+    pub fn check(&self) -> Result<()> {
+        self.curve.validate()?;
+        if self.curve.is_supply_based() {
+            self.curve
+                .cost(self.curve_supply()?, self.curve_supply()?)?;
+        }
+        Ok(())
+    }
+
+    /// Current price in lamports per whole token of `decimals` decimals
+    pub fn token_price(&self, decimals: u8) -> Result<u64> {
+        let unit = 10u128
+            .checked_pow(decimals as u32)
+            .ok_or(MathError::Overflow)?;
+        if self.curve.is_supply_based() {
+            let price = self.curve.price(self.curve_supply()?, self.tokens_sold)?;
+            let price = price.checked_mul(unit).ok_or(MathError::Overflow)?;
+            return to_u64(price / CURVE_PRICE_SCALE);
+        }
+        if self.virtual_token_reserves == 0 {
+            return Ok(0);
+        }
+        // Price = virtual_sol / virtual_tokens (in lamports per token)
+        let price = (self.virtual_sol_reserves as u128)
+            .checked_mul(unit)
+            .ok_or(MathError::Overflow)?;
+        to_u64(price / self.virtual_token_reserves as u128)
+    }
+
+    /// Marginal price in lamports per base unit, scaled by `CURVE_PRICE_SCALE`
+    pub fn spot_price(&self) -> Result<u128> {
+        if self.curve.is_supply_based() {
+            return self.curve.price(self.curve_supply()?, self.tokens_sold);
+        }
+        if self.virtual_token_reserves == 0 {
+            return Ok(0);
+        }
+        (self.virtual_sol_reserves as u128)
+            .checked_mul(CURVE_PRICE_SCALE)
+            .map(|p| p / self.virtual_token_reserves as u128)
+            .ok_or(MathError::Overflow)
+    }
+
+    /// Value of `total_supply` base units at the current spot price (in lamports)
+    pub fn market_cap(&self, total_supply: u64) -> Result<u64> {
+        let value = self
+            .spot_price()?
+            .checked_mul(total_supply as u128)
+            .ok_or(MathError::Overflow)?;
+        to_u64(value / CURVE_PRICE_SCALE)
+    }
+
+    /// How far the real SOL reserves are towards `threshold`, in basis points capped at
+    /// 100%
+    pub fn migration_progress_bps(&self, threshold: u64) -> u16 {
+        if threshold == 0 {
+            return BPS_DENOMINATOR as u16;
+        }
+        let progress = self.real_sol_reserves as u128 * BPS_DENOMINATOR as u128 / threshold as u128;
+        progress.min(BPS_DENOMINATOR as u128) as u16
+    }
+
+    /// Tokens out and fee for `sol_in` lamports, fee included
+    pub fn get_tokens_out(&self, sol_in: u64, fee_bps: u16) -> Result<(u64, u64)> {
+        let fee = fee_on(sol_in, fee_bps)?;
+        let tokens_out = self.tokens_for_sol_in(sol_in - fee)?;
+
+        Ok((tokens_out, fee))
+    }
+
+    /// Like `get_tokens_out`, but only charges as much of `sol_in` as is needed to take
+    /// the real SOL reserves up to `threshold`. Returns the SOL charged, fee and tokens out.
+    pub fn get_capped_buy(
+        &self,
+        sol_in: u64,
+        fee_bps: u16,
+        threshold: u64,
+    ) -> Result<(u64, u64, u64)> {
+        let remaining = threshold.saturating_sub(self.real_sol_reserves);
+        let fee = fee_on(sol_in, fee_bps)?;
+        if sol_in - fee <= remaining {
+            let tokens_out = self.tokens_for_sol_in(sol_in - fee)?;
+            return Ok((sol_in, fee, tokens_out));
+        }
+
+        let capped_sol_in = gross_up(remaining, fee_bps)?;
+        let tokens_out = self.tokens_for_sol_in(remaining)?;
+        Ok((capped_sol_in, capped_sol_in - remaining, tokens_out))
+    }
+
+    /// SOL out after fees and the fee for selling `tokens_in`
+    pub fn get_sol_out(&self, tokens_in: u64, fee_bps: u16) -> Result<(u64, u64)> {
+        let sol_out_gross = self.sol_for_tokens_in(tokens_in)?;
+
+        let fee = fee_on(sol_out_gross, fee_bps)?;
+        Ok((sol_out_gross - fee, fee))
+    }
+
+    /// SOL (fee included) needed to buy exactly `tokens_out`, and the fee
+    pub fn get_sol_in(&self, tokens_out: u64, fee_bps: u16) -> Result<(u64, u64)> {
+        let sol_after_fee = self.sol_for_tokens_out(tokens_out)?;
+
+        let sol_in = gross_up(sol_after_fee, fee_bps)?;
+        Ok((sol_in, sol_in - sol_after_fee))
+    }
+
+    /// Tokens needed to receive exactly `sol_out` after fees, and the fee
+    pub fn get_tokens_in(&self, sol_out: u64, fee_bps: u16) -> Result<(u64, u64)> {
+        let sol_out_gross = gross_up(sol_out, fee_bps)?;
+        let tokens_in = self.tokens_for_sol_out(sol_out_gross)?;
+
+        Ok((tokens_in, sol_out_gross - sol_out))
+    }
+
+    /// Move `sol_in` (fees excluded) into and `tokens_out` out of the reserves
+    pub fn apply_buy(&mut self, sol_in: u64, tokens_out: u64) -> Result<()> {
+        self.virtual_sol_reserves = checked_add(self.virtual_sol_reserves, sol_in)?;
+        self.virtual_token_reserves = checked_sub(self.virtual_token_reserves, tokens_out)?;
+        self.real_sol_reserves = checked_add(self.real_sol_reserves, sol_in)?;
+        self.real_token_reserves = checked_sub(self.real_token_reserves, tokens_out)?;
+        self.tokens_sold = checked_add(self.tokens_sold, tokens_out)?;
+        Ok(())
+    }
+
+    /// Move `tokens_in` into and `sol_out` (fees included) out of the reserves
+    pub fn apply_sell(&mut self, tokens_in: u64, sol_out: u64) -> Result<()> {
+        self.virtual_sol_reserves = checked_sub(self.virtual_sol_reserves, sol_out)?;
+        self.virtual_token_reserves = checked_add(self.virtual_token_reserves, tokens_in)?;
+        self.real_sol_reserves = checked_sub(self.real_sol_reserves, sol_out)?;
+        self.real_token_reserves = checked_add(self.real_token_reserves, tokens_in)?;
+        self.tokens_sold = checked_sub(self.tokens_sold, tokens_in)?;
+        Ok(())
+    }
+
+    /// Tokens bought with `sol_in` lamports after fees
+    pub fn tokens_for_sol_in(&self, sol_in: u64) -> Result<u64> {
+        if self.curve.is_supply_based() {
+            // Walk up the curve as far as the SOL pays for
+            let supply = self.curve_supply()?;
+            let budget = self.cost_at(self.tokens_sold)?.saturating_add(sol_in);
+            if budget > self.cost_at(supply)? {
+                return Err(MathError::InsufficientLiquidity);
+            }
+            let sold = self.max_sold_within(self.tokens_sold, supply, budget)?;
+            return Ok(sold - self.tokens_sold);
+        }
+        self.check_reserves()?;
+
+        // Constant product: k = x * y
+        // new_y = k / new_x, rounded up so k never decreases
+        // tokens_out = old_y - new_y
+        let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+        let new_sol = self.virtual_sol_reserves as u128 + sol_in as u128;
+        let new_tokens = k.div_ceil(new_sol);
+        let tokens_out = (self.virtual_token_reserves as u128)
+            .checked_sub(new_tokens)
+            .ok_or(MathError::Overflow)?;
+
+        to_u64(tokens_out)
+    }
+
+    /// Lamports (before fees) that selling `tokens_in` takes out of the curve
+    pub fn sol_for_tokens_in(&self, tokens_in: u64) -> Result<u64> {
+        if self.curve.is_supply_based() {
+            let sold = self
+                .tokens_sold
+                .checked_sub(tokens_in)
+                .ok_or(MathError::InsufficientLiquidity)?;
+            return Ok(self.cost_at(self.tokens_sold)? - self.cost_at(sold)?);
+        }
+        self.check_reserves()?;
+
+        // Constant product: k = x * y
+        // new_x = k / new_y, rounded up so k never decreases
+        // sol_out = old_x - new_x
+        let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+        let new_tokens = self.virtual_token_reserves as u128 + tokens_in as u128;
+        let new_sol = k.div_ceil(new_tokens);
+        let sol_out = (self.virtual_sol_reserves as u128)
+            .checked_sub(new_sol)
+            .ok_or(MathError::Overflow)?;
+
+        to_u64(sol_out)
+    }
+
+    /// Lamports (after fees) needed to buy exactly `tokens_out`
+    pub fn sol_for_tokens_out(&self, tokens_out: u64) -> Result<u64> {
+        if self.curve.is_supply_based() {
+            if tokens_out > self.real_token_reserves {
+                return Err(MathError::InsufficientLiquidity);
+            }
+            let sold = checked_add(self.tokens_sold, tokens_out)?;
+            return Ok(self.cost_at(sold)? - self.cost_at(self.tokens_sold)?);
+        }
+        self.check_reserves()?;
+        if tokens_out >= self.virtual_token_reserves {
+            return Err(MathError::InsufficientLiquidity);
+        }
+
+        // Inverse of tokens_for_sol_in: new_x = k / new_y, rounded up
+        let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+        let new_tokens = (self.virtual_token_reserves - tokens_out) as u128;
+        let new_sol = k.div_ceil(new_tokens);
+
+        to_u64(new_sol - self.virtual_sol_reserves as u128)
+    }
+
+    /// Tokens that must be sold to take `sol_out` lamports (before fees) out of the curve
+    pub fn tokens_for_sol_out(&self, sol_out: u64) -> Result<u64> {
+        if self.curve.is_supply_based() {
+            // Walk down the curve until it has paid out enough
+            let target = self
+                .cost_at(self.tokens_sold)?
+                .checked_sub(sol_out)
+                .ok_or(MathError::InsufficientLiquidity)?;
+            let sold = self.max_sold_within(0, self.tokens_sold, target)?;
+            return Ok(self.tokens_sold - sold);
+        }
+        self.check_reserves()?;
+        if sol_out >= self.virtual_sol_reserves {
+            return Err(MathError::InsufficientLiquidity);
+        }
+
+        // Inverse of sol_for_tokens_in: new_y = k / new_x, rounded up
+        let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
+        let new_sol = (self.virtual_sol_reserves - sol_out) as u128;
+        let new_tokens = k.div_ceil(new_sol);
+
+        to_u64(new_tokens - self.virtual_token_reserves as u128)
+    }
+
+    fn cost_at(&self, sold: u64) -> Result<u64> {
+        self.curve.cost(self.curve_supply()?, sold)
+    }
+
+    /// Largest number of tokens sold in `low..=high` whose cost is within `budget`,
+    /// given the cost at `low` already is
+    fn max_sold_within(&self, mut low: u64, mut high: u64, budget: u64) -> Result<u64> {
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if self.cost_at(mid)? <= budget {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        Ok(low)
+    }
+
+    /// An empty side of the curve would price the other side at nothing
+    fn check_reserves(&self) -> Result<()> {
+        if self.virtual_sol_reserves == 0 || self.virtual_token_reserves == 0 {
+            return Err(MathError::InsufficientLiquidity);
+        }
+        Ok(())
+    }
+}
+
+fn checked_add(a: u64, b: u64) -> Result<u64> {
+    a.checked_add(b).ok_or(MathError::Overflow)
+}
+
+fn checked_sub(a: u64, b: u64) -> Result<u64> {
+    a.checked_sub(b).ok_or(MathError::Overflow)
+}
@@ -0,0 +1,131 @@
+use openclaw_math::{fee_on, gross_up, split_fee, Curve, CurveState, MathError, CURVE_PRICE_SCALE};
+
+const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+const INITIAL_VIRTUAL_TOKENS: u64 = 1_073_000_000_000_000;
+
+fn constant_product() -> CurveState {
+    CurveState {
+        curve: Curve::ConstantProduct,
+        virtual_sol_reserves: 30 * LAMPORTS_PER_SOL,
+        virtual_token_reserves: INITIAL_VIRTUAL_TOKENS,
+        real_sol_reserves: 0,
+        real_token_reserves: INITIAL_VIRTUAL_TOKENS,
+        tokens_sold: 0,
+    }
+}
+
+fn linear() -> CurveState {
+    CurveState {
+        curve: Curve::Linear {
+            start_price: 27_958,
+            end_price: 279_580,
+        },
+        ..constant_product()
+    }
+}
+
+#[test]
+fn token_price_scales_with_decimals() {
+    for state in [constant_product(), linear()] {
+        assert_eq!(state.token_price(6).unwrap(), 27);
+        assert_eq!(state.token_price(9).unwrap(), 27_958);
+    }
+    assert_eq!(constant_product().token_price(u8::MAX), Err(MathError::Overflow));
+}
+
+#[test]
+fn quotes_invert_each_other() {
+    for state in [constant_product(), linear()] {
+        let (tokens_out, fee) = state.get_tokens_out(LAMPORTS_PER_SOL, 100).unwrap();
+        assert_eq!(fee, LAMPORTS_PER_SOL / 100);
+
+        // Buying the same tokens back costs no more than was paid
+        let (sol_in, _) = state.get_sol_in(tokens_out, 100).unwrap();
+        assert!(sol_in <= LAMPORTS_PER_SOL);
+
+        let mut after = state;
+        after.apply_buy(LAMPORTS_PER_SOL - fee, tokens_out).unwrap();
+        assert_eq!(after.tokens_sold, tokens_out);
+
+        // Selling everything back returns no more than went in, and selling for that SOL
+        // exactly pays at least that much
+        let (sol_out, sell_fee) = after.get_sol_out(tokens_out, 100).unwrap();
+        assert!(sol_out + sell_fee <= after.real_sol_reserves);
+        let (tokens_in, _) = after.get_tokens_in(sol_out, 100).unwrap();
+        assert!(after.get_sol_out(tokens_in, 100).unwrap().0 >= sol_out);
+    }
+}
+
+#[test]
+fn market_cap_and_migration_progress() {
+    let state = constant_product();
+    let price = state.spot_price().unwrap();
+    assert_eq!(
+        price,
+        30 * LAMPORTS_PER_SOL as u128 * CURVE_PRICE_SCALE / INITIAL_VIRTUAL_TOKENS as u128
+    );
+    // The whole virtual supply is worth the virtual SOL at the spot price
+    let market_cap = state.market_cap(INITIAL_VIRTUAL_TOKENS).unwrap();
+    assert!(market_cap.abs_diff(30 * LAMPORTS_PER_SOL) < LAMPORTS_PER_SOL / 100);
+
+    let threshold = 85 * LAMPORTS_PER_SOL;
+    assert_eq!(state.migration_progress_bps(threshold), 0);
+    let halfway = CurveState {
+        real_sol_reserves: threshold / 2,
+        ..state
+    };
+    assert_eq!(halfway.migration_progress_bps(threshold), 5_000);
+    let past = CurveState {
+        real_sol_reserves: threshold * 2,
+        ..state
+    };
+    assert_eq!(past.migration_progress_bps(threshold), 10_000);
+}
+
+#[test]
+fn capped_buy_stops_at_threshold() {
+    let state = linear();
+    let threshold = 10 * LAMPORTS_PER_SOL;
+    let (sol_in, fee, _) = state
+        .get_capped_buy(50 * LAMPORTS_PER_SOL, 100, threshold)
+        .unwrap();
+    assert_eq!(sol_in - fee, threshold);
+    assert_eq!(sol_in, gross_up(threshold, 100).unwrap());
+}
+
+#[test]
+fn fees_round_towards_the_protocol() {
+    assert_eq!(fee_on(999, 100).unwrap(), 9);
+    assert_eq!(gross_up(99, 100).unwrap(), 100);
+    assert_eq!(fee_on(1, 10_001), Err(MathError::InvalidFee));
+    assert_eq!(gross_up(1, 10_000), Err(MathError::InvalidFee));
+
+    assert_eq!(split_fee(150, 100, 50).unwrap(), (100, 50));
+    assert_eq!(split_fee(151, 100, 50).unwrap(), (101, 50));
+    assert_eq!(split_fee(7, 0, 0).unwrap(), (7, 0));
+}
+
+#[test]
+fn invalid_curves_are_rejected() {
+    let curves = [
+        Curve::Linear {
+            start_price: 0,
+            end_price: 1,
+        },
+        Curve::Linear {
+            start_price: 2,
+            end_price: 1,
+        },
+        Curve::Exponential {
+            start_price: 1,
+            doublings: 0,
+        },
+    ];
+    for curve in curves {
+        assert_eq!(curve.validate(), Err(MathError::InvalidCurve));
+    }
+    assert_eq!(
+        Curve::ConstantProduct.cost(1, 1),
+        Err(MathError::InvalidCurve)
+    );
+}
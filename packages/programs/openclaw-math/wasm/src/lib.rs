@@ -0,0 +1,175 @@
+//! JavaScript bindings for `openclaw-math`, built with `wasm-pack build --target bundler`
+//! into `pkg/`, which the SDK depends on.
+//! Amounts cross the boundary as `bigint`.
+use openclaw_math::{Curve, CurveState, MathError};
+use wasm_bindgen::prelude::*;
+
+fn js_error(err: MathError) -> JsError {
+    JsError::new(match err {
+        MathError::Overflow => "Math overflow",
+        MathError::InsufficientLiquidity => "Insufficient liquidity",
+        MathError::InvalidCurve => "Invalid curve parameters",
+        MathError::InvalidFee => "Invalid fee",
+    })
+}
+
+/// SOL, token and fee amounts of a quoted trade
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct TradeAmounts {
+    /// Lamports paid in (fee included) on a buy, paid out (fee excluded) on a sell
+    pub sol: u64,
+    /// Tokens bought or sold
+    pub tokens: u64,
+    /// Platform and creator fee together (in lamports)
+    pub fee: u64,
+}
+
+/// A bonding curve as read from its account
+#[wasm_bindgen(js_name = CurveState)]
+pub struct JsCurveState(CurveState);
+
+#[wasm_bindgen(js_class = CurveState)]
+impl JsCurveState {
+    /// A constant-product curve. Use `linear` or `exponential` to switch the curve kind.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        virtual_sol_reserves: u64,
+        virtual_token_reserves: u64,
+        real_sol_reserves: u64,
+        real_token_reserves: u64,
+        tokens_sold: u64,
+    ) -> JsCurveState {
+        JsCurveState(CurveState {
+            curve: Curve::ConstantProduct,
+            virtual_sol_reserves,
+            virtual_token_reserves,
+            real_sol_reserves,
+            real_token_reserves,
+            tokens_sold,
+        })
+    }
+
+    pub fn linear(mut self, start_price: u64, end_price: u64) -> Result<JsCurveState, JsError> {
+        self.0.curve = Curve::Linear {
+            start_price,
+            end_price,
+        };
+        self.0.curve.validate().map_err(js_error)?;
+        Ok(self)
+    }
+
+    pub fn exponential(mut self, start_price: u64, doublings: u8) -> Result<JsCurveState, JsError> {
+        self.0.curve = Curve::Exponential {
+            start_price,
+            doublings,
+        };
+        self.0.curve.validate().map_err(js_error)?;
+        Ok(self)
+    }
+
+    /// Current price in lamports per whole token of `decimals` decimals
+    #[wasm_bindgen(js_name = tokenPrice)]
+    pub fn token_price(&self, decimals: u8) -> Result<u64, JsError> {
+        self.0.token_price(decimals).map_err(js_error)
+    }
+
+    /// Marginal price in lamports per base unit, scaled by 10^9
+    #[wasm_bindgen(js_name = spotPrice)]
+    pub fn spot_price(&self) -> Result<u64, JsError> {
+        let price = self.0.spot_price().map_err(js_error)?;
+        u64::try_from(price).map_err(|_| js_error(MathError::Overflow))
+    }
+
+    #[wasm_bindgen(js_name = marketCap)]
+    pub fn market_cap(&self, total_supply: u64) -> Result<u64, JsError> {
+        self.0.market_cap(total_supply).map_err(js_error)
+    }
+
+    #[wasm_bindgen(js_name = migrationProgressBps)]
+    pub fn migration_progress_bps(&self, threshold: u64) -> u16 {
+        self.0.migration_progress_bps(threshold)
+    }
+
+    /// The curve after a buy that put `sol_in` lamports (fees excluded) in and took
+    /// `tokens_out` out
+    #[wasm_bindgen(js_name = afterBuy)]
+    pub fn after_buy(&self, sol_in: u64, tokens_out: u64) -> Result<JsCurveState, JsError> {
+        let mut state = self.0;
+        state.apply_buy(sol_in, tokens_out).map_err(js_error)?;
+        Ok(JsCurveState(state))
+    }
+
+    /// The curve after a sell that put `tokens_in` in and took `sol_out` lamports (fees
+    /// included) out
+    #[wasm_bindgen(js_name = afterSell)]
+    pub fn after_sell(&self, tokens_in: u64, sol_out: u64) -> Result<JsCurveState, JsError> {
+        let mut state = self.0;
+        state.apply_sell(tokens_in, sol_out).map_err(js_error)?;
+        Ok(JsCurveState(state))
+    }
+
+    /// Buy with `sol_in` lamports, stopping at the migration `threshold` like `buy` does
+    #[wasm_bindgen(js_name = quoteBuy)]
+    pub fn quote_buy(
+        &self,
+        sol_in: u64,
+        fee_bps: u16,
+        threshold: u64,
+    ) -> Result<TradeAmounts, JsError> {
+        let (sol, fee, tokens) = self
+            .0
+            .get_capped_buy(sol_in, fee_bps, threshold)
+            .map_err(js_error)?;
+        Ok(TradeAmounts { sol, tokens, fee })
+    }
+
+    /// Buy exactly `tokens_out`
+    #[wasm_bindgen(js_name = quoteBuyExactTokens)]
+    pub fn quote_buy_exact_tokens(
+        &self,
+        tokens_out: u64,
+        fee_bps: u16,
+    ) -> Result<TradeAmounts, JsError> {
+        let (sol, fee) = self.0.get_sol_in(tokens_out, fee_bps).map_err(js_error)?;
+        Ok(TradeAmounts {
+            sol,
+            tokens: tokens_out,
+            fee,
+        })
+    }
+
+    /// Sell `tokens_in`
+    #[wasm_bindgen(js_name = quoteSell)]
+    pub fn quote_sell(&self, tokens_in: u64, fee_bps: u16) -> Result<TradeAmounts, JsError> {
+        let (sol, fee) = self.0.get_sol_out(tokens_in, fee_bps).map_err(js_error)?;
+        Ok(TradeAmounts {
+            sol,
+            tokens: tokens_in,
+            fee,
+        })
+    }
+
+    /// Sell for exactly `sol_out` lamports after fees
+    #[wasm_bindgen(js_name = quoteSellForExactSol)]
+    pub fn quote_sell_for_exact_sol(
+        &self,
+        sol_out: u64,
+        fee_bps: u16,
+    ) -> Result<TradeAmounts, JsError> {
+        let (tokens, fee) = self.0.get_tokens_in(sol_out, fee_bps).map_err(js_error)?;
+        Ok(TradeAmounts {
+            sol: sol_out,
+            tokens,
+            fee,
+        })
+    }
+}
+
+/// Platform and creator shares of a trade fee
+#[wasm_bindgen(js_name = splitFee)]
+pub fn split_fee(fee: u64, platform_bps: u16, creator_bps: u16) -> Result<Vec<u64>, JsError> {
+    let (platform, creator) =
+        openclaw_math::split_fee(fee, platform_bps, creator_bps).map_err(js_error)?;
+    Ok(vec![platform, creator])
+}
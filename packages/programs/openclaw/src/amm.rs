@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+#[cfg(not(feature = "devnet"))]
+declare_id!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+
+#[cfg(feature = "devnet")]
+declare_id!("CPMDWBwJDtYax9qW7AyRuVC19Cc4L4Vcy4n2BHAbHkCW");
+
+/// Anchor discriminator of the CP-Swap `initialize` instruction
+pub const INITIALIZE_DISCRIMINATOR: [u8; 8] = [175, 175, 109, 31, 13, 152, 155, 237];
+
+/// Raydium CP-Swap (CPMM) program, the DEX curves migrate into
+#[derive(Clone)]
+pub struct Cpmm;
+
+impl Id for Cpmm {
+    fn id() -> Pubkey {
+        ID
+    }
+}
+
+/// Accounts of the CP-Swap `initialize` instruction, in order
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    pub creator: AccountInfo<'info>,
+    pub amm_config: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub token_0_mint: AccountInfo<'info>,
+    pub token_1_mint: AccountInfo<'info>,
+    pub lp_mint: AccountInfo<'info>,
+    pub creator_token_0: AccountInfo<'info>,
+    pub creator_token_1: AccountInfo<'info>,
+    pub creator_lp_token: AccountInfo<'info>,
+    pub token_0_vault: AccountInfo<'info>,
+    pub token_1_vault: AccountInfo<'info>,
+    pub create_pool_fee: AccountInfo<'info>,
+    pub observation_state: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub token_0_program: AccountInfo<'info>,
+    pub token_1_program: AccountInfo<'info>,
+    pub associated_token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub rent: AccountInfo<'info>,
+}
+
+/// Create a pool seeded with `init_amount_0` of token 0 and `init_amount_1` of token 1.
+/// Token 0 must be the mint with the lower address.
+pub fn initialize_pool<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, InitializePool<'info>>,
+    init_amount_0: u64,
+    init_amount_1: u64,
+    open_time: u64,
+) -> Result<()> {
+    let accounts = &ctx.accounts;
+    let mut data = INITIALIZE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&init_amount_0.to_le_bytes());
+    data.extend_from_slice(&init_amount_1.to_le_bytes());
+    data.extend_from_slice(&open_time.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: ctx.program.key(),
+        accounts: vec![
+            AccountMeta::new(accounts.creator.key(), true),
+            AccountMeta::new_readonly(accounts.amm_config.key(), false),
+            AccountMeta::new_readonly(accounts.authority.key(), false),
+            AccountMeta::new(accounts.pool_state.key(), false),
+            AccountMeta::new_readonly(accounts.token_0_mint.key(), false),
+            AccountMeta::new_readonly(accounts.token_1_mint.key(), false),
+            AccountMeta::new(accounts.lp_mint.key(), false),
+            AccountMeta::new(accounts.creator_token_0.key(), false),
+            AccountMeta::new(accounts.creator_token_1.key(), false),
+            AccountMeta::new(accounts.creator_lp_token.key(), false),
+            AccountMeta::new(accounts.token_0_vault.key(), false),
+            AccountMeta::new(accounts.token_1_vault.key(), false),
+            AccountMeta::new(accounts.create_pool_fee.key(), false),
+            AccountMeta::new(accounts.observation_state.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_0_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_1_program.key(), false),
+            AccountMeta::new_readonly(accounts.associated_token_program.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+            AccountMeta::new_readonly(accounts.rent.key(), false),
+        ],
+        data,
+    };
+
+    invoke(&ix, &ToAccountInfos::to_account_infos(&ctx)).map_err(Into::into)
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use openclaw_math::Curve;
+use crate::error::OpenClawError;
+
+pub use openclaw_math::{CURVE_PRICE_SCALE, MAX_DOUBLINGS};
+
+/// Price dynamics of a bonding curve. The math lives in `openclaw_math::Curve`; this is
+/// its on-chain form.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CurveKind {
+    /// x * y = k over the virtual reserves taken from the global config
+    #[default]
+    ConstantProduct,
+    /// Price rises in a straight line from `start_price` to `end_price` as the supply sells
+    Linear { start_price: u64, end_price: u64 },
+    /// Price starts at `start_price` and doubles `doublings` times as the supply sells,
+    /// rising linearly between doublings
+    Exponential { start_price: u64, doublings: u8 },
+}
+
+impl From<CurveKind> for Curve {
+    fn from(kind: CurveKind) -> Self {
+        match kind {
+            CurveKind::ConstantProduct => Curve::ConstantProduct,
+            CurveKind::Linear { start_price, end_price } => Curve::Linear { start_price, end_price },
+            CurveKind::Exponential { start_price, doublings } => {
+                Curve::Exponential { start_price, doublings }
+            }
+        }
+    }
+}
+
+impl CurveKind {
+    pub const LEN: usize = 1 + // variant
+        8 +  // start_price
+        8;   // end_price, the largest variant
+
+    pub fn validate(&self) -> Result<()> {
+        Ok(Curve::from(*self).validate().map_err(OpenClawError::from)?)
+    }
+
+    /// Whether prices follow the tokens sold out of `supply` rather than virtual reserves
+    pub fn is_supply_based(&self) -> bool {
+        Curve::from(*self).is_supply_based()
+    }
+
+    /// Lamports paid into the curve for the first `sold` tokens of `supply`, rounded down.
+    /// Non-decreasing in `sold`, so trading between two points always costs the difference.
+    pub fn cost(&self, supply: u64, sold: u64) -> Result<u64> {
+        Ok(Curve::from(*self).cost(supply, sold).map_err(OpenClawError::from)?)
+    }
+
+    /// Marginal price after `sold` tokens of `supply`, scaled by `CURVE_PRICE_SCALE`
+    pub fn price(&self, supply: u64, sold: u64) -> Result<u128> {
+        Ok(Curve::from(*self).price(supply, sold).map_err(OpenClawError::from)?)
+    }
+}
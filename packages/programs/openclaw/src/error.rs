@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use openclaw_math::MathError;
 
 #[error_code]
 pub enum OpenClawError {
@@ -31,4 +32,108 @@ pub enum OpenClawError {
 
     #[msg("Curve has insufficient liquidity")]
     InsufficientLiquidity,
+
+    #[msg("Token name must be between 1 and 32 bytes")]
+    InvalidTokenName,
+
+    #[msg("Token symbol must be between 1 and 10 bytes")]
+    InvalidTokenSymbol,
+
+    #[msg("Token URI must be at most 200 bytes")]
+    InvalidTokenUri,
+
+    #[msg("SOL vault does not back the curve's reserves")]
+    SolVaultMismatch,
+
+    #[msg("Bonding curve is complete and awaiting migration")]
+    CurveComplete,
+
+    #[msg("Purchase would take the curve past the migration threshold")]
+    MigrationThresholdExceeded,
+
+    #[msg("Invalid curve kind parameters")]
+    InvalidCurveKind,
+
+    #[msg("Curve kind is not allowed by the global config")]
+    CurveKindNotAllowed,
+
+    #[msg("No creator fees to claim")]
+    NoCreatorFees,
+
+    #[msg("Referrer account does not match the referrer wallet")]
+    InvalidReferrer,
+
+    #[msg("No authority transfer is pending")]
+    NoPendingAuthority,
+
+    #[msg("Operation is paused")]
+    Paused,
+
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Platform fee exceeds the maximum")]
+    FeeTooHigh,
+
+    #[msg("Creator fee exceeds the maximum")]
+    CreatorFeeTooHigh,
+
+    #[msg("Referral share exceeds the maximum")]
+    ReferralFeeTooHigh,
+
+    #[msg("Initial virtual reserves must be non-zero")]
+    ZeroVirtualReserves,
+
+    #[msg("Migration fee must be below the migration threshold")]
+    MigrationFeeTooHigh,
+
+    #[msg("Migration threshold is more than an allowed curve can raise")]
+    MigrationThresholdUnreachable,
+
+    #[msg("Account type cannot be upgraded")]
+    UnsupportedAccount,
+
+    #[msg("Account is already on the current layout")]
+    AccountUpToDate,
+
+    #[msg("Invalid launch protection settings")]
+    InvalidLaunchProtection,
+
+    #[msg("Buy exceeds the launch protection limit")]
+    LaunchLimitExceeded,
+
+    #[msg("Buys during the launch window need the buyer's position")]
+    PositionRequired,
+
+    #[msg("Position still holds tokens")]
+    PositionNotEmpty,
+
+    #[msg("Position can't be closed during the launch window")]
+    PositionLocked,
+
+    #[msg("Not enough price history for the requested window")]
+    InsufficientPriceHistory,
+
+    #[msg("Token decimals not allowed")]
+    InvalidTokenDecimals,
+
+    #[msg("Curve supply must be positive and fit in the total supply and virtual reserves")]
+    InvalidTokenSupply,
+
+    #[msg("AMM config doesn't match the global config")]
+    InvalidAmmConfig,
+
+    #[msg("Authority can't be the default public key")]
+    InvalidAuthority,
+}
+
+impl From<MathError> for OpenClawError {
+    fn from(err: MathError) -> Self {
+        match err {
+            MathError::Overflow => OpenClawError::MathOverflow,
+            MathError::InsufficientLiquidity => OpenClawError::InsufficientLiquidity,
+            MathError::InvalidCurve => OpenClawError::InvalidCurveKind,
+            MathError::InvalidFee => OpenClawError::InvalidFeeConfig,
+        }
+    }
 }
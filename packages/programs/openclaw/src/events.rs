@@ -0,0 +1,306 @@
+use anchor_lang::prelude::*;
+use crate::curve::CurveKind;
+use crate::state::LaunchProtection;
+
+/// Emitted when a new token and its bonding curve are created
+#[event]
+pub struct TokenCreated {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Bonding curve account
+    pub bonding_curve: Pubkey,
+    /// Token creator
+    pub creator: Pubkey,
+    /// Token name
+    pub name: String,
+    /// Token symbol
+    pub symbol: String,
+    /// Metadata URI
+    pub uri: String,
+    /// Token program owning the mint, SPL Token or Token-2022
+    pub token_program: Pubkey,
+    /// Price dynamics of the curve
+    pub curve_kind: CurveKind,
+    /// Creator fee in basis points
+    pub creator_fee_bps: u16,
+    /// Global config version the curve was created under
+    pub config_version: u32,
+    /// Buy limits while the curve is new, all zero when off
+    pub launch_protection: LaunchProtection,
+    /// Initial virtual SOL reserves
+    pub virtual_sol_reserves: u64,
+    /// Initial virtual token reserves
+    pub virtual_token_reserves: u64,
+    /// Initial real token reserves
+    pub real_token_reserves: u64,
+    /// Tokens locked in the migration vault for the DEX pool
+    pub migration_tokens: u64,
+    /// Mint decimals
+    pub decimals: u8,
+    /// Tokens minted, the curve's real reserves included
+    pub total_supply: u64,
+    /// Slot of the creation
+    pub slot: u64,
+    /// Unix timestamp of the creation
+    pub timestamp: i64,
+}
+
+/// Emitted on every buy and sell against a bonding curve
+#[event]
+pub struct Trade {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Buyer or seller
+    pub user: Pubkey,
+    /// True for a buy, false for a sell
+    pub is_buy: bool,
+    /// SOL paid by the buyer (including fee) or received by the seller (after fee)
+    pub sol_amount: u64,
+    /// Tokens received by the buyer or sold by the seller
+    pub token_amount: u64,
+    /// Platform fee (in lamports)
+    pub fee: u64,
+    /// Creator fee accrued to the curve's creator (in lamports)
+    pub creator_fee: u64,
+    /// Referrer wallet that routed the trade, if any
+    pub referrer: Option<Pubkey>,
+    /// Share of the platform fee paid to the referrer (in lamports)
+    pub referral_fee: u64,
+    /// Virtual SOL reserves after the trade
+    pub virtual_sol_reserves: u64,
+    /// Virtual token reserves after the trade
+    pub virtual_token_reserves: u64,
+    /// Real SOL reserves after the trade
+    pub real_sol_reserves: u64,
+    /// Real token reserves after the trade
+    pub real_token_reserves: u64,
+    /// Slot of the trade
+    pub slot: u64,
+    /// Unix timestamp of the trade
+    pub timestamp: i64,
+}
+
+/// Emitted when a buy takes a curve to the migration threshold
+#[event]
+pub struct CurveCompleted {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Bonding curve account
+    pub bonding_curve: Pubkey,
+    /// Real SOL reserves waiting to be migrated
+    pub real_sol_reserves: u64,
+    /// Real token reserves waiting to be migrated
+    pub real_token_reserves: u64,
+    /// Slot of the completion
+    pub slot: u64,
+    /// Unix timestamp of the completion
+    pub timestamp: i64,
+}
+
+/// Emitted when a creator claims the fees accrued on their curve
+#[event]
+pub struct CreatorFeesClaimed {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Token creator
+    pub creator: Pubkey,
+    /// Fees paid out (in lamports)
+    pub amount: u64,
+    /// Fees claimed over the curve's lifetime (in lamports)
+    pub total_claimed: u64,
+    /// Slot of the claim
+    pub slot: u64,
+    /// Unix timestamp of the claim
+    pub timestamp: i64,
+}
+
+/// Emitted when a wallet opens its position on a curve
+#[event]
+pub struct PositionOpened {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Wallet the position belongs to
+    pub owner: Pubkey,
+    /// Position account
+    pub position: Pubkey,
+    /// Slot of the opening
+    pub slot: u64,
+    /// Unix timestamp of the opening
+    pub timestamp: i64,
+}
+
+/// Emitted when an empty position is closed
+#[event]
+pub struct PositionClosed {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Wallet the position belonged to
+    pub owner: Pubkey,
+    /// SOL received from sells less the cost of the tokens sold (in lamports)
+    pub realized_pnl: i64,
+    /// Slot of the closing
+    pub slot: u64,
+    /// Unix timestamp of the closing
+    pub timestamp: i64,
+}
+
+/// Emitted when a referrer registers or changes its requested share
+#[event]
+pub struct ReferrerRegistered {
+    /// Wallet paid the referral share
+    pub wallet: Pubkey,
+    /// Requested share of the platform fee, in basis points of the fee
+    pub referral_bps: u16,
+    /// Slot of the registration
+    pub slot: u64,
+    /// Unix timestamp of the registration
+    pub timestamp: i64,
+}
+
+/// Emitted when a bonding curve is migrated to a DEX
+#[event]
+pub struct Migrated {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// DEX pool the liquidity was moved into
+    pub pool: Pubkey,
+    /// SOL deposited into the pool (in lamports)
+    pub sol_amount: u64,
+    /// Tokens deposited into the pool, the migration allocation included
+    pub token_amount: u64,
+    /// Migration fee paid to the fee recipient (in lamports)
+    pub migration_fee: u64,
+    /// LP tokens burned
+    pub lp_burned: u64,
+    /// Slot of the migration
+    pub slot: u64,
+    /// Unix timestamp of the migration
+    pub timestamp: i64,
+}
+
+/// Emitted when the global config is initialized or updated
+#[event]
+pub struct ConfigUpdated {
+    /// Admin authority
+    pub authority: Pubkey,
+    /// Extra signer allowed to change fee settings, default when unset
+    pub fee_admin: Pubkey,
+    /// Extra signer allowed to pause the protocol, default when unset
+    pub pause_admin: Pubkey,
+    /// Fee recipient
+    pub fee_recipient: Pubkey,
+    /// Platform fee in basis points
+    pub fee_bps: u16,
+    /// Creator fee in basis points for new curves
+    pub creator_fee_bps: u16,
+    /// Highest share of the platform fee paid to referrers, in basis points of the fee
+    pub max_referral_bps: u16,
+    /// SOL threshold for migration (in lamports)
+    pub migration_threshold: u64,
+    /// Flat migration fee (in lamports)
+    pub migration_fee: u64,
+    /// Initial virtual SOL reserves for new curves
+    pub initial_virtual_sol: u64,
+    /// Initial virtual token reserves for new curves
+    pub initial_virtual_tokens: u64,
+    /// Decimals of new mints
+    pub token_decimals: u8,
+    /// Tokens minted for each new curve
+    pub total_supply: u64,
+    /// Part of the total supply sold on each new curve
+    pub curve_supply: u64,
+    /// CP-Swap config migrated pools are created under
+    pub amm_config: Pubkey,
+    /// Curve kinds creators may pick from
+    pub allowed_curve_kinds: Vec<CurveKind>,
+    /// Config version after the update
+    pub config_version: u32,
+    /// Slot of the update
+    pub slot: u64,
+    /// Unix timestamp of the update
+    pub timestamp: i64,
+}
+
+/// Emitted when an account is upgraded to the current layout
+#[event]
+pub struct AccountUpgraded {
+    /// Upgraded account
+    pub account: Pubkey,
+    /// Layout version before the upgrade
+    pub from_version: u8,
+    /// Layout version after the upgrade
+    pub to_version: u8,
+    /// Account size after the upgrade
+    pub len: u64,
+    /// Slot of the upgrade
+    pub slot: u64,
+    /// Unix timestamp of the upgrade
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority proposes a new authority
+#[event]
+pub struct AuthorityProposed {
+    /// Current admin authority
+    pub authority: Pubkey,
+    /// Proposed authority
+    pub pending_authority: Pubkey,
+    /// Slot of the proposal
+    pub slot: u64,
+    /// Unix timestamp of the proposal
+    pub timestamp: i64,
+}
+
+/// Emitted when the authority withdraws a pending proposal
+#[event]
+pub struct AuthorityProposalCancelled {
+    /// Current admin authority
+    pub authority: Pubkey,
+    /// Authority that was proposed
+    pub cancelled_authority: Pubkey,
+    /// Slot of the cancellation
+    pub slot: u64,
+    /// Unix timestamp of the cancellation
+    pub timestamp: i64,
+}
+
+/// Emitted when a proposed authority accepts and takes over the global config
+#[event]
+pub struct AuthorityTransferred {
+    /// Previous admin authority
+    pub previous_authority: Pubkey,
+    /// New admin authority
+    pub authority: Pubkey,
+    /// Slot of the transfer
+    pub slot: u64,
+    /// Unix timestamp of the transfer
+    pub timestamp: i64,
+}
+
+/// Emitted when the pause flags on the global config change
+#[event]
+pub struct PauseUpdated {
+    /// Signer that changed the flags
+    pub admin: Pubkey,
+    /// Paused operations, a combination of the `PAUSE_*` flags
+    pub paused: u8,
+    /// Slot of the change
+    pub slot: u64,
+    /// Unix timestamp of the change
+    pub timestamp: i64,
+}
+
+/// Emitted when a single curve is frozen or unfrozen
+#[event]
+pub struct CurveFrozen {
+    /// Token mint address
+    pub mint: Pubkey,
+    /// Signer that changed the flag
+    pub admin: Pubkey,
+    /// Whether the curve is now frozen
+    pub frozen: bool,
+    /// Slot of the change
+    pub slot: u64,
+    /// Unix timestamp of the change
+    pub timestamp: i64,
+}
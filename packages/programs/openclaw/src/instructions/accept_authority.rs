@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::events::AuthorityTransferred;
+use crate::state::{GlobalConfig, GLOBAL_CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.global_config;
+    require_keys_neq!(
+        config.pending_authority,
+        Pubkey::default(),
+        OpenClawError::NoPendingAuthority
+    );
+    require_keys_eq!(
+        ctx.accounts.new_authority.key(),
+        config.pending_authority,
+        OpenClawError::Unauthorized
+    );
+
+    let previous_authority = config.authority;
+    config.authority = config.pending_authority;
+    config.pending_authority = Pubkey::default();
+
+    let clock = Clock::get()?;
+    emit!(AuthorityTransferred {
+        previous_authority,
+        authority: config.authority,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Authority transferred from {} to {}", previous_authority, config.authority);
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::error::OpenClawError;
-use crate::state::{BondingCurve, GlobalConfig, BONDING_CURVE_SEED, GLOBAL_CONFIG_SEED};
+use crate::events::{CurveCompleted, Trade};
+use crate::state::{BondingCurve, GlobalConfig, Position, PriceHistory, Referrer, BONDING_CURVE_SEED, CURVE_SOL_VAULT_SEED, GLOBAL_CONFIG_SEED, POSITION_SEED, PRICE_HISTORY_SEED, REFERRER_SEED, PAUSE_TRADING};
 
 #[derive(Accounts)]
 pub struct Buy<'info> {
@@ -24,20 +25,32 @@ pub struct Buy<'info> {
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
+    #[account(address = bonding_curve.mint @ OpenClawError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
     #[account(
         mut,
-        associated_token::mint = bonding_curve.mint,
+        seeds = [CURVE_SOL_VAULT_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    pub curve_token_vault: Account<'info, TokenAccount>,
+    pub curve_token_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         init_if_needed,
         payer = buyer,
-        associated_token::mint = bonding_curve.mint,
+        associated_token::mint = mint,
         associated_token::authority = buyer,
+        associated_token::token_program = token_program,
     )]
-    pub buyer_token_account: Account<'info, TokenAccount>,
+    pub buyer_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Fee recipient
     #[account(
@@ -46,114 +59,318 @@ pub struct Buy<'info> {
     )]
     pub fee_recipient: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022, whichever owns the mint
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+
+    /// Referrer that routed the trade, if any
+    #[account(
+        mut,
+        seeds = [REFERRER_SEED, referrer.wallet.as_ref()],
+        bump = referrer.bump
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+
+    /// CHECK: Referrer's wallet, checked against `referrer`
+    #[account(mut)]
+    pub referrer_wallet: Option<UncheckedAccount<'info>>,
+
+    /// Buyer's position, updated when given and required during the curve's launch window
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, bonding_curve.mint.as_ref(), buyer.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    /// Created on the first trade for curves that predate price histories
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = PriceHistory::LEN,
+        seeds = [PRICE_HISTORY_SEED, bonding_curve.mint.as_ref()],
+        bump
+    )]
+    pub price_history: Box<Account<'info, PriceHistory>>,
 }
 
 pub fn handler(ctx: Context<Buy>, sol_amount: u64, min_tokens_out: u64) -> Result<()> {
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
-    let config = &mut ctx.accounts.global_config;
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let config = &ctx.accounts.global_config;
 
-    // Check not migrated
-    require!(!bonding_curve.migrated, OpenClawError::AlreadyMigrated);
+    // Check not paused, frozen, migrated or complete
+    config.check_not_paused(PAUSE_TRADING)?;
+    bonding_curve.check_not_frozen()?;
+    bonding_curve.check_tradable()?;
 
-    // Calculate tokens out and fee
-    let (tokens_out, fee) = bonding_curve.get_tokens_out(sol_amount, config.fee_bps)?;
+    // Calculate tokens out and fee, stopping at the migration threshold
+    let (sol_in, fee, tokens_out) = bonding_curve.get_capped_buy(
+        sol_amount,
+        bonding_curve.total_fee_bps(config.fee_bps)?,
+        config.migration_threshold,
+    )?;
 
-    // Check slippage
+    // Check slippage, scaled down to the part of the order that was filled
+    let min_tokens_out =
+        (min_tokens_out as u128 * sol_in as u128 / sol_amount.max(1) as u128) as u64;
     require!(tokens_out >= min_tokens_out, OpenClawError::SlippageExceeded);
 
-    // Check sufficient liquidity
-    require!(
-        tokens_out <= bonding_curve.real_token_reserves,
-        OpenClawError::InsufficientLiquidity
-    );
-
-    let sol_after_fee = sol_amount.saturating_sub(fee);
-
-    // Transfer SOL from buyer to curve (minus fee)
-    system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            system_program::Transfer {
-                from: ctx.accounts.buyer.to_account_info(),
-                to: bonding_curve.to_account_info(),
-            },
-        ),
-        sol_after_fee,
-    )?;
+    if sol_in < sol_amount {
+        msg!(
+            "Buy capped at migration threshold: {} of {} lamports used",
+            sol_in,
+            sol_amount
+        );
+    }
+
+    ctx.accounts
+        .execute(sol_in, fee, tokens_out, ctx.bumps.price_history)
+}
+
+impl<'info> Buy<'info> {
+    /// Move `sol_amount` (fee included) in and `tokens_out` out of the curve. `fee` is the
+    /// platform and creator fee together.
+    pub(crate) fn execute(
+        &mut self,
+        sol_amount: u64,
+        fee: u64,
+        tokens_out: u64,
+        price_history_bump: u8,
+    ) -> Result<()> {
+        let clock = Clock::get()?;
+        self.price_history
+            .start(self.bonding_curve.mint, price_history_bump);
 
-    // Transfer fee to fee recipient
-    if fee > 0 {
+        // Cap buys per transaction and per wallet while the curve is new
+        if self.bonding_curve.in_launch_window(clock.unix_timestamp) {
+            let limits = self.bonding_curve.launch_protection;
+            require!(
+                sol_amount <= limits.max_sol_per_tx,
+                OpenClawError::LaunchLimitExceeded
+            );
+            let position = self
+                .position
+                .as_mut()
+                .ok_or(OpenClawError::PositionRequired)?;
+            position.launch_sol_spent = position
+                .launch_sol_spent
+                .checked_add(sol_amount)
+                .ok_or(OpenClawError::MathOverflow)?;
+            require!(
+                position.launch_sol_spent <= limits.max_sol_per_wallet,
+                OpenClawError::LaunchLimitExceeded
+            );
+        }
+
+        let referrer = match (self.referrer.as_mut(), &self.referrer_wallet) {
+            (Some(referrer), Some(wallet)) => Some((referrer, wallet.to_account_info())),
+            (None, None) => None,
+            _ => return err!(OpenClawError::InvalidReferrer),
+        };
+
+        BuyAccounts {
+            buyer: self.buyer.to_account_info(),
+            global_config: &mut self.global_config,
+            bonding_curve: &mut self.bonding_curve,
+            price_history: &mut self.price_history,
+            mint: self.mint.to_account_info(),
+            decimals: self.mint.decimals,
+            sol_vault: self.sol_vault.to_account_info(),
+            curve_token_vault: self.curve_token_vault.to_account_info(),
+            buyer_token_account: self.buyer_token_account.to_account_info(),
+            fee_recipient: self.fee_recipient.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            referrer,
+            position: self.position.as_mut(),
+        }
+        .execute(sol_amount, fee, tokens_out)
+    }
+}
+
+/// Accounts a buy moves SOL and tokens between. `buy`, `buy_exact_tokens` and the
+/// creator's initial buy in `create_token` all go through here, so they are charged and
+/// recorded the same way.
+pub(crate) struct BuyAccounts<'a, 'info> {
+    pub buyer: AccountInfo<'info>,
+    pub global_config: &'a mut Account<'info, GlobalConfig>,
+    pub bonding_curve: &'a mut Account<'info, BondingCurve>,
+    pub price_history: &'a mut PriceHistory,
+    pub mint: AccountInfo<'info>,
+    pub decimals: u8,
+    pub sol_vault: AccountInfo<'info>,
+    pub curve_token_vault: AccountInfo<'info>,
+    pub buyer_token_account: AccountInfo<'info>,
+    pub fee_recipient: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    /// Referrer that routed the buy and its wallet, checked against each other here
+    pub referrer: Option<(&'a mut Account<'info, Referrer>, AccountInfo<'info>)>,
+    /// Buyer's position, if any
+    pub position: Option<&'a mut Account<'info, Position>>,
+}
+
+impl BuyAccounts<'_, '_> {
+    /// Move `sol_amount` (fee included) in and `tokens_out` out of the curve, paying the
+    /// fees and updating the curve. Launch protection is left to the caller.
+    pub(crate) fn execute(self, sol_amount: u64, fee: u64, tokens_out: u64) -> Result<()> {
+        let bonding_curve = self.bonding_curve;
+        let config = self.global_config;
+        let clock = Clock::get()?;
+
+        // Check sufficient liquidity. The migration allocation sits in its own vault and
+        // never counts towards the curve's real reserves.
+        require!(
+            tokens_out <= bonding_curve.real_token_reserves,
+            OpenClawError::InsufficientLiquidity
+        );
+
+        let sol_after_fee = sol_amount
+            .checked_sub(fee)
+            .ok_or(OpenClawError::MathOverflow)?;
+        let (platform_fee, creator_fee) = bonding_curve.split_fee(fee, config.fee_bps)?;
+
+        // The referrer's share comes out of the platform fee
+        let referral_fee = match &self.referrer {
+            Some((referrer, wallet)) => {
+                referrer.check_wallet(wallet.key, self.buyer.key)?;
+                referrer.referral_fee(platform_fee, config.max_referral_bps)?
+            }
+            None => 0,
+        };
+        let platform_fee = platform_fee - referral_fee;
+
+        // Transfer SOL from buyer to the curve's SOL vault (minus the platform fee). The
+        // creator fee waits in the vault until the creator claims it.
         system_program::transfer(
             CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
+                self.system_program.clone(),
                 system_program::Transfer {
-                    from: ctx.accounts.buyer.to_account_info(),
-                    to: ctx.accounts.fee_recipient.to_account_info(),
+                    from: self.buyer.clone(),
+                    to: self.sol_vault.clone(),
                 },
             ),
-            fee,
+            sol_after_fee
+                .checked_add(creator_fee)
+                .ok_or(OpenClawError::MathOverflow)?,
         )?;
-    }
 
-    // Transfer tokens from curve vault to buyer
-    let mint_key = bonding_curve.mint;
-    let seeds = &[
-        BONDING_CURVE_SEED,
-        mint_key.as_ref(),
-        &[bonding_curve.bump],
-    ];
-    let signer = &[&seeds[..]];
-
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.curve_token_vault.to_account_info(),
-                to: ctx.accounts.buyer_token_account.to_account_info(),
-                authority: bonding_curve.to_account_info(),
-            },
-            signer,
-        ),
-        tokens_out,
-    )?;
+        // Transfer platform fee to fee recipient
+        if platform_fee > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.clone(),
+                    system_program::Transfer {
+                        from: self.buyer.clone(),
+                        to: self.fee_recipient,
+                    },
+                ),
+                platform_fee,
+            )?;
+        }
 
-    // Update bonding curve state
-    bonding_curve.virtual_sol_reserves = bonding_curve
-        .virtual_sol_reserves
-        .checked_add(sol_after_fee)
-        .ok_or(OpenClawError::MathOverflow)?;
-    bonding_curve.virtual_token_reserves = bonding_curve
-        .virtual_token_reserves
-        .checked_sub(tokens_out)
-        .ok_or(OpenClawError::MathOverflow)?;
-    bonding_curve.real_sol_reserves = bonding_curve
-        .real_sol_reserves
-        .checked_add(sol_after_fee)
-        .ok_or(OpenClawError::MathOverflow)?;
-    bonding_curve.real_token_reserves = bonding_curve
-        .real_token_reserves
-        .checked_sub(tokens_out)
-        .ok_or(OpenClawError::MathOverflow)?;
-    bonding_curve.tokens_sold = bonding_curve
-        .tokens_sold
-        .checked_add(tokens_out)
-        .ok_or(OpenClawError::MathOverflow)?;
-
-    // Update global volume
-    config.total_volume = config
-        .total_volume
-        .checked_add(sol_amount)
-        .ok_or(OpenClawError::MathOverflow)?;
-
-    msg!(
-        "Buy: {} lamports -> {} tokens (fee: {} lamports)",
-        sol_amount,
-        tokens_out,
-        fee
-    );
+        // Transfer referral fee to the referrer
+        let referrer_wallet = self.referrer.as_ref().map(|(_, wallet)| wallet.key());
+        if let Some((referrer, wallet)) = self.referrer {
+            if referral_fee > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.clone(),
+                        system_program::Transfer {
+                            from: self.buyer.clone(),
+                            to: wallet,
+                        },
+                    ),
+                    referral_fee,
+                )?;
+            }
+            referrer.record(sol_amount, referral_fee)?;
+        }
+        if let Some(position) = self.position {
+            position.record_buy(sol_amount, tokens_out, fee, clock.unix_timestamp)?;
+        }
+
+        // Transfer tokens from curve vault to buyer
+        let mint_key = bonding_curve.mint;
+        let seeds = &[
+            BONDING_CURVE_SEED,
+            mint_key.as_ref(),
+            &[bonding_curve.bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program,
+                TransferChecked {
+                    from: self.curve_token_vault,
+                    mint: self.mint,
+                    to: self.buyer_token_account,
+                    authority: bonding_curve.to_account_info(),
+                },
+                signer,
+            ),
+            tokens_out,
+            self.decimals,
+        )?;
+
+        // Update bonding curve state, recording the price it traded at until now
+        self.price_history.record(clock.slot, bonding_curve.spot_price()?);
+        bonding_curve.apply_buy(sol_after_fee, tokens_out)?;
+        bonding_curve.creator_fees_accrued = bonding_curve
+            .creator_fees_accrued
+            .checked_add(creator_fee)
+            .ok_or(OpenClawError::MathOverflow)?;
 
-    Ok(())
+        bonding_curve.check_sol_vault(self.sol_vault.lamports())?;
+
+        // Stop trading once the curve has raised enough or sold out
+        if bonding_curve.is_complete_after(config) {
+            bonding_curve.complete = true;
+            emit!(CurveCompleted {
+                mint: bonding_curve.mint,
+                bonding_curve: bonding_curve.key(),
+                real_sol_reserves: bonding_curve.real_sol_reserves,
+                real_token_reserves: bonding_curve.real_token_reserves,
+                slot: clock.slot,
+                timestamp: clock.unix_timestamp,
+            });
+            msg!("Bonding curve complete: {} ready to migrate", bonding_curve.mint);
+        }
+
+        // Update global volume
+        config.total_volume = config
+            .total_volume
+            .checked_add(sol_amount)
+            .ok_or(OpenClawError::MathOverflow)?;
+
+        emit!(Trade {
+            mint: bonding_curve.mint,
+            user: self.buyer.key(),
+            is_buy: true,
+            sol_amount,
+            token_amount: tokens_out,
+            fee: platform_fee,
+            creator_fee,
+            referrer: referrer_wallet,
+            referral_fee,
+            virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+            virtual_token_reserves: bonding_curve.virtual_token_reserves,
+            real_sol_reserves: bonding_curve.real_sol_reserves,
+            real_token_reserves: bonding_curve.real_token_reserves,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Buy: {} lamports -> {} tokens (fee: {} lamports, creator fee: {} lamports)",
+            sol_amount,
+            tokens_out,
+            platform_fee,
+            creator_fee
+        );
+
+        Ok(())
+    }
 }
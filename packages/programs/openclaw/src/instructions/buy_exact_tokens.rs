@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::instructions::Buy;
+use crate::state::PAUSE_TRADING;
+
+pub fn handler(ctx: Context<Buy>, tokens_out: u64, max_sol_in: u64) -> Result<()> {
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let config = &ctx.accounts.global_config;
+
+    // Check not paused, frozen, migrated or complete
+    config.check_not_paused(PAUSE_TRADING)?;
+    bonding_curve.check_not_frozen()?;
+    bonding_curve.check_tradable()?;
+    require!(tokens_out > 0, OpenClawError::InsufficientTokenAmount);
+
+    // Calculate SOL in and fee
+    let (sol_in, fee) = bonding_curve.get_sol_in(tokens_out, bonding_curve.total_fee_bps(config.fee_bps)?)?;
+
+    // Check slippage
+    require!(sol_in <= max_sol_in, OpenClawError::SlippageExceeded);
+
+    // An exact amount can't be capped, so it has to fit under the threshold
+    let remaining = config
+        .migration_threshold
+        .saturating_sub(bonding_curve.real_sol_reserves);
+    require!(
+        sol_in - fee <= remaining,
+        OpenClawError::MigrationThresholdExceeded
+    );
+
+    ctx.accounts
+        .execute(sol_in, fee, tokens_out, ctx.bumps.price_history)
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::events::AuthorityProposalCancelled;
+use crate::instructions::ProposeAuthority;
+
+pub fn handler(ctx: Context<ProposeAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.global_config;
+    require_keys_neq!(
+        config.pending_authority,
+        Pubkey::default(),
+        OpenClawError::NoPendingAuthority
+    );
+
+    let cancelled = config.pending_authority;
+    config.pending_authority = Pubkey::default();
+
+    let clock = Clock::get()?;
+    emit!(AuthorityProposalCancelled {
+        authority: config.authority,
+        cancelled_authority: cancelled,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Cancelled authority proposal for {}", cancelled);
+
+    Ok(())
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::error::OpenClawError;
+use crate::events::CreatorFeesClaimed;
+use crate::state::{BondingCurve, BONDING_CURVE_SEED, CURVE_SOL_VAULT_SEED};
+
+#[derive(Accounts)]
+pub struct ClaimCreatorFees<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump,
+        has_one = creator @ OpenClawError::Unauthorized
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        seeds = [CURVE_SOL_VAULT_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+
+    let amount = bonding_curve.creator_fees_accrued;
+    require!(amount > 0, OpenClawError::NoCreatorFees);
+
+    let mint_key = bonding_curve.mint;
+    let vault_seeds = &[
+        CURVE_SOL_VAULT_SEED,
+        mint_key.as_ref(),
+        &[bonding_curve.sol_vault_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    // Pay the accrued fees out of the curve's SOL vault
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.sol_vault.to_account_info(),
+                to: ctx.accounts.creator.to_account_info(),
+            },
+            vault_signer,
+        ),
+        amount,
+    )?;
+
+    bonding_curve.creator_fees_accrued = 0;
+    bonding_curve.creator_fees_claimed = bonding_curve
+        .creator_fees_claimed
+        .checked_add(amount)
+        .ok_or(OpenClawError::MathOverflow)?;
+
+    bonding_curve.check_sol_vault(ctx.accounts.sol_vault.lamports())?;
+
+    let clock = Clock::get()?;
+    emit!(CreatorFeesClaimed {
+        mint: bonding_curve.mint,
+        creator: bonding_curve.creator,
+        amount,
+        total_claimed: bonding_curve.creator_fees_claimed,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Creator fees claimed: {} lamports for {}", amount, bonding_curve.mint);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::events::PositionClosed;
+use crate::state::{BondingCurve, Position, BONDING_CURVE_SEED, POSITION_SEED};
+
+#[derive(Accounts)]
+pub struct ClosePosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [POSITION_SEED, bonding_curve.mint.as_ref(), owner.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Account<'info, Position>,
+}
+
+pub fn handler(ctx: Context<ClosePosition>) -> Result<()> {
+    let position = &ctx.accounts.position;
+    require!(position.tokens_held() == 0, OpenClawError::PositionNotEmpty);
+
+    // Reopening would reset the wallet's launch allowance
+    let clock = Clock::get()?;
+    require!(
+        !ctx.accounts.bonding_curve.in_launch_window(clock.unix_timestamp),
+        OpenClawError::PositionLocked
+    );
+
+    let realized_pnl = position.realized_pnl()?;
+    emit!(PositionClosed {
+        mint: position.mint,
+        owner: position.owner,
+        realized_pnl,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Position closed on {} for {} (realized PnL: {} lamports)",
+        position.mint,
+        position.owner,
+        realized_pnl
+    );
+
+    Ok(())
+}
@@ -1,13 +1,54 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, MintTo};
-use anchor_spl::associated_token::AssociatedToken;
-use crate::state::{BondingCurve, GlobalConfig, BONDING_CURVE_SEED, GLOBAL_CONFIG_SEED, CURVE_TOKEN_VAULT_SEED};
+use anchor_lang::system_program;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token_2022::{self, spl_token_2022};
+use anchor_spl::token_2022::spl_token_2022::extension::{metadata_pointer, ExtensionType};
+use anchor_spl::token_interface::{self, InitializeAccount3, InitializeMint2, MintTo, TokenInterface};
+use anchor_spl::associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken};
+use anchor_spl::metadata::{self, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3, Metadata};
+use spl_token_metadata_interface::state::TokenMetadata;
+use crate::curve::CurveKind;
+use crate::error::OpenClawError;
+use crate::events::TokenCreated;
+use crate::instructions::BuyAccounts;
+use crate::state::{BondingCurve, GlobalConfig, LaunchProtection, Position, PriceHistory, BONDING_CURVE_SEED, CURVE_SOL_VAULT_SEED, GLOBAL_CONFIG_SEED, METADATA_SEED, MIGRATION_VAULT_SEED, POSITION_SEED, PRICE_HISTORY_SEED, PAUSE_CREATION, PAUSE_TRADING, ACCOUNT_VERSION};
+
+/// Metaplex limits for on-chain metadata fields (in bytes)
+pub const MAX_NAME_LEN: usize = 32;
+pub const MAX_SYMBOL_LEN: usize = 10;
+pub const MAX_URI_LEN: usize = 200;
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateTokenParams {
     pub name: String,
     pub symbol: String,
     pub uri: String,
+    pub curve_kind: CurveKind,
+    /// Buy limits for the first moments of trading, if any
+    pub launch_protection: Option<LaunchProtection>,
+    /// SOL (fee included) the creator spends buying from the new curve, if any
+    pub initial_buy_sol: Option<u64>,
+    /// Slippage limit of the initial buy
+    pub min_tokens_out: u64,
+}
+
+impl CreateTokenParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            !self.name.is_empty() && self.name.len() <= MAX_NAME_LEN,
+            OpenClawError::InvalidTokenName
+        );
+        require!(
+            !self.symbol.is_empty() && self.symbol.len() <= MAX_SYMBOL_LEN,
+            OpenClawError::InvalidTokenSymbol
+        );
+        require!(self.uri.len() <= MAX_URI_LEN, OpenClawError::InvalidTokenUri);
+        if let Some(launch_protection) = &self.launch_protection {
+            launch_protection.validate()?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Accounts)]
@@ -23,14 +64,9 @@ pub struct CreateToken<'info> {
     )]
     pub global_config: Account<'info, GlobalConfig>,
 
-    /// The token mint (vanity address)
-    #[account(
-        init,
-        payer = creator,
-        mint::decimals = 6,
-        mint::authority = bonding_curve,
-    )]
-    pub mint: Account<'info, Mint>,
+    /// The token mint (vanity address), created under `token_program`
+    #[account(mut)]
+    pub mint: Signer<'info>,
 
     #[account(
         init,
@@ -44,19 +80,96 @@ pub struct CreateToken<'info> {
     #[account(
         init,
         payer = creator,
-        associated_token::mint = mint,
-        associated_token::authority = bonding_curve,
+        space = PriceHistory::LEN,
+        seeds = [PRICE_HISTORY_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub price_history: Box<Account<'info, PriceHistory>>,
+
+    /// System-owned account holding the curve's SOL
+    #[account(
+        mut,
+        seeds = [CURVE_SOL_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    /// CHECK: The curve's associated token account, created here once the mint exists
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &bonding_curve.key(),
+            &mint.key(),
+            &token_program.key()
+        )
     )]
-    pub curve_token_vault: Account<'info, TokenAccount>,
+    pub curve_token_vault: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Token account locking the supply kept for the DEX pool, created here
+    #[account(
+        mut,
+        seeds = [MIGRATION_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub migration_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex metadata PDA, created by the token metadata program. Only needed
+    /// for SPL Token mints, Token-2022 mints keep their metadata in the mint.
+    #[account(
+        mut,
+        seeds = [METADATA_SEED, Metadata::id().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = Metadata::id()
+    )]
+    pub metadata: Option<UncheckedAccount<'info>>,
+
+    /// SPL Token or Token-2022, picking the program the mint is created under
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_metadata_program: Option<Program<'info, Metadata>>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: Fee recipient, needed for an initial buy
+    #[account(
+        mut,
+        constraint = fee_recipient.key() == global_config.fee_recipient
+    )]
+    pub fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Creator's associated token account, created here for an initial buy
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(
+            &creator.key(),
+            &mint.key(),
+            &token_program.key()
+        )
+    )]
+    pub creator_token_account: Option<UncheckedAccount<'info>>,
+
+    /// Creator's position, opened here to record the initial buy, if given
+    #[account(
+        init,
+        payer = creator,
+        space = Position::LEN,
+        seeds = [POSITION_SEED, mint.key().as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub creator_position: Option<Box<Account<'info, Position>>>,
 }
 
 pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<()> {
+    params.validate()?;
+
     let config = &ctx.accounts.global_config;
+    config.check_not_paused(PAUSE_CREATION)?;
+    require!(
+        config.allowed_curve_kinds.contains(&params.curve_kind),
+        OpenClawError::CurveKindNotAllowed
+    );
+
+    let (decimals, total_supply, curve_supply) = config.launch_supply();
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     let clock = Clock::get()?;
 
@@ -66,13 +179,57 @@ pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<(
     bonding_curve.virtual_sol_reserves = config.initial_virtual_sol;
     bonding_curve.virtual_token_reserves = config.initial_virtual_tokens;
     bonding_curve.real_sol_reserves = 0;
-    bonding_curve.real_token_reserves = config.initial_virtual_tokens;
+    bonding_curve.real_token_reserves = curve_supply;
     bonding_curve.tokens_sold = 0;
     bonding_curve.migrated = false;
+    bonding_curve.complete = false;
     bonding_curve.created_at = clock.unix_timestamp;
     bonding_curve.bump = ctx.bumps.bonding_curve;
+    bonding_curve.sol_vault_bump = ctx.bumps.sol_vault;
+    bonding_curve.curve_kind = params.curve_kind;
+    bonding_curve.creator_fee_bps = config.creator_fee_bps;
+    bonding_curve.creator_fees_accrued = 0;
+    bonding_curve.creator_fees_claimed = 0;
+    bonding_curve.frozen = false;
+    bonding_curve.config_version = config.config_version;
+    bonding_curve.version = ACCOUNT_VERSION;
+    bonding_curve.launch_protection = params.launch_protection.unwrap_or_default();
+    bonding_curve.migration_tokens = total_supply
+        .checked_sub(curve_supply)
+        .ok_or(OpenClawError::MathOverflow)?;
+    bonding_curve.check_curve()?;
+
+    // Start the price history at the curve's opening price
+    let price_history = &mut ctx.accounts.price_history;
+    price_history.mint = bonding_curve.mint;
+    price_history.bump = ctx.bumps.price_history;
+    price_history.record(clock.slot, bonding_curve.spot_price()?);
+
+    if let Some(position) = ctx.accounts.creator_position.as_mut() {
+        position.mint = ctx.accounts.mint.key();
+        position.owner = ctx.accounts.creator.key();
+        position.bump = ctx.bumps.creator_position;
+    }
+
+    // Fund the SOL vault with its rent-exempt minimum
+    let vault_rent = Rent::get()?
+        .minimum_balance(0)
+        .saturating_sub(ctx.accounts.sol_vault.lamports());
+    if vault_rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: ctx.accounts.sol_vault.to_account_info(),
+                },
+            ),
+            vault_rent,
+        )?;
+    }
 
-    // Mint total supply to the curve's token vault
+    // Create the mint and its vaults. The curve's token vault gets the curve supply and
+    // the migration vault the rest, locked until `migrate` moves it into the DEX pool.
     let mint_key = ctx.accounts.mint.key();
     let seeds = &[
         BONDING_CURVE_SEED,
@@ -81,23 +238,69 @@ pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<(
     ];
     let signer = &[&seeds[..]];
 
-    token::mint_to(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.curve_token_vault.to_account_info(),
-                authority: bonding_curve.to_account_info(),
-            },
-            signer,
-        ),
-        config.initial_virtual_tokens,
-    )?;
+    ctx.accounts.create_mint(&params, decimals, signer)?;
+    associated_token::create(CpiContext::new(
+        ctx.accounts.associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: ctx.accounts.creator.to_account_info(),
+            associated_token: ctx.accounts.curve_token_vault.to_account_info(),
+            authority: ctx.accounts.bonding_curve.to_account_info(),
+            mint: ctx.accounts.mint.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+    ))?;
+    ctx.accounts.create_migration_vault(ctx.bumps.migration_vault)?;
+
+    let migration_tokens = ctx.accounts.bonding_curve.migration_tokens;
+    for (vault, amount) in [
+        (&ctx.accounts.curve_token_vault, curve_supply),
+        (&ctx.accounts.migration_vault, migration_tokens),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                signer,
+            ),
+            amount,
+        )?;
+    }
 
     // Update global stats
     let config = &mut ctx.accounts.global_config;
     config.total_tokens = config.total_tokens.saturating_add(1);
 
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    emit!(TokenCreated {
+        mint: bonding_curve.mint,
+        bonding_curve: bonding_curve.key(),
+        creator: bonding_curve.creator,
+        name: params.name.clone(),
+        symbol: params.symbol.clone(),
+        uri: params.uri.clone(),
+        token_program: ctx.accounts.token_program.key(),
+        curve_kind: bonding_curve.curve_kind,
+        creator_fee_bps: bonding_curve.creator_fee_bps,
+        config_version: bonding_curve.config_version,
+        launch_protection: bonding_curve.launch_protection,
+        virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+        virtual_token_reserves: bonding_curve.virtual_token_reserves,
+        real_token_reserves: bonding_curve.real_token_reserves,
+        migration_tokens: bonding_curve.migration_tokens,
+        decimals,
+        total_supply,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
     msg!(
         "Token created: {} ({}) at {}",
         params.name,
@@ -105,5 +308,243 @@ pub fn handler(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<(
         ctx.accounts.mint.key()
     );
 
+    if let Some(sol_amount) = params.initial_buy_sol {
+        ctx.accounts
+            .initial_buy(sol_amount, params.min_tokens_out, decimals)?;
+    }
+
     Ok(())
 }
+
+impl<'info> CreateToken<'info> {
+    /// Create the mint under `token_program` with the bonding curve as mint authority, and
+    /// its metadata. SPL Token mints get Metaplex metadata; Token-2022 mints point their
+    /// metadata at themselves and store it in the token metadata extension.
+    fn create_mint(
+        &self,
+        params: &CreateTokenParams,
+        decimals: u8,
+        signer: &[&[&[u8]]],
+    ) -> Result<()> {
+        let token_program = self.token_program.key();
+        let is_token_2022 = token_program == token_2022::ID;
+        let mint = self.mint.to_account_info();
+        let bonding_curve = self.bonding_curve.key();
+
+        // The metadata extension reallocates the mint when it is written, so fund that
+        // space up front
+        let (extensions, metadata_len) = if is_token_2022 {
+            let metadata = TokenMetadata {
+                name: params.name.clone(),
+                symbol: params.symbol.clone(),
+                uri: params.uri.clone(),
+                ..Default::default()
+            };
+            (vec![ExtensionType::MetadataPointer], metadata.tlv_size_of()?)
+        } else {
+            (vec![], 0)
+        };
+        let space = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+            &extensions,
+        )?;
+        let lamports = Rent::get()?.minimum_balance(
+            space
+                .checked_add(metadata_len)
+                .ok_or(OpenClawError::MathOverflow)?,
+        );
+        system_program::create_account(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: self.creator.to_account_info(),
+                    to: mint.clone(),
+                },
+            ),
+            lamports,
+            space as u64,
+            &token_program,
+        )?;
+
+        // Extensions must be set up before the mint is initialized
+        if is_token_2022 {
+            invoke(
+                &metadata_pointer::instruction::initialize(
+                    &token_program,
+                    &mint.key(),
+                    None,
+                    Some(mint.key()),
+                )?,
+                std::slice::from_ref(&mint),
+            )?;
+        }
+
+        token_interface::initialize_mint2(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                InitializeMint2 { mint: mint.clone() },
+            ),
+            decimals,
+            &bonding_curve,
+            None,
+        )?;
+
+        if is_token_2022 {
+            // The bonding curve stays update authority, and never updates it
+            invoke_signed(
+                &spl_token_metadata_interface::instruction::initialize(
+                    &token_program,
+                    &mint.key(),
+                    &bonding_curve,
+                    &mint.key(),
+                    &bonding_curve,
+                    params.name.clone(),
+                    params.symbol.clone(),
+                    params.uri.clone(),
+                ),
+                &[mint.clone(), self.bonding_curve.to_account_info()],
+                signer,
+            )?;
+            return Ok(());
+        }
+
+        // Create immutable metadata, with the bonding curve as update authority
+        let metadata = self
+            .metadata
+            .as_ref()
+            .ok_or(ErrorCode::AccountNotEnoughKeys)?;
+        let token_metadata_program = self
+            .token_metadata_program
+            .as_ref()
+            .ok_or(ErrorCode::AccountNotEnoughKeys)?;
+        metadata::create_metadata_accounts_v3(
+            CpiContext::new_with_signer(
+                token_metadata_program.to_account_info(),
+                CreateMetadataAccountsV3 {
+                    metadata: metadata.to_account_info(),
+                    mint,
+                    mint_authority: self.bonding_curve.to_account_info(),
+                    payer: self.creator.to_account_info(),
+                    update_authority: self.bonding_curve.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    rent: self.rent.to_account_info(),
+                },
+                signer,
+            ),
+            DataV2 {
+                name: params.name.clone(),
+                symbol: params.symbol.clone(),
+                uri: params.uri.clone(),
+                seller_fee_basis_points: 0,
+                creators: None,
+                collection: None,
+                uses: None,
+            },
+            false,
+            true,
+            None,
+        )?;
+
+        Ok(())
+    }
+
+    /// Create the migration vault, a token account owned by the bonding curve at a PDA
+    fn create_migration_vault(&self, bump: u8) -> Result<()> {
+        let mint_key = self.mint.key();
+        let seeds = &[MIGRATION_VAULT_SEED, mint_key.as_ref(), &[bump]];
+        let space = spl_token_2022::state::Account::LEN;
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::CreateAccount {
+                    from: self.creator.to_account_info(),
+                    to: self.migration_vault.to_account_info(),
+                },
+                &[&seeds[..]],
+            ),
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            &self.token_program.key(),
+        )?;
+        token_interface::initialize_account3(CpiContext::new(
+            self.token_program.to_account_info(),
+            InitializeAccount3 {
+                account: self.migration_vault.to_account_info(),
+                mint: self.mint.to_account_info(),
+                authority: self.bonding_curve.to_account_info(),
+            },
+        ))
+    }
+
+    /// Buy from the new curve for the creator like `buy`, launch limits included, minus
+    /// referrals
+    fn initial_buy(&mut self, sol_amount: u64, min_tokens_out: u64, decimals: u8) -> Result<()> {
+        require!(sol_amount > 0, OpenClawError::InsufficientSolAmount);
+        let fee_recipient = self
+            .fee_recipient
+            .as_ref()
+            .ok_or(ErrorCode::AccountNotEnoughKeys)?;
+        let creator_token_account = self
+            .creator_token_account
+            .as_ref()
+            .ok_or(ErrorCode::AccountNotEnoughKeys)?;
+        let bonding_curve = &self.bonding_curve;
+        let config = &self.global_config;
+        config.check_not_paused(PAUSE_TRADING)?;
+
+        // Calculate tokens out and fee, stopping at the migration threshold
+        let (sol_in, fee, tokens_out) = bonding_curve.get_capped_buy(
+            sol_amount,
+            bonding_curve.total_fee_bps(config.fee_bps)?,
+            config.migration_threshold,
+        )?;
+        let min_tokens_out =
+            (min_tokens_out as u128 * sol_in as u128 / sol_amount as u128) as u64;
+        require!(tokens_out >= min_tokens_out, OpenClawError::SlippageExceeded);
+
+        // The curve is new, so the creator's buy is the first charged to its launch limits
+        let clock = Clock::get()?;
+        if bonding_curve.in_launch_window(clock.unix_timestamp) {
+            let limits = bonding_curve.launch_protection;
+            require!(
+                sol_in <= limits.max_sol_per_tx,
+                OpenClawError::LaunchLimitExceeded
+            );
+            let position = self
+                .creator_position
+                .as_mut()
+                .ok_or(OpenClawError::PositionRequired)?;
+            position.launch_sol_spent = sol_in;
+        }
+
+        // The mint is new, so the creator's token account can't exist yet
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: self.creator.to_account_info(),
+                associated_token: creator_token_account.to_account_info(),
+                authority: self.creator.to_account_info(),
+                mint: self.mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))?;
+
+        BuyAccounts {
+            buyer: self.creator.to_account_info(),
+            global_config: &mut self.global_config,
+            bonding_curve: &mut self.bonding_curve,
+            price_history: &mut self.price_history,
+            mint: self.mint.to_account_info(),
+            decimals,
+            sol_vault: self.sol_vault.to_account_info(),
+            curve_token_vault: self.curve_token_vault.to_account_info(),
+            buyer_token_account: creator_token_account.to_account_info(),
+            fee_recipient: fee_recipient.to_account_info(),
+            token_program: self.token_program.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            referrer: None,
+            position: self.creator_position.as_deref_mut(),
+        }
+        .execute(sol_in, fee, tokens_out)
+    }
+}
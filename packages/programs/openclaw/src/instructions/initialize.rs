@@ -1,12 +1,26 @@
 use anchor_lang::prelude::*;
-use crate::state::{GlobalConfig, GLOBAL_CONFIG_SEED};
+use crate::curve::CurveKind;
+use crate::events::ConfigUpdated;
+use crate::state::{GlobalConfig, ACCOUNT_VERSION, GLOBAL_CONFIG_SEED};
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitializeParams {
     pub fee_bps: u16,
+    pub creator_fee_bps: u16,
+    pub max_referral_bps: u16,
     pub migration_threshold: u64,
+    pub migration_fee: u64,
     pub initial_virtual_sol: u64,
     pub initial_virtual_tokens: u64,
+    /// Decimals of new mints, one of `TOKEN_DECIMALS`
+    pub token_decimals: u8,
+    /// Tokens minted for each new curve
+    pub total_supply: u64,
+    /// Part of `total_supply` sold on the curve
+    pub curve_supply: u64,
+    /// CP-Swap config migrated pools are created under
+    pub amm_config: Pubkey,
+    pub allowed_curve_kinds: Vec<CurveKind>,
 }
 
 #[derive(Accounts)]
@@ -33,14 +47,52 @@ pub fn handler(ctx: Context<Initialize>, params: InitializeParams) -> Result<()>
     let config = &mut ctx.accounts.global_config;
 
     config.authority = ctx.accounts.authority.key();
+    config.pending_authority = Pubkey::default();
+    config.fee_admin = Pubkey::default();
+    config.pause_admin = Pubkey::default();
     config.fee_recipient = ctx.accounts.fee_recipient.key();
     config.fee_bps = params.fee_bps;
+    config.creator_fee_bps = params.creator_fee_bps;
+    config.max_referral_bps = params.max_referral_bps;
     config.migration_threshold = params.migration_threshold;
+    config.migration_fee = params.migration_fee;
     config.initial_virtual_sol = params.initial_virtual_sol;
     config.initial_virtual_tokens = params.initial_virtual_tokens;
     config.total_tokens = 0;
     config.total_volume = 0;
     config.bump = ctx.bumps.global_config;
+    config.allowed_curve_kinds = params.allowed_curve_kinds;
+    config.paused = 0;
+    config.config_version = 0;
+    config.version = ACCOUNT_VERSION;
+    config.token_decimals = params.token_decimals;
+    config.total_supply = params.total_supply;
+    config.curve_supply = params.curve_supply;
+    config.amm_config = params.amm_config;
+    config.validate()?;
+
+    let clock = Clock::get()?;
+    emit!(ConfigUpdated {
+        authority: config.authority,
+        fee_admin: config.fee_admin,
+        pause_admin: config.pause_admin,
+        fee_recipient: config.fee_recipient,
+        fee_bps: config.fee_bps,
+        creator_fee_bps: config.creator_fee_bps,
+        max_referral_bps: config.max_referral_bps,
+        migration_threshold: config.migration_threshold,
+        migration_fee: config.migration_fee,
+        initial_virtual_sol: config.initial_virtual_sol,
+        initial_virtual_tokens: config.initial_virtual_tokens,
+        token_decimals: config.token_decimals,
+        total_supply: config.total_supply,
+        curve_supply: config.curve_supply,
+        amm_config: config.amm_config,
+        allowed_curve_kinds: config.allowed_curve_kinds.clone(),
+        config_version: config.config_version,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!("OpenClaw initialized with fee: {} bps", params.fee_bps);
 
@@ -1,10 +1,17 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Burn, SyncNative, Token};
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_interface::{self, Mint, SetAuthority, TokenAccount, TokenInterface, TransferChecked};
+use crate::amm::{self, Cpmm, InitializePool};
 use crate::error::OpenClawError;
-use crate::state::{BondingCurve, GlobalConfig, BONDING_CURVE_SEED, GLOBAL_CONFIG_SEED};
+use crate::events::Migrated;
+use crate::state::{BondingCurve, GlobalConfig, BONDING_CURVE_SEED, CURVE_SOL_VAULT_SEED, GLOBAL_CONFIG_SEED, MIGRATION_VAULT_SEED, PAUSE_MIGRATION};
 
 #[derive(Accounts)]
 pub struct Migrate<'info> {
+    /// Pays for the pool accounts and acts as the pool creator
     #[account(mut)]
     pub payer: Signer<'info>,
 
@@ -12,55 +19,349 @@ pub struct Migrate<'info> {
         seeds = [GLOBAL_CONFIG_SEED],
         bump = global_config.bump
     )]
-    pub global_config: Account<'info, GlobalConfig>,
+    pub global_config: Box<Account<'info, GlobalConfig>>,
 
     #[account(
         mut,
         seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
         bump = bonding_curve.bump
     )]
-    pub bonding_curve: Account<'info, BondingCurve>,
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
 
     #[account(
         mut,
-        associated_token::mint = bonding_curve.mint,
+        seeds = [CURVE_SOL_VAULT_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(mut, address = bonding_curve.mint @ OpenClawError::InvalidMint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = mint_token_program,
+    )]
+    pub curve_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Vault holding the migration allocation, required when the curve has one
+    #[account(
+        mut,
+        seeds = [MIGRATION_VAULT_SEED, bonding_curve.mint.as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = bonding_curve,
+        token::token_program = mint_token_program,
+    )]
+    pub migration_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Fee recipient
+    #[account(
+        mut,
+        constraint = fee_recipient.key() == global_config.fee_recipient
     )]
-    pub curve_token_vault: Account<'info, TokenAccount>,
+    pub fee_recipient: AccountInfo<'info>,
+
+    #[account(address = token::spl_token::native_mint::ID)]
+    pub wsol_mint: Box<Account<'info, token::Mint>>,
+
+    /// Payer's token account the curve tokens pass through on the way into the pool
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = payer,
+        associated_token::token_program = mint_token_program,
+    )]
+    pub payer_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Payer's wrapped SOL account the curve SOL passes through on the way into the pool
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = wsol_mint,
+        associated_token::authority = payer,
+    )]
+    pub payer_wsol_account: Box<Account<'info, token::TokenAccount>>,
+
+    pub amm_program: Program<'info, Cpmm>,
+
+    /// CHECK: Pinned in the global config, validated by the AMM program
+    #[account(address = global_config.amm_config @ OpenClawError::InvalidAmmConfig)]
+    pub amm_config: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the AMM program
+    pub amm_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Created by the AMM program
+    #[account(mut)]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// CHECK: Created by the AMM program
+    #[account(mut)]
+    pub lp_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Payer's LP token account, created by the AMM program
+    #[account(mut)]
+    pub payer_lp_token: UncheckedAccount<'info>,
 
-    // TODO: Add Raydium accounts for actual migration
-    // For now, this is a placeholder that marks the token as migrated
+    /// CHECK: Created by the AMM program
+    #[account(mut)]
+    pub token_0_vault: UncheckedAccount<'info>,
 
+    /// CHECK: Created by the AMM program
+    #[account(mut)]
+    pub token_1_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Validated by the AMM program
+    #[account(mut)]
+    pub create_pool_fee: UncheckedAccount<'info>,
+
+    /// CHECK: Created by the AMM program
+    #[account(mut)]
+    pub observation_state: UncheckedAccount<'info>,
+
+    /// SPL Token, owning wrapped SOL and the LP mint
     pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022, whichever owns the curve mint
+    pub mint_token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn handler(ctx: Context<Migrate>) -> Result<()> {
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let bonding_curve = &ctx.accounts.bonding_curve;
     let config = &ctx.accounts.global_config;
 
+    // Check not paused or frozen
+    config.check_not_paused(PAUSE_MIGRATION)?;
+    bonding_curve.check_not_frozen()?;
+
     // Check not already migrated
     require!(!bonding_curve.migrated, OpenClawError::AlreadyMigrated);
 
-    // Check migration threshold reached
+    // Check migration threshold reached. A complete curve stays migratable even if the
+    // threshold has since been raised.
     require!(
-        bonding_curve.real_sol_reserves >= config.migration_threshold,
+        bonding_curve.complete || bonding_curve.real_sol_reserves >= config.migration_threshold,
         OpenClawError::MigrationThresholdNotReached
     );
 
+    let migration_fee = config.migration_fee;
+    let sol_amount = bonding_curve
+        .real_sol_reserves
+        .checked_sub(migration_fee)
+        .filter(|amount| *amount > 0)
+        .ok_or(OpenClawError::InsufficientLiquidity)?;
+    // The pool gets every token left in the curve vault plus the migration allocation
+    let migration_tokens = bonding_curve.migration_tokens;
+    let token_amount = ctx
+        .accounts
+        .curve_token_vault
+        .amount
+        .checked_add(migration_tokens)
+        .ok_or(OpenClawError::MathOverflow)?;
+    require!(token_amount > 0, OpenClawError::InsufficientLiquidity);
+
+    let mint_key = bonding_curve.mint;
+    let seeds = &[
+        BONDING_CURVE_SEED,
+        mint_key.as_ref(),
+        &[bonding_curve.bump],
+    ];
+    let signer = &[&seeds[..]];
+    let vault_seeds = &[
+        CURVE_SOL_VAULT_SEED,
+        mint_key.as_ref(),
+        &[bonding_curve.sol_vault_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    // Pay the migration fee out of the curve's SOL
+    if migration_fee > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.sol_vault.to_account_info(),
+                    to: ctx.accounts.fee_recipient.to_account_info(),
+                },
+                vault_signer,
+            ),
+            migration_fee,
+        )?;
+    }
+
+    // Wrap the rest of the curve's SOL for the pool
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.sol_vault.to_account_info(),
+                to: ctx.accounts.payer_wsol_account.to_account_info(),
+            },
+            vault_signer,
+        ),
+        sol_amount,
+    )?;
+
+    token::sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.payer_wsol_account.to_account_info(),
+        },
+    ))?;
+
+    // Move the tokens out of the curve vault
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.mint_token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.curve_token_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.payer_token_account.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+            },
+            signer,
+        ),
+        ctx.accounts.curve_token_vault.amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // Then the migration allocation out of its vault
+    if migration_tokens > 0 {
+        let migration_vault = ctx
+            .accounts
+            .migration_vault
+            .as_ref()
+            .ok_or(ErrorCode::AccountNotEnoughKeys)?;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.mint_token_program.to_account_info(),
+                TransferChecked {
+                    from: migration_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.payer_token_account.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                signer,
+            ),
+            migration_tokens,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    // The AMM orders the pair by mint address
+    let token_is_0 = ctx.accounts.mint.key() < ctx.accounts.wsol_mint.key();
+    let token = (
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.payer_token_account.to_account_info(),
+        ctx.accounts.mint_token_program.to_account_info(),
+        token_amount,
+    );
+    let wsol = (
+        ctx.accounts.wsol_mint.to_account_info(),
+        ctx.accounts.payer_wsol_account.to_account_info(),
+        ctx.accounts.token_program.to_account_info(),
+        sol_amount,
+    );
+    let (
+        (mint_0, creator_token_0, token_0_program, amount_0),
+        (mint_1, creator_token_1, token_1_program, amount_1),
+    ) = if token_is_0 { (token, wsol) } else { (wsol, token) };
+
+    amm::initialize_pool(
+        CpiContext::new(
+            ctx.accounts.amm_program.to_account_info(),
+            InitializePool {
+                creator: ctx.accounts.payer.to_account_info(),
+                amm_config: ctx.accounts.amm_config.to_account_info(),
+                authority: ctx.accounts.amm_authority.to_account_info(),
+                pool_state: ctx.accounts.pool_state.to_account_info(),
+                token_0_mint: mint_0,
+                token_1_mint: mint_1,
+                lp_mint: ctx.accounts.lp_mint.to_account_info(),
+                creator_token_0,
+                creator_token_1,
+                creator_lp_token: ctx.accounts.payer_lp_token.to_account_info(),
+                token_0_vault: ctx.accounts.token_0_vault.to_account_info(),
+                token_1_vault: ctx.accounts.token_1_vault.to_account_info(),
+                create_pool_fee: ctx.accounts.create_pool_fee.to_account_info(),
+                observation_state: ctx.accounts.observation_state.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                token_0_program,
+                token_1_program,
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+        ),
+        amount_0,
+        amount_1,
+        0,
+    )?;
+
+    // Burn the LP tokens so the liquidity is locked forever
+    let lp_burned = {
+        let data = ctx.accounts.payer_lp_token.try_borrow_data()?;
+        let lp_account = token::TokenAccount::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(lp_account.mint, ctx.accounts.lp_mint.key(), OpenClawError::InvalidMint);
+        lp_account.amount
+    };
+    token::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.payer_lp_token.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        ),
+        lp_burned,
+    )?;
+
+    // Revoke the curve's mint authority so the supply is fixed
+    token_interface::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.mint_token_program.to_account_info(),
+            SetAuthority {
+                current_authority: ctx.accounts.bonding_curve.to_account_info(),
+                account_or_mint: ctx.accounts.mint.to_account_info(),
+            },
+            signer,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
     // Mark as migrated
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
     bonding_curve.migrated = true;
+    bonding_curve.real_sol_reserves = 0;
+    bonding_curve.real_token_reserves = 0;
+    bonding_curve.migration_tokens = 0;
+    bonding_curve.check_sol_vault(ctx.accounts.sol_vault.lamports())?;
 
-    // TODO: Implement actual Raydium migration
-    // 1. Create Raydium AMM pool
-    // 2. Transfer SOL and remaining tokens to pool
-    // 3. Burn LP tokens (lock liquidity forever)
+    let clock = Clock::get()?;
+    emit!(Migrated {
+        mint: bonding_curve.mint,
+        pool: ctx.accounts.pool_state.key(),
+        sol_amount,
+        token_amount,
+        migration_fee,
+        lp_burned,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!(
         "Token {} migrated to DEX with {} lamports and {} tokens",
         bonding_curve.mint,
-        bonding_curve.real_sol_reserves,
-        bonding_curve.real_token_reserves
+        sol_amount,
+        token_amount
     );
 
     Ok(())
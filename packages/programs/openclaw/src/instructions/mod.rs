@@ -1,9 +1,22 @@
 pub mod initialize;
 pub mod create_token;
 pub mod buy;
+pub mod buy_exact_tokens;
 pub mod sell;
+pub mod sell_for_exact_sol;
 pub mod migrate;
 pub mod update_config;
+pub mod claim_creator_fees;
+pub mod register_referrer;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod cancel_authority_proposal;
+pub mod set_paused;
+pub mod set_curve_frozen;
+pub mod upgrade_account;
+pub mod quote;
+pub mod open_position;
+pub mod close_position;
 
 pub use initialize::*;
 pub use create_token::*;
@@ -11,3 +24,13 @@ pub use buy::*;
 pub use sell::*;
 pub use migrate::*;
 pub use update_config::*;
+pub use claim_creator_fees::*;
+pub use register_referrer::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use set_paused::*;
+pub use set_curve_frozen::*;
+pub use upgrade_account::*;
+pub use quote::*;
+pub use open_position::*;
+pub use close_position::*;
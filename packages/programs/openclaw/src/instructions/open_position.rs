@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::events::PositionOpened;
+use crate::state::{BondingCurve, Position, BONDING_CURVE_SEED, POSITION_SEED};
+
+#[derive(Accounts)]
+pub struct OpenPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Position::LEN,
+        seeds = [POSITION_SEED, bonding_curve.mint.as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<OpenPosition>) -> Result<()> {
+    let position = &mut ctx.accounts.position;
+    position.mint = ctx.accounts.bonding_curve.mint;
+    position.owner = ctx.accounts.owner.key();
+    position.bump = ctx.bumps.position;
+
+    let clock = Clock::get()?;
+    emit!(PositionOpened {
+        mint: position.mint,
+        owner: position.owner,
+        position: position.key(),
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Position opened on {} for {}", position.mint, position.owner);
+
+    Ok(())
+}
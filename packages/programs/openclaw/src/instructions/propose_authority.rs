@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::events::AuthorityProposed;
+use crate::state::{GlobalConfig, GLOBAL_CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        constraint = authority.key() == global_config.authority @ OpenClawError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    require_keys_neq!(new_authority, Pubkey::default(), OpenClawError::InvalidAuthority);
+
+    let config = &mut ctx.accounts.global_config;
+    config.pending_authority = new_authority;
+
+    let clock = Clock::get()?;
+    emit!(AuthorityProposed {
+        authority: config.authority,
+        pending_authority: new_authority,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Proposed authority {}", new_authority);
+
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::state::{BondingCurve, GlobalConfig, BONDING_CURVE_SEED, GLOBAL_CONFIG_SEED, PAUSE_TRADING};
+
+/// Direction of a quoted trade
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeSide {
+    /// Spend `amount` lamports, as `buy` does
+    Buy,
+    /// Sell `amount` tokens, as `sell` does
+    Sell,
+}
+
+/// Outcome of a trade as `buy` or `sell` would execute it right now
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TradeQuote {
+    /// SOL charged to the buyer (fees included) or paid to the seller (fees excluded).
+    /// A buy that reaches the migration threshold is charged less than it offered.
+    pub sol_amount: u64,
+    /// Tokens received by the buyer or sold by the seller
+    pub token_amount: u64,
+    /// Platform fee (in lamports), referral share included
+    pub fee: u64,
+    /// Creator fee (in lamports)
+    pub creator_fee: u64,
+    /// Marginal price before the trade, lamports per base unit scaled by `CURVE_PRICE_SCALE`
+    pub price_before: u128,
+    /// Marginal price after the trade, lamports per base unit scaled by `CURVE_PRICE_SCALE`
+    pub price_after: u128,
+    /// Change from `price_before` to `price_after` in basis points
+    pub price_impact_bps: u64,
+    /// Whether the trade completes the curve, by reaching the migration threshold or
+    /// selling out
+    pub completes_curve: bool,
+}
+
+#[derive(Accounts)]
+pub struct Quote<'info> {
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+pub fn handler(ctx: Context<Quote>, side: TradeSide, amount: u64) -> Result<TradeQuote> {
+    let config = &ctx.accounts.global_config;
+    let bonding_curve = &ctx.accounts.bonding_curve;
+
+    // Fail the same way the trade would
+    config.check_not_paused(PAUSE_TRADING)?;
+    bonding_curve.check_not_frozen()?;
+    bonding_curve.check_tradable()?;
+
+    let fee_bps = bonding_curve.total_fee_bps(config.fee_bps)?;
+    let price_before = bonding_curve.spot_price()?;
+    let mut after = (**bonding_curve).clone();
+
+    let (sol_amount, token_amount, fee) = match side {
+        TradeSide::Buy => {
+            let (sol_in, fee, tokens_out) =
+                bonding_curve.get_capped_buy(amount, fee_bps, config.migration_threshold)?;
+            require!(
+                tokens_out <= bonding_curve.real_token_reserves,
+                OpenClawError::InsufficientLiquidity
+            );
+            after.apply_buy(sol_in - fee, tokens_out)?;
+            (sol_in, tokens_out, fee)
+        }
+        TradeSide::Sell => {
+            let (sol_out, fee) = bonding_curve.get_sol_out(amount, fee_bps)?;
+            let sol_out_plus_fee = sol_out
+                .checked_add(fee)
+                .ok_or(OpenClawError::MathOverflow)?;
+            require!(
+                sol_out_plus_fee <= bonding_curve.real_sol_reserves,
+                OpenClawError::InsufficientLiquidity
+            );
+            after.apply_sell(amount, sol_out_plus_fee)?;
+            (sol_out, amount, fee)
+        }
+    };
+
+    let (platform_fee, creator_fee) = bonding_curve.split_fee(fee, config.fee_bps)?;
+    let price_after = after.spot_price()?;
+    let price_impact_bps = price_after
+        .abs_diff(price_before)
+        .checked_mul(10_000)
+        .ok_or(OpenClawError::MathOverflow)?
+        .checked_div(price_before)
+        .unwrap_or(0);
+
+    Ok(TradeQuote {
+        sol_amount,
+        token_amount,
+        fee: platform_fee,
+        creator_fee,
+        price_before,
+        price_after,
+        price_impact_bps: u64::try_from(price_impact_bps)
+            .map_err(|_| error!(OpenClawError::MathOverflow))?,
+        completes_curve: side == TradeSide::Buy && after.is_complete_after(config),
+    })
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::events::ReferrerRegistered;
+use crate::state::{GlobalConfig, Referrer, GLOBAL_CONFIG_SEED, REFERRER_SEED};
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = Referrer::LEN,
+        seeds = [REFERRER_SEED, wallet.key().as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterReferrer>, referral_bps: u16) -> Result<()> {
+    require!(
+        referral_bps <= ctx.accounts.global_config.max_referral_bps,
+        OpenClawError::ReferralFeeTooHigh
+    );
+
+    // Registering again only changes the requested share; totals carry over
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.wallet = ctx.accounts.wallet.key();
+    referrer.referral_bps = referral_bps;
+    referrer.bump = ctx.bumps.referrer;
+
+    let clock = Clock::get()?;
+    emit!(ReferrerRegistered {
+        wallet: referrer.wallet,
+        referral_bps,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Referrer {} registered at {} bps", referrer.wallet, referral_bps);
+
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use crate::error::OpenClawError;
-use crate::state::{BondingCurve, GlobalConfig, BONDING_CURVE_SEED, GLOBAL_CONFIG_SEED};
+use crate::events::Trade;
+use crate::state::{BondingCurve, GlobalConfig, Position, PriceHistory, Referrer, BONDING_CURVE_SEED, CURVE_SOL_VAULT_SEED, GLOBAL_CONFIG_SEED, POSITION_SEED, PRICE_HISTORY_SEED, REFERRER_SEED, PAUSE_TRADING};
 
 #[derive(Accounts)]
 pub struct Sell<'info> {
@@ -22,19 +24,31 @@ pub struct Sell<'info> {
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
+    #[account(address = bonding_curve.mint @ OpenClawError::InvalidMint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [CURVE_SOL_VAULT_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.sol_vault_bump
+    )]
+    pub sol_vault: SystemAccount<'info>,
+
     #[account(
         mut,
-        associated_token::mint = bonding_curve.mint,
+        associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    pub curve_token_vault: Account<'info, TokenAccount>,
+    pub curve_token_vault: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
         mut,
-        associated_token::mint = bonding_curve.mint,
+        associated_token::mint = mint,
         associated_token::authority = seller,
+        associated_token::token_program = token_program,
     )]
-    pub seller_token_account: Account<'info, TokenAccount>,
+    pub seller_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// CHECK: Fee recipient
     #[account(
@@ -43,87 +57,213 @@ pub struct Sell<'info> {
     )]
     pub fee_recipient: AccountInfo<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022, whichever owns the mint
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
+
+    /// Referrer that routed the trade, if any
+    #[account(
+        mut,
+        seeds = [REFERRER_SEED, referrer.wallet.as_ref()],
+        bump = referrer.bump
+    )]
+    pub referrer: Option<Account<'info, Referrer>>,
+
+    /// CHECK: Referrer's wallet, checked against `referrer`
+    #[account(mut)]
+    pub referrer_wallet: Option<UncheckedAccount<'info>>,
+
+    /// Seller's position, updated when given
+    #[account(
+        mut,
+        seeds = [POSITION_SEED, bonding_curve.mint.as_ref(), seller.key().as_ref()],
+        bump = position.bump
+    )]
+    pub position: Option<Account<'info, Position>>,
+
+    /// Created on the first trade for curves that predate price histories
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = PriceHistory::LEN,
+        seeds = [PRICE_HISTORY_SEED, bonding_curve.mint.as_ref()],
+        bump
+    )]
+    pub price_history: Box<Account<'info, PriceHistory>>,
 }
 
 pub fn handler(ctx: Context<Sell>, token_amount: u64, min_sol_out: u64) -> Result<()> {
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
-    let config = &mut ctx.accounts.global_config;
+    let bonding_curve = &ctx.accounts.bonding_curve;
 
-    // Check not migrated
-    require!(!bonding_curve.migrated, OpenClawError::AlreadyMigrated);
+    // Check not paused, frozen, migrated or complete
+    ctx.accounts.global_config.check_not_paused(PAUSE_TRADING)?;
+    bonding_curve.check_not_frozen()?;
+    bonding_curve.check_tradable()?;
 
     // Calculate SOL out and fee
-    let (sol_out, fee) = bonding_curve.get_sol_out(token_amount, config.fee_bps)?;
+    let fee_bps = bonding_curve.total_fee_bps(ctx.accounts.global_config.fee_bps)?;
+    let (sol_out, fee) = bonding_curve.get_sol_out(token_amount, fee_bps)?;
 
     // Check slippage
     require!(sol_out >= min_sol_out, OpenClawError::SlippageExceeded);
 
-    // Check sufficient SOL in curve
-    require!(
-        sol_out <= bonding_curve.real_sol_reserves,
-        OpenClawError::InsufficientLiquidity
-    );
-
-    // Transfer tokens from seller to curve vault
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.seller_token_account.to_account_info(),
-                to: ctx.accounts.curve_token_vault.to_account_info(),
-                authority: ctx.accounts.seller.to_account_info(),
-            },
-        ),
-        token_amount,
-    )?;
-
-    let sol_out_plus_fee = sol_out.saturating_add(fee);
-
-    // Transfer SOL from curve to seller
-    **bonding_curve.to_account_info().try_borrow_mut_lamports()? -= sol_out_plus_fee;
-    **ctx.accounts.seller.to_account_info().try_borrow_mut_lamports()? += sol_out;
-
-    // Transfer fee to fee recipient
-    if fee > 0 {
-        **ctx.accounts.fee_recipient.to_account_info().try_borrow_mut_lamports()? += fee;
-    }
+    ctx.accounts
+        .execute(token_amount, sol_out, fee, ctx.bumps.price_history)
+}
+
+impl<'info> Sell<'info> {
+    /// Move `token_amount` into the curve and pay out `sol_out` plus `fee`, the platform
+    /// and creator fee together
+    pub(crate) fn execute(
+        &mut self,
+        token_amount: u64,
+        sol_out: u64,
+        fee: u64,
+        price_history_bump: u8,
+    ) -> Result<()> {
+        let bonding_curve = &mut self.bonding_curve;
+        let config = &mut self.global_config;
+
+        // Check sufficient SOL in curve to pay out both the seller and the fee
+        let sol_out_plus_fee = sol_out
+            .checked_add(fee)
+            .ok_or(OpenClawError::MathOverflow)?;
+        require!(
+            sol_out_plus_fee <= bonding_curve.real_sol_reserves,
+            OpenClawError::InsufficientLiquidity
+        );
+        let (platform_fee, creator_fee) = bonding_curve.split_fee(fee, config.fee_bps)?;
+
+        // The referrer's share comes out of the platform fee
+        let referral_fee = match (&self.referrer, &self.referrer_wallet) {
+            (Some(referrer), Some(wallet)) => {
+                referrer.check_wallet(wallet.key, self.seller.key)?;
+                referrer.referral_fee(platform_fee, config.max_referral_bps)?
+            }
+            (None, None) => 0,
+            _ => return err!(OpenClawError::InvalidReferrer),
+        };
+        let platform_fee = platform_fee - referral_fee;
+
+        // Transfer tokens from seller to curve vault
+        token_interface::transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.seller_token_account.to_account_info(),
+                    mint: self.mint.to_account_info(),
+                    to: self.curve_token_vault.to_account_info(),
+                    authority: self.seller.to_account_info(),
+                },
+            ),
+            token_amount,
+            self.mint.decimals,
+        )?;
+
+        let mint_key = bonding_curve.mint;
+        let vault_seeds = &[
+            CURVE_SOL_VAULT_SEED,
+            mint_key.as_ref(),
+            &[bonding_curve.sol_vault_bump],
+        ];
+        let vault_signer = &[&vault_seeds[..]];
+
+        // Transfer SOL from the curve's SOL vault to seller
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: self.sol_vault.to_account_info(),
+                    to: self.seller.to_account_info(),
+                },
+                vault_signer,
+            ),
+            sol_out,
+        )?;
 
-    // Update bonding curve state
-    bonding_curve.virtual_sol_reserves = bonding_curve
-        .virtual_sol_reserves
-        .checked_sub(sol_out_plus_fee)
-        .ok_or(OpenClawError::MathOverflow)?;
-    bonding_curve.virtual_token_reserves = bonding_curve
-        .virtual_token_reserves
-        .checked_add(token_amount)
-        .ok_or(OpenClawError::MathOverflow)?;
-    bonding_curve.real_sol_reserves = bonding_curve
-        .real_sol_reserves
-        .checked_sub(sol_out_plus_fee)
-        .ok_or(OpenClawError::MathOverflow)?;
-    bonding_curve.real_token_reserves = bonding_curve
-        .real_token_reserves
-        .checked_add(token_amount)
-        .ok_or(OpenClawError::MathOverflow)?;
-    bonding_curve.tokens_sold = bonding_curve
-        .tokens_sold
-        .checked_sub(token_amount)
-        .ok_or(OpenClawError::MathOverflow)?;
-
-    // Update global volume
-    config.total_volume = config
-        .total_volume
-        .checked_add(sol_out_plus_fee)
-        .ok_or(OpenClawError::MathOverflow)?;
-
-    msg!(
-        "Sell: {} tokens -> {} lamports (fee: {} lamports)",
-        token_amount,
-        sol_out,
-        fee
-    );
-
-    Ok(())
+        // Transfer platform fee to fee recipient. The creator fee stays in the vault
+        // until the creator claims it.
+        if platform_fee > 0 {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.sol_vault.to_account_info(),
+                        to: self.fee_recipient.to_account_info(),
+                    },
+                    vault_signer,
+                ),
+                platform_fee,
+            )?;
+        }
+
+        // Transfer referral fee to the referrer
+        if let Some(wallet) = &self.referrer_wallet {
+            if referral_fee > 0 {
+                system_program::transfer(
+                    CpiContext::new_with_signer(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: self.sol_vault.to_account_info(),
+                            to: wallet.to_account_info(),
+                        },
+                        vault_signer,
+                    ),
+                    referral_fee,
+                )?;
+            }
+        }
+        if let Some(referrer) = &mut self.referrer {
+            referrer.record(sol_out_plus_fee, referral_fee)?;
+        }
+        let clock = Clock::get()?;
+        if let Some(position) = &mut self.position {
+            position.record_sell(token_amount, sol_out, fee, clock.unix_timestamp)?;
+        }
+
+        // Update bonding curve state, recording the price it traded at until now
+        self.price_history.start(bonding_curve.mint, price_history_bump);
+        self.price_history.record(clock.slot, bonding_curve.spot_price()?);
+        bonding_curve.apply_sell(token_amount, sol_out_plus_fee)?;
+        bonding_curve.creator_fees_accrued = bonding_curve
+            .creator_fees_accrued
+            .checked_add(creator_fee)
+            .ok_or(OpenClawError::MathOverflow)?;
+
+        bonding_curve.check_sol_vault(self.sol_vault.lamports())?;
+
+        // Update global volume
+        config.total_volume = config
+            .total_volume
+            .checked_add(sol_out_plus_fee)
+            .ok_or(OpenClawError::MathOverflow)?;
+
+        emit!(Trade {
+            mint: bonding_curve.mint,
+            user: self.seller.key(),
+            is_buy: false,
+            sol_amount: sol_out,
+            token_amount,
+            fee: platform_fee,
+            creator_fee,
+            referrer: self.referrer_wallet.as_ref().map(|wallet| wallet.key()),
+            referral_fee,
+            virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
+            virtual_token_reserves: bonding_curve.virtual_token_reserves,
+            real_sol_reserves: bonding_curve.real_sol_reserves,
+            real_token_reserves: bonding_curve.real_token_reserves,
+            slot: clock.slot,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!(
+            "Sell: {} tokens -> {} lamports (fee: {} lamports, creator fee: {} lamports)",
+            token_amount,
+            sol_out,
+            platform_fee,
+            creator_fee
+        );
+
+        Ok(())
+    }
 }
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::instructions::Sell;
+use crate::state::PAUSE_TRADING;
+
+pub fn handler(ctx: Context<Sell>, sol_out: u64, max_tokens_in: u64) -> Result<()> {
+    let bonding_curve = &ctx.accounts.bonding_curve;
+
+    // Check not paused, frozen, migrated or complete
+    ctx.accounts.global_config.check_not_paused(PAUSE_TRADING)?;
+    bonding_curve.check_not_frozen()?;
+    bonding_curve.check_tradable()?;
+    require!(sol_out > 0, OpenClawError::InsufficientSolAmount);
+
+    // Calculate tokens in and fee
+    let fee_bps = bonding_curve.total_fee_bps(ctx.accounts.global_config.fee_bps)?;
+    let (tokens_in, fee) = bonding_curve.get_tokens_in(sol_out, fee_bps)?;
+
+    // Check slippage
+    require!(tokens_in <= max_tokens_in, OpenClawError::SlippageExceeded);
+
+    ctx.accounts
+        .execute(tokens_in, sol_out, fee, ctx.bumps.price_history)
+}
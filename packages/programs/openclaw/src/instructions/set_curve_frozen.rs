@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::events::CurveFrozen;
+use crate::state::{BondingCurve, GlobalConfig, BONDING_CURVE_SEED, GLOBAL_CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct SetCurveFrozen<'info> {
+    #[account(
+        constraint = global_config.is_pause_admin(&admin.key()) @ OpenClawError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        seeds = [BONDING_CURVE_SEED, bonding_curve.mint.as_ref()],
+        bump = bonding_curve.bump
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+}
+
+pub fn handler(ctx: Context<SetCurveFrozen>, frozen: bool) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.frozen = frozen;
+
+    let clock = Clock::get()?;
+    emit!(CurveFrozen {
+        mint: bonding_curve.mint,
+        admin: ctx.accounts.admin.key(),
+        frozen,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Curve {} frozen: {}", bonding_curve.mint, frozen);
+
+    Ok(())
+}
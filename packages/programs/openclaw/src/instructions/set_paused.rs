@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::error::OpenClawError;
+use crate::events::PauseUpdated;
+use crate::state::{GlobalConfig, GLOBAL_CONFIG_SEED, PAUSE_ALL};
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        constraint = global_config.is_pause_admin(&admin.key()) @ OpenClawError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
+
+pub fn handler(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+    require!(paused & !PAUSE_ALL == 0, OpenClawError::InvalidPauseFlags);
+
+    let config = &mut ctx.accounts.global_config;
+    config.paused = paused;
+
+    let clock = Clock::get()?;
+    emit!(PauseUpdated {
+        admin: ctx.accounts.admin.key(),
+        paused,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Updated pause flags to {:#05b}", paused);
+
+    Ok(())
+}
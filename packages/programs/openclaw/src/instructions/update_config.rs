@@ -1,18 +1,55 @@
 use anchor_lang::prelude::*;
+use crate::curve::CurveKind;
 use crate::error::OpenClawError;
+use crate::events::ConfigUpdated;
 use crate::state::{GlobalConfig, GLOBAL_CONFIG_SEED};
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+#[derive(AnchorSerialize, AnchorDeserialize, Default, PartialEq)]
 pub struct UpdateConfigParams {
     pub fee_bps: Option<u16>,
+    pub creator_fee_bps: Option<u16>,
+    pub max_referral_bps: Option<u16>,
     pub migration_threshold: Option<u64>,
+    pub migration_fee: Option<u64>,
+    /// Applies to curves created from now on
+    pub initial_virtual_sol: Option<u64>,
+    /// Applies to curves created from now on
+    pub initial_virtual_tokens: Option<u64>,
+    /// Applies to curves created from now on
+    pub token_decimals: Option<u8>,
+    /// Applies to curves created from now on
+    pub total_supply: Option<u64>,
+    /// Applies to curves created from now on
+    pub curve_supply: Option<u64>,
     pub fee_recipient: Option<Pubkey>,
+    /// CP-Swap config migrated pools are created under
+    pub amm_config: Option<Pubkey>,
+    pub allowed_curve_kinds: Option<Vec<CurveKind>>,
+    /// Set to the default pubkey to clear the role
+    pub fee_admin: Option<Pubkey>,
+    /// Set to the default pubkey to clear the role
+    pub pause_admin: Option<Pubkey>,
+}
+
+impl UpdateConfigParams {
+    /// Whether only fee rates change, which the fee admin may do on its own. Anything
+    /// not listed here, the fee recipient included, needs the authority.
+    pub fn is_fee_only(&self) -> bool {
+        let fee_changes = Self {
+            fee_bps: self.fee_bps,
+            creator_fee_bps: self.creator_fee_bps,
+            max_referral_bps: self.max_referral_bps,
+            migration_fee: self.migration_fee,
+            ..Self::default()
+        };
+        *self == fee_changes
+    }
 }
 
 #[derive(Accounts)]
 pub struct UpdateConfig<'info> {
     #[account(
-        constraint = authority.key() == global_config.authority @ OpenClawError::Unauthorized
+        constraint = global_config.is_fee_admin(&authority.key()) @ OpenClawError::Unauthorized
     )]
     pub authority: Signer<'info>,
 
@@ -27,21 +64,117 @@ pub struct UpdateConfig<'info> {
 pub fn handler(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
     let config = &mut ctx.accounts.global_config;
 
+    // The fee admin can only touch fee settings, everything else needs the authority
+    if ctx.accounts.authority.key() != config.authority {
+        require!(params.is_fee_only(), OpenClawError::Unauthorized);
+    }
+
+    // Configs from before the launch supply fields store what they have been minting
+    (config.token_decimals, config.total_supply, config.curve_supply) = config.launch_supply();
+
     if let Some(fee_bps) = params.fee_bps {
-        require!(fee_bps <= 1000, OpenClawError::InvalidFeeConfig); // Max 10%
         config.fee_bps = fee_bps;
         msg!("Updated fee to {} bps", fee_bps);
     }
 
+    if let Some(creator_fee_bps) = params.creator_fee_bps {
+        config.creator_fee_bps = creator_fee_bps;
+        msg!("Updated creator fee to {} bps", creator_fee_bps);
+    }
+
+    if let Some(max_referral_bps) = params.max_referral_bps {
+        config.max_referral_bps = max_referral_bps;
+        msg!("Updated referral cap to {} bps", max_referral_bps);
+    }
+
     if let Some(migration_threshold) = params.migration_threshold {
         config.migration_threshold = migration_threshold;
         msg!("Updated migration threshold to {} lamports", migration_threshold);
     }
 
+    if let Some(migration_fee) = params.migration_fee {
+        config.migration_fee = migration_fee;
+        msg!("Updated migration fee to {} lamports", migration_fee);
+    }
+
+    if let Some(initial_virtual_sol) = params.initial_virtual_sol {
+        config.initial_virtual_sol = initial_virtual_sol;
+        msg!("Updated initial virtual SOL to {} lamports", initial_virtual_sol);
+    }
+
+    if let Some(initial_virtual_tokens) = params.initial_virtual_tokens {
+        config.initial_virtual_tokens = initial_virtual_tokens;
+        msg!("Updated initial virtual tokens to {}", initial_virtual_tokens);
+    }
+
+    if let Some(token_decimals) = params.token_decimals {
+        config.token_decimals = token_decimals;
+        msg!("Updated token decimals to {}", token_decimals);
+    }
+
+    if let Some(total_supply) = params.total_supply {
+        config.total_supply = total_supply;
+        msg!("Updated total supply to {}", total_supply);
+    }
+
+    if let Some(curve_supply) = params.curve_supply {
+        config.curve_supply = curve_supply;
+        msg!("Updated curve supply to {}", curve_supply);
+    }
+
     if let Some(fee_recipient) = params.fee_recipient {
         config.fee_recipient = fee_recipient;
         msg!("Updated fee recipient to {}", fee_recipient);
     }
 
+    if let Some(amm_config) = params.amm_config {
+        config.amm_config = amm_config;
+        msg!("Updated AMM config to {}", amm_config);
+    }
+
+    if let Some(allowed_curve_kinds) = params.allowed_curve_kinds {
+        msg!("Updated allowed curve kinds: {:?}", allowed_curve_kinds);
+        config.allowed_curve_kinds = allowed_curve_kinds;
+    }
+
+    if let Some(fee_admin) = params.fee_admin {
+        config.fee_admin = fee_admin;
+        msg!("Updated fee admin to {}", fee_admin);
+    }
+
+    if let Some(pause_admin) = params.pause_admin {
+        config.pause_admin = pause_admin;
+        msg!("Updated pause admin to {}", pause_admin);
+    }
+
+    config.validate()?;
+    config.config_version = config
+        .config_version
+        .checked_add(1)
+        .ok_or(OpenClawError::MathOverflow)?;
+
+    let clock = Clock::get()?;
+    emit!(ConfigUpdated {
+        authority: config.authority,
+        fee_admin: config.fee_admin,
+        pause_admin: config.pause_admin,
+        fee_recipient: config.fee_recipient,
+        fee_bps: config.fee_bps,
+        creator_fee_bps: config.creator_fee_bps,
+        max_referral_bps: config.max_referral_bps,
+        migration_threshold: config.migration_threshold,
+        migration_fee: config.migration_fee,
+        initial_virtual_sol: config.initial_virtual_sol,
+        initial_virtual_tokens: config.initial_virtual_tokens,
+        token_decimals: config.token_decimals,
+        total_supply: config.total_supply,
+        curve_supply: config.curve_supply,
+        amm_config: config.amm_config,
+        allowed_curve_kinds: config.allowed_curve_kinds.clone(),
+        config_version: config.config_version,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
     Ok(())
 }
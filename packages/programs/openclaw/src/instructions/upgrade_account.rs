@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::error::OpenClawError;
+use crate::events::AccountUpgraded;
+use crate::state::{BondingCurve, BondingCurveV1, GlobalConfig, GlobalConfigV1, ACCOUNT_VERSION, CURVE_SOL_VAULT_SEED, GLOBAL_CONFIG_SEED};
+
+#[derive(Accounts)]
+pub struct UpgradeAccount<'info> {
+    /// Admin authority, pays for the extra rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Read as raw bytes, since the config may itself be on an old layout.
+    /// The authority is its first field, which every layout keeps in place.
+    #[account(seeds = [GLOBAL_CONFIG_SEED], bump)]
+    pub global_config: UncheckedAccount<'info>,
+
+    /// CHECK: `GlobalConfig` or `BondingCurve` to upgrade, told apart by discriminator.
+    /// Pass the global config here as well to upgrade it.
+    #[account(mut, owner = crate::ID)]
+    pub account: UncheckedAccount<'info>,
+
+    /// CHECK: SOL vault of the curve being upgraded, checked against its mint. Version 1
+    /// curves held their SOL themselves, so it moves here. Only needed for curves.
+    #[account(mut)]
+    pub sol_vault: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpgradeAccount>) -> Result<()> {
+    let authority = {
+        let data = ctx.accounts.global_config.try_borrow_data()?;
+        data.get(8..40)
+            .and_then(|bytes| Pubkey::try_from(bytes).ok())
+            .ok_or(OpenClawError::UnsupportedAccount)?
+    };
+    require_keys_eq!(
+        ctx.accounts.authority.key(),
+        authority,
+        OpenClawError::Unauthorized
+    );
+
+    let account = ctx.accounts.account.to_account_info();
+    let discriminator: [u8; 8] = account
+        .try_borrow_data()?
+        .get(..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(OpenClawError::UnsupportedAccount)?;
+    let is_config = discriminator == GlobalConfig::DISCRIMINATOR;
+    let (len, v1_len) = if is_config {
+        (GlobalConfig::LEN, GlobalConfigV1::LEN)
+    } else if discriminator == BondingCurve::DISCRIMINATOR {
+        (BondingCurve::LEN, BondingCurveV1::LEN)
+    } else {
+        return err!(OpenClawError::UnsupportedAccount);
+    };
+
+    // Version 1 is the only older layout, and it was never resized
+    require!(account.data_len() != len, OpenClawError::AccountUpToDate);
+    require!(
+        account.data_len() == v1_len,
+        OpenClawError::UnsupportedAccount
+    );
+
+    let rent = Rent::get()?;
+    if is_config {
+        let config = GlobalConfigV1::deserialize(&mut &account.try_borrow_data()?[8..])?.upgrade();
+
+        ctx.accounts.top_up(&account, rent.minimum_balance(len))?;
+        account.realloc(len, true)?;
+        config.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+    } else {
+        let old = BondingCurveV1::deserialize(&mut &account.try_borrow_data()?[8..])?;
+        let sol_vault = ctx
+            .accounts
+            .sol_vault
+            .as_ref()
+            .ok_or(ErrorCode::AccountNotEnoughKeys)?
+            .to_account_info();
+        let (vault_key, vault_bump) = Pubkey::find_program_address(
+            &[CURVE_SOL_VAULT_SEED, old.mint.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(sol_vault.key(), vault_key, ErrorCode::ConstraintSeeds);
+        let curve = old.upgrade(vault_bump);
+
+        // Fund the vault's rent and the curve's rent at its new size, then move the
+        // reserves out of the curve and into the vault
+        let reserves = curve.real_sol_reserves;
+        ctx.accounts.top_up(&sol_vault, rent.minimum_balance(0))?;
+        ctx.accounts.top_up(
+            &account,
+            rent.minimum_balance(len)
+                .checked_add(reserves)
+                .ok_or(OpenClawError::MathOverflow)?,
+        )?;
+        account.realloc(len, true)?;
+        **account.try_borrow_mut_lamports()? -= reserves;
+        **sol_vault.try_borrow_mut_lamports()? += reserves;
+
+        curve.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+        curve.check_sol_vault(sol_vault.lamports())?;
+    }
+
+    // Accounts on the old layout predate the version field
+    let from_version = 1;
+    let clock = Clock::get()?;
+    emit!(AccountUpgraded {
+        account: account.key(),
+        from_version,
+        to_version: ACCOUNT_VERSION,
+        len: len as u64,
+        slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Upgraded {} from v{} to v{}",
+        account.key(),
+        from_version,
+        ACCOUNT_VERSION
+    );
+
+    Ok(())
+}
+
+impl<'info> UpgradeAccount<'info> {
+    /// Have the authority bring `account` up to `lamports`
+    fn top_up(&self, account: &AccountInfo<'info>, lamports: u64) -> Result<()> {
+        let shortfall = lamports.saturating_sub(account.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.authority.to_account_info(),
+                        to: account.clone(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        Ok(())
+    }
+}
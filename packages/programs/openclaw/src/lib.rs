@@ -1,12 +1,15 @@
 use anchor_lang::prelude::*;
 
+pub mod amm;
+pub mod curve;
 pub mod error;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 use instructions::*;
 
-declare_id!("CLAWxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx");
+declare_id!("CLAWMASi45ub7Qe4ZE36UT5G6cU4ud8Fhhe4deS4F3cw");
 
 #[program]
 pub mod openclaw {
@@ -27,12 +30,39 @@ pub mod openclaw {
         instructions::buy::handler(ctx, sol_amount, min_tokens_out)
     }
 
+    /// Buy exactly `tokens_out` tokens, paying at most `max_sol_in` lamports
+    pub fn buy_exact_tokens(ctx: Context<Buy>, tokens_out: u64, max_sol_in: u64) -> Result<()> {
+        instructions::buy_exact_tokens::handler(ctx, tokens_out, max_sol_in)
+    }
+
     /// Sell tokens back to the bonding curve
     pub fn sell(ctx: Context<Sell>, token_amount: u64, min_sol_out: u64) -> Result<()> {
         instructions::sell::handler(ctx, token_amount, min_sol_out)
     }
 
-    /// Migrate liquidity to Raydium when threshold is reached
+    /// Sell at most `max_tokens_in` tokens to receive exactly `sol_out` lamports
+    pub fn sell_for_exact_sol(ctx: Context<Sell>, sol_out: u64, max_tokens_in: u64) -> Result<()> {
+        instructions::sell_for_exact_sol::handler(ctx, sol_out, max_tokens_in)
+    }
+
+    /// Quote a buy or sell with the on-chain math, returned as `TradeQuote` return data.
+    /// Meant to be simulated rather than sent.
+    pub fn quote(ctx: Context<Quote>, side: TradeSide, amount: u64) -> Result<TradeQuote> {
+        instructions::quote::handler(ctx, side, amount)
+    }
+
+    /// Open the caller's position on a curve, to track its trades and to buy during the
+    /// launch window
+    pub fn open_position(ctx: Context<OpenPosition>) -> Result<()> {
+        instructions::open_position::handler(ctx)
+    }
+
+    /// Close an empty position and return its rent
+    pub fn close_position(ctx: Context<ClosePosition>) -> Result<()> {
+        instructions::close_position::handler(ctx)
+    }
+
+    /// Migrate liquidity to a Raydium CP-Swap pool when threshold is reached
     pub fn migrate(ctx: Context<Migrate>) -> Result<()> {
         instructions::migrate::handler(ctx)
     }
@@ -41,4 +71,44 @@ pub mod openclaw {
     pub fn update_config(ctx: Context<UpdateConfig>, params: UpdateConfigParams) -> Result<()> {
         instructions::update_config::handler(ctx, params)
     }
+
+    /// Pay the creator fees accrued on a curve out to its creator (creator only)
+    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+        instructions::claim_creator_fees::handler(ctx)
+    }
+
+    /// Register as a referrer, or change the share of the platform fee requested
+    pub fn register_referrer(ctx: Context<RegisterReferrer>, referral_bps: u16) -> Result<()> {
+        instructions::register_referrer::handler(ctx, referral_bps)
+    }
+
+    /// Propose a new admin authority, which takes over once it accepts (admin only)
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    /// Accept a pending authority proposal (proposed authority only)
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    /// Withdraw a pending authority proposal (admin only)
+    pub fn cancel_authority_proposal(ctx: Context<ProposeAuthority>) -> Result<()> {
+        instructions::cancel_authority_proposal::handler(ctx)
+    }
+
+    /// Pause or resume token creation, trading and migration (pause admin only)
+    pub fn set_paused(ctx: Context<SetPaused>, paused: u8) -> Result<()> {
+        instructions::set_paused::handler(ctx, paused)
+    }
+
+    /// Freeze or unfreeze trading and migration on one curve (pause admin only)
+    pub fn set_curve_frozen(ctx: Context<SetCurveFrozen>, frozen: bool) -> Result<()> {
+        instructions::set_curve_frozen::handler(ctx, frozen)
+    }
+
+    /// Realloc a global config or bonding curve to the current account layout (admin only)
+    pub fn upgrade_account(ctx: Context<UpgradeAccount>) -> Result<()> {
+        instructions::upgrade_account::handler(ctx)
+    }
 }
@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use openclaw_math::CurveState;
+use crate::curve::CurveKind;
+use crate::error::OpenClawError;
 
 /// Global configuration for the OpenClaw protocol
 #[account]
@@ -6,15 +9,27 @@ use anchor_lang::prelude::*;
 pub struct GlobalConfig {
     /// Admin authority
     pub authority: Pubkey,
+    /// Authority proposed by the current one, default when there is no proposal
+    pub pending_authority: Pubkey,
+    /// Extra signer allowed to change fee settings, default when unset
+    pub fee_admin: Pubkey,
+    /// Extra signer allowed to pause the protocol, default when unset
+    pub pause_admin: Pubkey,
     /// Fee recipient
     pub fee_recipient: Pubkey,
     /// Platform fee in basis points (1% = 100)
     pub fee_bps: u16,
+    /// Creator fee in basis points given to new curves
+    pub creator_fee_bps: u16,
+    /// Highest share of the platform fee, in basis points of the fee, paid to referrers
+    pub max_referral_bps: u16,
     /// SOL threshold for migration (in lamports)
     pub migration_threshold: u64,
+    /// Flat fee taken from the curve's SOL on migration (in lamports)
+    pub migration_fee: u64,
     /// Initial virtual SOL reserves (in lamports)
     pub initial_virtual_sol: u64,
-    /// Initial virtual token reserves
+    /// Initial virtual token reserves, which price the curve
     pub initial_virtual_tokens: u64,
     /// Total tokens created
     pub total_tokens: u64,
@@ -22,19 +37,143 @@ pub struct GlobalConfig {
     pub total_volume: u64,
     /// Bump seed
     pub bump: u8,
+    /// Curve kinds creators may pick from
+    pub allowed_curve_kinds: Vec<CurveKind>,
+    /// Paused operations, a combination of the `PAUSE_*` flags
+    pub paused: u8,
+    /// Bumped on every config update, so curves record what they were created under
+    pub config_version: u32,
+    /// Account layout version
+    pub version: u8,
+    /// Decimals of new mints, one of `TOKEN_DECIMALS`
+    pub token_decimals: u8,
+    /// Tokens minted for each new curve (in base units)
+    pub total_supply: u64,
+    /// Part of `total_supply` sold on the curve, the rest is kept for the DEX pool
+    pub curve_supply: u64,
+    /// CP-Swap config `migrate` creates pools under, default until set
+    pub amm_config: Pubkey,
+    /// Keeps the account the size it was before the launch supply fields
+    pub padding: [u8; 7],
+    /// Reserved for future fields
+    pub reserved: [u64; 9],
 }
 
 impl GlobalConfig {
     pub const LEN: usize = 8 + // discriminator
         32 + // authority
+        32 + // pending_authority
+        32 + // fee_admin
+        32 + // pause_admin
         32 + // fee_recipient
         2 +  // fee_bps
+        2 +  // creator_fee_bps
+        2 +  // max_referral_bps
         8 +  // migration_threshold
+        8 +  // migration_fee
         8 +  // initial_virtual_sol
         8 +  // initial_virtual_tokens
         8 +  // total_tokens
         8 +  // total_volume
-        1;   // bump
+        1 +  // bump
+        4 + MAX_CURVE_KINDS * CurveKind::LEN + // allowed_curve_kinds
+        1 +  // paused
+        4 +  // config_version
+        1 +  // version
+        1 +  // token_decimals
+        8 +  // total_supply
+        8 +  // curve_supply
+        32 + // amm_config
+        7 +  // padding
+        8 * 9; // reserved
+
+    /// Decimals, total supply and curve supply of new mints. Configs from before these
+    /// fields read all zero and keep minting their virtual token reserves at 6 decimals
+    /// until `update_config` stores that.
+    pub fn launch_supply(&self) -> (u8, u64, u64) {
+        if self.token_decimals == 0 && self.total_supply == 0 && self.curve_supply == 0 {
+            return (6, self.initial_virtual_tokens, self.initial_virtual_tokens);
+        }
+        (self.token_decimals, self.total_supply, self.curve_supply)
+    }
+
+    /// Whether `key` may change fee settings: the authority or the fee admin
+    pub fn is_fee_admin(&self, key: &Pubkey) -> bool {
+        *key == self.authority || (self.fee_admin != Pubkey::default() && *key == self.fee_admin)
+    }
+
+    /// Whether `key` may pause the protocol: the authority or the pause admin
+    pub fn is_pause_admin(&self, key: &Pubkey) -> bool {
+        *key == self.authority
+            || (self.pause_admin != Pubkey::default() && *key == self.pause_admin)
+    }
+
+    /// Check none of the operations in `flags` are paused
+    pub fn check_not_paused(&self, flags: u8) -> Result<()> {
+        require!(self.paused & flags == 0, OpenClawError::Paused);
+        Ok(())
+    }
+
+    /// Check the configuration makes sense, after `initialize` or `update_config`
+    pub fn validate(&self) -> Result<()> {
+        require!(self.fee_bps <= MAX_FEE_BPS, OpenClawError::FeeTooHigh);
+        require!(
+            self.creator_fee_bps <= MAX_CREATOR_FEE_BPS,
+            OpenClawError::CreatorFeeTooHigh
+        );
+        require!(
+            self.max_referral_bps <= 10_000,
+            OpenClawError::ReferralFeeTooHigh
+        );
+        require!(
+            self.initial_virtual_sol > 0 && self.initial_virtual_tokens > 0,
+            OpenClawError::ZeroVirtualReserves
+        );
+        require!(
+            self.migration_fee < self.migration_threshold,
+            OpenClawError::MigrationFeeTooHigh
+        );
+
+        // The curve can't sell more than is minted, nor more than its virtual reserves.
+        // What it doesn't sell is locked away for the DEX pool.
+        let curve_supply = self.curve_supply;
+        require!(
+            TOKEN_DECIMALS.contains(&self.token_decimals),
+            OpenClawError::InvalidTokenDecimals
+        );
+        require!(
+            curve_supply > 0
+                && curve_supply <= self.total_supply
+                && curve_supply <= self.initial_virtual_tokens,
+            OpenClawError::InvalidTokenSupply
+        );
+
+        require!(
+            !self.allowed_curve_kinds.is_empty()
+                && self.allowed_curve_kinds.len() <= MAX_CURVE_KINDS,
+            OpenClawError::InvalidCurveKind
+        );
+        for kind in &self.allowed_curve_kinds {
+            kind.validate()?;
+            // A curve tops out once its curve supply is sold. Only a constant-product
+            // curve selling its whole virtual reserves can always raise more.
+            let raised = if kind.is_supply_based() {
+                kind.cost(curve_supply, curve_supply)
+                    .map_err(|_| error!(OpenClawError::InvalidCurveKind))?
+            } else if curve_supply < self.initial_virtual_tokens {
+                let raised = self.initial_virtual_sol as u128 * curve_supply as u128
+                    / (self.initial_virtual_tokens - curve_supply) as u128;
+                u64::try_from(raised).unwrap_or(u64::MAX)
+            } else {
+                continue;
+            };
+            require!(
+                raised >= self.migration_threshold,
+                OpenClawError::MigrationThresholdUnreachable
+            );
+        }
+        Ok(())
+    }
 }
 
 /// Bonding curve state for a token
@@ -57,10 +196,62 @@ pub struct BondingCurve {
     pub tokens_sold: u64,
     /// Whether the token has been migrated to DEX
     pub migrated: bool,
+    /// Whether the curve reached the migration threshold and stopped trading
+    pub complete: bool,
     /// Timestamp when the token was created
     pub created_at: i64,
     /// Bump seed
     pub bump: u8,
+    /// Bump seed of the curve's SOL vault
+    pub sol_vault_bump: u8,
+    /// Price dynamics of the curve
+    pub curve_kind: CurveKind,
+    /// Creator fee in basis points, fixed when the curve is created
+    pub creator_fee_bps: u16,
+    /// Creator fees held in the SOL vault and not yet claimed (in lamports)
+    pub creator_fees_accrued: u64,
+    /// Creator fees claimed so far (in lamports)
+    pub creator_fees_claimed: u64,
+    /// Whether the authority froze trading and migration on this curve
+    pub frozen: bool,
+    /// Global config version the curve was created under
+    pub config_version: u32,
+    /// Account layout version
+    pub version: u8,
+    /// Buy limits while the curve is new, all zero when off
+    pub launch_protection: LaunchProtection,
+    /// Tokens locked in the migration vault for the DEX pool, never sold on the curve
+    pub migration_tokens: u64,
+    /// Reserved for future fields
+    pub reserved: [u64; 4],
+}
+
+/// Buy limits for the first `window_secs` after a curve is created, to blunt snipers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LaunchProtection {
+    /// Seconds after `created_at` the limits apply for
+    pub window_secs: u64,
+    /// Most SOL (fee included, in lamports) a single buy may spend
+    pub max_sol_per_tx: u64,
+    /// Most SOL (fee included, in lamports) one wallet may spend over the window
+    pub max_sol_per_wallet: u64,
+}
+
+impl LaunchProtection {
+    pub const LEN: usize = 8 + // window_secs
+        8 +  // max_sol_per_tx
+        8;   // max_sol_per_wallet
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.window_secs > 0
+                && self.window_secs <= MAX_LAUNCH_WINDOW_SECS
+                && self.max_sol_per_tx > 0
+                && self.max_sol_per_wallet >= self.max_sol_per_tx,
+            OpenClawError::InvalidLaunchProtection
+        );
+        Ok(())
+    }
 }
 
 impl BondingCurve {
@@ -73,55 +264,599 @@ impl BondingCurve {
         8 +  // real_token_reserves
         8 +  // tokens_sold
         1 +  // migrated
+        1 +  // complete
         8 +  // created_at
-        1;   // bump
+        1 +  // bump
+        1 +  // sol_vault_bump
+        CurveKind::LEN + // curve_kind
+        2 +  // creator_fee_bps
+        8 +  // creator_fees_accrued
+        8 +  // creator_fees_claimed
+        1 +  // frozen
+        4 +  // config_version
+        1 +  // version
+        LaunchProtection::LEN + // launch_protection
+        8 +  // migration_tokens
+        8 * 4; // reserved
+
+    /// Check the SOL vault backs the real SOL reserves and unclaimed creator fees on top
+    /// of its rent-exempt minimum. Lamports sent to the vault by anyone else are never
+    /// counted as reserves, so the vault may hold more than that but never less.
+    pub fn check_sol_vault(&self, vault_lamports: u64) -> Result<()> {
+        let rent = Rent::get()?.minimum_balance(0);
+        let required = self
+            .real_sol_reserves
+            .checked_add(self.creator_fees_accrued)
+            .and_then(|v| v.checked_add(rent))
+            .ok_or(OpenClawError::MathOverflow)?;
+        require!(vault_lamports >= required, OpenClawError::SolVaultMismatch);
+        Ok(())
+    }
+
+    /// Check the curve hasn't been frozen
+    pub fn check_not_frozen(&self) -> Result<()> {
+        require!(!self.frozen, OpenClawError::Paused);
+        Ok(())
+    }
+
+    /// Whether the launch protection limits apply at `now`
+    pub fn in_launch_window(&self, now: i64) -> bool {
+        let window = self.launch_protection.window_secs as i64;
+        window > 0 && now < self.created_at.saturating_add(window)
+    }
+
+    /// Check the curve is still open for trading
+    pub fn check_tradable(&self) -> Result<()> {
+        require!(!self.migrated, OpenClawError::AlreadyMigrated);
+        require!(!self.complete, OpenClawError::CurveComplete);
+        Ok(())
+    }
+
+    /// Whether the curve should stop trading after its last buy: it has raised enough to
+    /// migrate, or has nothing left to sell, which can happen when the threshold is
+    /// raised after the curve was created
+    pub fn is_complete_after(&self, config: &GlobalConfig) -> bool {
+        self.real_sol_reserves >= config.migration_threshold || self.real_token_reserves == 0
+    }
 
-    /// Calculate the current token price
-    pub fn get_price(&self) -> u64 {
-        if self.virtual_token_reserves == 0 {
-            return 0;
+    /// Snapshot of the reserves for the curve math
+    pub fn curve_state(&self) -> CurveState {
+        CurveState {
+            curve: self.curve_kind.into(),
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            tokens_sold: self.tokens_sold,
         }
-        // Price = virtual_sol / virtual_tokens (in lamports per token)
-        (self.virtual_sol_reserves as u128 * 1_000_000 / self.virtual_token_reserves as u128) as u64
+    }
+
+    fn set_curve_state(&mut self, state: &CurveState) {
+        self.virtual_sol_reserves = state.virtual_sol_reserves;
+        self.virtual_token_reserves = state.virtual_token_reserves;
+        self.real_sol_reserves = state.real_sol_reserves;
+        self.real_token_reserves = state.real_token_reserves;
+        self.tokens_sold = state.tokens_sold;
+    }
+
+    /// Tokens the curve sells in total: those sold so far plus those still for sale
+    pub fn curve_supply(&self) -> Result<u64> {
+        Ok(self.curve_state().curve_supply().map_err(OpenClawError::from)?)
+    }
+
+    /// Check the curve kind can price its whole supply without overflowing
+    pub fn check_curve(&self) -> Result<()> {
+        Ok(self.curve_state().check().map_err(OpenClawError::from)?)
+    }
+
+    /// Calculate the current token price (in lamports per whole token of `decimals`
+    /// decimals)
+    pub fn get_price(&self, decimals: u8) -> Result<u64> {
+        Ok(self.curve_state().token_price(decimals).map_err(OpenClawError::from)?)
+    }
+
+    /// Marginal price in lamports per base unit, scaled by `CURVE_PRICE_SCALE`
+    pub fn spot_price(&self) -> Result<u128> {
+        Ok(self.curve_state().spot_price().map_err(OpenClawError::from)?)
+    }
+
+    /// Move `sol_in` (fees excluded) into and `tokens_out` out of the reserves
+    pub fn apply_buy(&mut self, sol_in: u64, tokens_out: u64) -> Result<()> {
+        let mut state = self.curve_state();
+        state
+            .apply_buy(sol_in, tokens_out)
+            .map_err(OpenClawError::from)?;
+        self.set_curve_state(&state);
+        Ok(())
+    }
+
+    /// Move `tokens_in` into and `sol_out` (fees included) out of the reserves
+    pub fn apply_sell(&mut self, tokens_in: u64, sol_out: u64) -> Result<()> {
+        let mut state = self.curve_state();
+        state
+            .apply_sell(tokens_in, sol_out)
+            .map_err(OpenClawError::from)?;
+        self.set_curve_state(&state);
+        Ok(())
+    }
+
+    /// Total fee charged on trades, platform and creator fee together
+    pub fn total_fee_bps(&self, fee_bps: u16) -> Result<u16> {
+        fee_bps
+            .checked_add(self.creator_fee_bps)
+            .ok_or(error!(OpenClawError::MathOverflow))
+    }
+
+    /// Split a fee charged at `total_fee_bps(fee_bps)` into the platform's and the
+    /// creator's share. Rounding leftovers go to the platform.
+    pub fn split_fee(&self, fee: u64, fee_bps: u16) -> Result<(u64, u64)> {
+        Ok(openclaw_math::split_fee(fee, fee_bps, self.creator_fee_bps)
+            .map_err(OpenClawError::from)?)
     }
 
     /// Calculate tokens out for a given SOL input
     pub fn get_tokens_out(&self, sol_in: u64, fee_bps: u16) -> Result<(u64, u64)> {
-        // Calculate fee
-        let fee = (sol_in as u128 * fee_bps as u128 / 10_000) as u64;
-        let sol_after_fee = sol_in.saturating_sub(fee);
-
-        // Constant product: k = x * y
-        // new_y = k / new_x
-        // tokens_out = old_y - new_y
-        let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
-        let new_sol = self.virtual_sol_reserves as u128 + sol_after_fee as u128;
-        let new_tokens = k / new_sol;
-        let tokens_out = self.virtual_token_reserves as u128 - new_tokens;
+        Ok(self
+            .curve_state()
+            .get_tokens_out(sol_in, fee_bps)
+            .map_err(OpenClawError::from)?)
+    }
 
-        Ok((tokens_out as u64, fee))
+    /// Like `get_tokens_out`, but only charges as much of `sol_in` as is needed to take
+    /// the real SOL reserves up to `threshold`. Returns the SOL charged, fee and tokens out.
+    pub fn get_capped_buy(
+        &self,
+        sol_in: u64,
+        fee_bps: u16,
+        threshold: u64,
+    ) -> Result<(u64, u64, u64)> {
+        Ok(self
+            .curve_state()
+            .get_capped_buy(sol_in, fee_bps, threshold)
+            .map_err(OpenClawError::from)?)
     }
 
     /// Calculate SOL out for a given token input
     pub fn get_sol_out(&self, tokens_in: u64, fee_bps: u16) -> Result<(u64, u64)> {
-        // Constant product: k = x * y
-        // new_x = k / new_y
-        // sol_out = old_x - new_x
-        let k = self.virtual_sol_reserves as u128 * self.virtual_token_reserves as u128;
-        let new_tokens = self.virtual_token_reserves as u128 + tokens_in as u128;
-        let new_sol = k / new_tokens;
-        let sol_out_gross = self.virtual_sol_reserves as u128 - new_sol;
+        Ok(self
+            .curve_state()
+            .get_sol_out(tokens_in, fee_bps)
+            .map_err(OpenClawError::from)?)
+    }
+
+    /// Calculate the SOL (fee included) needed to buy exactly `tokens_out`
+    pub fn get_sol_in(&self, tokens_out: u64, fee_bps: u16) -> Result<(u64, u64)> {
+        Ok(self
+            .curve_state()
+            .get_sol_in(tokens_out, fee_bps)
+            .map_err(OpenClawError::from)?)
+    }
+
+    /// Calculate the tokens needed to receive exactly `sol_out` after fees
+    pub fn get_tokens_in(&self, sol_out: u64, fee_bps: u16) -> Result<(u64, u64)> {
+        Ok(self
+            .curve_state()
+            .get_tokens_in(sol_out, fee_bps)
+            .map_err(OpenClawError::from)?)
+    }
+}
+
+/// A frontend or bot that routes trades to the protocol for a share of the platform fee
+#[account]
+#[derive(Default)]
+pub struct Referrer {
+    /// Wallet paid the referral share
+    pub wallet: Pubkey,
+    /// Requested share of the platform fee in basis points of the fee, capped by the
+    /// global config at trade time
+    pub referral_bps: u16,
+    /// SOL volume of referred trades (in lamports)
+    pub total_volume: u64,
+    /// Referral fees paid out (in lamports)
+    pub total_fees: u64,
+    /// Number of referred trades
+    pub trade_count: u64,
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl Referrer {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // wallet
+        2 +  // referral_bps
+        8 +  // total_volume
+        8 +  // total_fees
+        8 +  // trade_count
+        1;   // bump
+
+    /// Referral share of `platform_fee` under a global cap of `max_referral_bps`
+    pub fn referral_fee(&self, platform_fee: u64, max_referral_bps: u16) -> Result<u64> {
+        Ok(openclaw_math::fee_on(platform_fee, self.referral_bps.min(max_referral_bps))
+            .map_err(OpenClawError::from)?)
+    }
+
+    /// Check `wallet` is this referrer's and isn't the trader referring themselves
+    pub fn check_wallet(&self, wallet: &Pubkey, trader: &Pubkey) -> Result<()> {
+        require_keys_eq!(*wallet, self.wallet, OpenClawError::InvalidReferrer);
+        require_keys_neq!(*wallet, *trader, OpenClawError::InvalidReferrer);
+        Ok(())
+    }
+
+    /// Add a referred trade to the totals
+    pub fn record(&mut self, sol_amount: u64, referral_fee: u64) -> Result<()> {
+        self.total_volume = self
+            .total_volume
+            .checked_add(sol_amount)
+            .ok_or(OpenClawError::MathOverflow)?;
+        self.total_fees = self
+            .total_fees
+            .checked_add(referral_fee)
+            .ok_or(OpenClawError::MathOverflow)?;
+        self.trade_count = self
+            .trade_count
+            .checked_add(1)
+            .ok_or(OpenClawError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// A wallet's position on one curve
+#[account]
+#[derive(Default)]
+pub struct Position {
+    /// Token mint of the curve
+    pub mint: Pubkey,
+    /// Wallet the position belongs to
+    pub owner: Pubkey,
+    /// SOL spent on buys during the curve's launch window (in lamports), fees included
+    pub launch_sol_spent: u64,
+    /// Bump seed
+    pub bump: u8,
+    /// Tokens bought through the program
+    pub tokens_bought: u64,
+    /// Tokens sold through the program
+    pub tokens_sold: u64,
+    /// SOL paid for buys (in lamports), fees included
+    pub sol_spent: u64,
+    /// SOL received from sells (in lamports), fees excluded
+    pub sol_received: u64,
+    /// Platform and creator fees paid on both sides (in lamports)
+    pub fees_paid: u64,
+    /// Average-cost basis of the tokens still held (in lamports)
+    pub cost_basis: u64,
+    /// Timestamp of the first trade, 0 before any
+    pub first_trade_at: i64,
+    /// Timestamp of the latest trade
+    pub last_trade_at: i64,
+}
+
+impl Position {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // owner
+        8 +  // launch_sol_spent
+        1 +  // bump
+        8 +  // tokens_bought
+        8 +  // tokens_sold
+        8 +  // sol_spent
+        8 +  // sol_received
+        8 +  // fees_paid
+        8 +  // cost_basis
+        8 +  // first_trade_at
+        8;   // last_trade_at
+
+    /// Tokens bought and not yet sold through the program
+    pub fn tokens_held(&self) -> u64 {
+        self.tokens_bought.saturating_sub(self.tokens_sold)
+    }
+
+    /// SOL received from sells less the cost basis of the tokens sold (in lamports)
+    pub fn realized_pnl(&self) -> Result<i64> {
+        let cost_sold = self.sol_spent as i128 - self.cost_basis as i128;
+        Ok(i64::try_from(self.sol_received as i128 - cost_sold)
+            .map_err(|_| OpenClawError::MathOverflow)?)
+    }
 
-        // Calculate fee
-        let fee = (sol_out_gross * fee_bps as u128 / 10_000) as u64;
-        let sol_out = (sol_out_gross as u64).saturating_sub(fee);
+    /// Add a buy of `tokens` for `sol_amount`, `fee` included
+    pub fn record_buy(&mut self, sol_amount: u64, tokens: u64, fee: u64, now: i64) -> Result<()> {
+        self.tokens_bought = self
+            .tokens_bought
+            .checked_add(tokens)
+            .ok_or(OpenClawError::MathOverflow)?;
+        self.sol_spent = self
+            .sol_spent
+            .checked_add(sol_amount)
+            .ok_or(OpenClawError::MathOverflow)?;
+        self.cost_basis = self
+            .cost_basis
+            .checked_add(sol_amount)
+            .ok_or(OpenClawError::MathOverflow)?;
+        self.record_trade(fee, now)
+    }
+
+    /// Add a sell of `tokens` for `sol_amount`, `fee` excluded. The cost basis drops by
+    /// the average cost of the tokens sold.
+    pub fn record_sell(&mut self, tokens: u64, sol_amount: u64, fee: u64, now: i64) -> Result<()> {
+        let held = self.tokens_held();
+        let cost_sold = if tokens >= held {
+            self.cost_basis
+        } else {
+            (self.cost_basis as u128 * tokens as u128 / held as u128) as u64
+        };
+        self.cost_basis -= cost_sold;
+        self.tokens_sold = self
+            .tokens_sold
+            .checked_add(tokens)
+            .ok_or(OpenClawError::MathOverflow)?;
+        self.sol_received = self
+            .sol_received
+            .checked_add(sol_amount)
+            .ok_or(OpenClawError::MathOverflow)?;
+        self.record_trade(fee, now)
+    }
+
+    fn record_trade(&mut self, fee: u64, now: i64) -> Result<()> {
+        self.fees_paid = self
+            .fees_paid
+            .checked_add(fee)
+            .ok_or(OpenClawError::MathOverflow)?;
+        if self.first_trade_at == 0 {
+            self.first_trade_at = now;
+        }
+        self.last_trade_at = now;
+        Ok(())
+    }
+}
+
+/// Recent prices of a curve, for time-weighted averages that a single transaction can't
+/// move. Each observation holds the running sum of price times slots elapsed, like a
+/// Uniswap oracle.
+#[account]
+#[derive(Default)]
+pub struct PriceHistory {
+    /// Token mint of the curve
+    pub mint: Pubkey,
+    /// Bump seed
+    pub bump: u8,
+    /// Index of the latest observation
+    pub head: u16,
+    /// Observations written so far, up to `OBSERVATIONS`
+    pub count: u16,
+    /// Ring buffer of observations, oldest overwritten first
+    pub observations: [Observation; OBSERVATIONS],
+}
+
+/// Cumulative price of a curve at a slot
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Observation {
+    /// Slot of the observation
+    pub slot: u64,
+    /// Sum of spot price (scaled by `CURVE_PRICE_SCALE`) times slots up to `slot`,
+    /// wrapping on overflow
+    pub cumulative_price: u128,
+}
+
+impl PriceHistory {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        1 +  // bump
+        2 +  // head
+        2 +  // count
+        OBSERVATIONS * (8 + 16); // observations
+
+    /// Fill in a history `init_if_needed` just created. Does nothing once it is set up.
+    pub fn start(&mut self, mint: Pubkey, bump: u8) {
+        if self.mint == Pubkey::default() {
+            self.mint = mint;
+            self.bump = bump;
+        }
+    }
+
+    /// Latest observation, if any
+    pub fn latest(&self) -> Option<Observation> {
+        (self.count > 0).then(|| self.observations[self.head as usize])
+    }
+
+    /// Record that the curve traded at `price` since the latest observation, up to `slot`.
+    /// Called before each trade, so only the first trade in a slot writes an observation
+    /// and prices within a slot carry no weight.
+    pub fn record(&mut self, slot: u64, price: u128) {
+        let observation = match self.latest() {
+            Some(latest) if slot <= latest.slot => return,
+            Some(latest) => Observation {
+                slot,
+                cumulative_price: latest
+                    .cumulative_price
+                    .wrapping_add(price.wrapping_mul((slot - latest.slot) as u128)),
+            },
+            None => Observation {
+                slot,
+                cumulative_price: 0,
+            },
+        };
+        if self.count > 0 {
+            self.head = (self.head + 1) % OBSERVATIONS as u16;
+        }
+        self.count = self.count.saturating_add(1).min(OBSERVATIONS as u16);
+        self.observations[self.head as usize] = observation;
+    }
+
+    /// Time-weighted average price of `bonding_curve` over the `window_slots` before
+    /// `slot`, scaled by `CURVE_PRICE_SCALE`
+    pub fn twap(&self, bonding_curve: &BondingCurve, slot: u64, window_slots: u64) -> Result<u128> {
+        require!(window_slots > 0, OpenClawError::InsufficientPriceHistory);
+        let latest = self.latest().ok_or(OpenClawError::InsufficientPriceHistory)?;
+        require!(slot >= latest.slot, OpenClawError::InsufficientPriceHistory);
+        let target = slot
+            .checked_sub(window_slots)
+            .ok_or(OpenClawError::InsufficientPriceHistory)?;
+
+        let price = bonding_curve.spot_price()?;
+        let cumulative_now = latest
+            .cumulative_price
+            .wrapping_add(price.wrapping_mul((slot - latest.slot) as u128));
+
+        // Walk back from the latest observation to the last one at or before the target,
+        // then interpolate towards the observation after it
+        let mut next: Option<Observation> = None;
+        for i in 0..self.count as usize {
+            let index = (self.head as usize + OBSERVATIONS - i) % OBSERVATIONS;
+            let observation = self.observations[index];
+            if observation.slot > target {
+                next = Some(observation);
+                continue;
+            }
+            let cumulative_target = match next {
+                None => observation
+                    .cumulative_price
+                    .wrapping_add(price.wrapping_mul((target - observation.slot) as u128)),
+                Some(next) => {
+                    let delta = next.cumulative_price.wrapping_sub(observation.cumulative_price);
+                    let elapsed = (target - observation.slot) as u128;
+                    let span = (next.slot - observation.slot) as u128;
+                    observation
+                        .cumulative_price
+                        .wrapping_add(delta / span * elapsed + delta % span * elapsed / span)
+                }
+            };
+            return Ok(cumulative_now.wrapping_sub(cumulative_target) / window_slots as u128);
+        }
+        err!(OpenClawError::InsufficientPriceHistory)
+    }
+}
+
+/// Layout version of new `GlobalConfig` and `BondingCurve` accounts. Version 1 accounts
+/// predate the `version` field and are upgraded with `upgrade_account`.
+pub const ACCOUNT_VERSION: u8 = 2;
+
+/// `GlobalConfig` as version 1 laid it out, after the discriminator
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GlobalConfigV1 {
+    pub authority: Pubkey,
+    pub fee_recipient: Pubkey,
+    pub fee_bps: u16,
+    pub migration_threshold: u64,
+    pub initial_virtual_sol: u64,
+    pub initial_virtual_tokens: u64,
+    pub total_tokens: u64,
+    pub total_volume: u64,
+    pub bump: u8,
+}
+
+impl GlobalConfigV1 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        32 + // fee_recipient
+        2 +  // fee_bps
+        8 +  // migration_threshold
+        8 +  // initial_virtual_sol
+        8 +  // initial_virtual_tokens
+        8 +  // total_tokens
+        8 +  // total_volume
+        1;   // bump
+
+    /// Carry the fields over to the current layout. Roles, creator and referral fees
+    /// start out unset, and new curves stay constant-product on the legacy supply.
+    pub fn upgrade(self) -> GlobalConfig {
+        GlobalConfig {
+            authority: self.authority,
+            fee_recipient: self.fee_recipient,
+            fee_bps: self.fee_bps,
+            migration_threshold: self.migration_threshold,
+            initial_virtual_sol: self.initial_virtual_sol,
+            initial_virtual_tokens: self.initial_virtual_tokens,
+            total_tokens: self.total_tokens,
+            total_volume: self.total_volume,
+            bump: self.bump,
+            allowed_curve_kinds: vec![CurveKind::ConstantProduct],
+            version: ACCOUNT_VERSION,
+            ..GlobalConfig::default()
+        }
+    }
+}
+
+/// `BondingCurve` as version 1 laid it out, after the discriminator. Its real SOL
+/// reserves sat in the curve account itself rather than in a SOL vault.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct BondingCurveV1 {
+    pub mint: Pubkey,
+    pub creator: Pubkey,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub tokens_sold: u64,
+    pub migrated: bool,
+    pub created_at: i64,
+    pub bump: u8,
+}
 
-        Ok((sol_out, fee))
+impl BondingCurveV1 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // mint
+        32 + // creator
+        8 +  // virtual_sol_reserves
+        8 +  // virtual_token_reserves
+        8 +  // real_sol_reserves
+        8 +  // real_token_reserves
+        8 +  // tokens_sold
+        1 +  // migrated
+        8 +  // created_at
+        1;   // bump
+
+    /// Carry the fields over to the current layout as a constant-product curve without
+    /// creator fees, launch protection or a migration allocation
+    pub fn upgrade(self, sol_vault_bump: u8) -> BondingCurve {
+        BondingCurve {
+            mint: self.mint,
+            creator: self.creator,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            tokens_sold: self.tokens_sold,
+            migrated: self.migrated,
+            created_at: self.created_at,
+            bump: self.bump,
+            sol_vault_bump,
+            curve_kind: CurveKind::ConstantProduct,
+            version: ACCOUNT_VERSION,
+            ..BondingCurve::default()
+        }
     }
 }
 
+/// Decimals the admin may give new mints
+pub const TOKEN_DECIMALS: [u8; 2] = [6, 9];
+
+/// Observations kept per curve in its `PriceHistory`
+pub const OBSERVATIONS: usize = 32;
+
+/// Longest launch protection window a creator may set (1 hour)
+pub const MAX_LAUNCH_WINDOW_SECS: u64 = 3_600;
+
+/// Most curve kinds the global config can allow at once
+pub const MAX_CURVE_KINDS: usize = 4;
+
+/// Highest platform fee the admin may set (10%)
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Highest creator fee the admin may set (5%)
+pub const MAX_CREATOR_FEE_BPS: u16 = 500;
+
+/// `GlobalConfig::paused` flags
+pub const PAUSE_CREATION: u8 = 1 << 0;
+pub const PAUSE_TRADING: u8 = 1 << 1;
+pub const PAUSE_MIGRATION: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_CREATION | PAUSE_TRADING | PAUSE_MIGRATION;
+
 /// Seeds for PDAs
 pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
 pub const BONDING_CURVE_SEED: &[u8] = b"bonding_curve";
 pub const CURVE_SOL_VAULT_SEED: &[u8] = b"curve_sol_vault";
 pub const CURVE_TOKEN_VAULT_SEED: &[u8] = b"curve_token_vault";
+pub const MIGRATION_VAULT_SEED: &[u8] = b"migration_vault";
+pub const METADATA_SEED: &[u8] = b"metadata";
+pub const REFERRER_SEED: &[u8] = b"referrer";
+pub const POSITION_SEED: &[u8] = b"position";
+pub const PRICE_HISTORY_SEED: &[u8] = b"price_history";
@@ -0,0 +1,178 @@
+mod common;
+
+use common::*;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::UpdateConfigParams;
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn authority_transfers_in_two_steps() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let successor = h.new_user(LAMPORTS_PER_SOL).await;
+
+    h.propose_authority(&authority, &successor.pubkey())
+        .await
+        .unwrap();
+    let config = h.global_config().await;
+    assert_eq!(config.authority, authority.pubkey());
+    assert_eq!(config.pending_authority, successor.pubkey());
+
+    // Nothing changes hands until the successor accepts
+    let intruder = h.new_user(LAMPORTS_PER_SOL).await;
+    let result = h.accept_authority(&intruder).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+
+    h.accept_authority(&successor).await.unwrap();
+    let config = h.global_config().await;
+    assert_eq!(config.authority, successor.pubkey());
+    assert_eq!(config.pending_authority, Pubkey::default());
+
+    // The old authority is out, the new one is in
+    let params = UpdateConfigParams {
+        fee_bps: Some(150),
+        ..no_config_changes()
+    };
+    let result = h.update_config(&authority, params).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+    let params = UpdateConfigParams {
+        fee_bps: Some(150),
+        ..no_config_changes()
+    };
+    h.update_config(&successor, params).await.unwrap();
+    assert_eq!(h.global_config().await.fee_bps, 150);
+}
+
+#[tokio::test]
+async fn unaccepted_proposal_can_be_cancelled() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let successor = h.new_user(LAMPORTS_PER_SOL).await;
+
+    h.propose_authority(&authority, &successor.pubkey())
+        .await
+        .unwrap();
+    h.cancel_authority_proposal(&authority).await.unwrap();
+    assert_eq!(h.global_config().await.pending_authority, Pubkey::default());
+
+    let result = h.accept_authority(&successor).await;
+    assert_openclaw_error(result, OpenClawError::NoPendingAuthority);
+    assert_eq!(h.global_config().await.authority, authority.pubkey());
+
+    h.refresh_blockhash().await;
+    let result = h.cancel_authority_proposal(&authority).await;
+    assert_openclaw_error(result, OpenClawError::NoPendingAuthority);
+}
+
+#[tokio::test]
+async fn only_authority_can_propose_or_cancel() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let intruder = h.new_user(LAMPORTS_PER_SOL).await;
+
+    let result = h.propose_authority(&intruder, &intruder.pubkey()).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+
+    let result = h.propose_authority(&authority, &Pubkey::default()).await;
+    assert_openclaw_error(result, OpenClawError::InvalidAuthority);
+
+    h.propose_authority(&authority, &Pubkey::new_unique())
+        .await
+        .unwrap();
+    let result = h.cancel_authority_proposal(&intruder).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+}
+
+#[tokio::test]
+async fn fee_admin_can_only_change_fees() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let fee_admin = h.new_user(LAMPORTS_PER_SOL).await;
+
+    // Not an admin until the authority names it
+    let params = UpdateConfigParams {
+        fee_bps: Some(50),
+        ..no_config_changes()
+    };
+    let result = h.update_config(&fee_admin, params).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            fee_admin: Some(fee_admin.pubkey()),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    h.update_config(
+        &fee_admin,
+        UpdateConfigParams {
+            fee_bps: Some(50),
+            creator_fee_bps: Some(25),
+            max_referral_bps: Some(1_000),
+            migration_fee: Some(LAMPORTS_PER_SOL / 5),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+    let config = h.global_config().await;
+    assert_eq!(config.fee_bps, 50);
+    assert_eq!(config.creator_fee_bps, 25);
+    assert_eq!(config.max_referral_bps, 1_000);
+    assert_eq!(config.migration_fee, LAMPORTS_PER_SOL / 5);
+
+    // Where the fees go stays with the authority
+    for params in [
+        UpdateConfigParams {
+            fee_recipient: Some(fee_admin.pubkey()),
+            ..no_config_changes()
+        },
+        UpdateConfigParams {
+            fee_bps: Some(60),
+            fee_recipient: Some(fee_admin.pubkey()),
+            ..no_config_changes()
+        },
+        UpdateConfigParams {
+            amm_config: Some(Pubkey::new_unique()),
+            ..no_config_changes()
+        },
+        UpdateConfigParams {
+            migration_threshold: Some(LAMPORTS_PER_SOL),
+            ..no_config_changes()
+        },
+        UpdateConfigParams {
+            fee_admin: Some(Pubkey::new_unique()),
+            ..no_config_changes()
+        },
+        UpdateConfigParams {
+            pause_admin: Some(fee_admin.pubkey()),
+            ..no_config_changes()
+        },
+    ] {
+        let result = h.update_config(&fee_admin, params).await;
+        assert_openclaw_error(result, OpenClawError::Unauthorized);
+    }
+
+    // Clearing the role takes the fee admin's rights away again
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            fee_admin: Some(Pubkey::default()),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+    let params = UpdateConfigParams {
+        fee_bps: Some(75),
+        ..no_config_changes()
+    };
+    let result = h.update_config(&fee_admin, params).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+}
@@ -0,0 +1,1010 @@
+#![allow(dead_code)]
+
+pub mod stubs;
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{
+    get_associated_token_address, get_associated_token_address_with_program_id,
+};
+use anchor_spl::metadata::mpl_token_metadata::instructions::CreateMetadataAccountV3InstructionArgs;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use openclaw::curve::CurveKind;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::{
+    CreateTokenParams, InitializeParams, TradeQuote, TradeSide, UpdateConfigParams,
+};
+use openclaw::state::{
+    BondingCurve, GlobalConfig, Position, PriceHistory, Referrer, BONDING_CURVE_SEED,
+    CURVE_SOL_VAULT_SEED, GLOBAL_CONFIG_SEED, METADATA_SEED, MIGRATION_VAULT_SEED, POSITION_SEED,
+    PRICE_HISTORY_SEED, REFERRER_SEED,
+};
+use solana_program_test::{
+    processor, BanksClientError, ProgramTest, ProgramTestBanksClientExt, ProgramTestContext,
+};
+use solana_sdk::account::Account;
+use solana_sdk::clock::Clock;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::program_pack::Pack;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use spl_token_metadata_interface::state::TokenMetadata;
+
+pub const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+
+/// Starts at the default constant-product price and ends 10x higher
+pub const LINEAR: CurveKind = CurveKind::Linear {
+    start_price: 27_958,
+    end_price: 279_580,
+};
+
+/// Starts at the default constant-product price and doubles 8 times
+pub const EXPONENTIAL: CurveKind = CurveKind::Exponential {
+    start_price: 27_958,
+    doublings: 8,
+};
+
+pub fn default_initialize_params() -> InitializeParams {
+    InitializeParams {
+        fee_bps: 100,
+        creator_fee_bps: 0,
+        max_referral_bps: 2_000,
+        migration_threshold: 10 * LAMPORTS_PER_SOL,
+        migration_fee: LAMPORTS_PER_SOL / 10,
+        initial_virtual_sol: 30 * LAMPORTS_PER_SOL,
+        initial_virtual_tokens: 1_073_000_000_000_000,
+        token_decimals: 6,
+        total_supply: 1_073_000_000_000_000,
+        curve_supply: 1_073_000_000_000_000,
+        amm_config: stubs::AMM_CONFIG,
+        allowed_curve_kinds: vec![CurveKind::ConstantProduct, LINEAR, EXPONENTIAL],
+    }
+}
+
+pub fn no_config_changes() -> UpdateConfigParams {
+    UpdateConfigParams {
+        fee_bps: None,
+        creator_fee_bps: None,
+        max_referral_bps: None,
+        migration_threshold: None,
+        migration_fee: None,
+        initial_virtual_sol: None,
+        initial_virtual_tokens: None,
+        token_decimals: None,
+        total_supply: None,
+        curve_supply: None,
+        fee_recipient: None,
+        amm_config: None,
+        allowed_curve_kinds: None,
+        fee_admin: None,
+        pause_admin: None,
+    }
+}
+
+pub fn token_params(name: &str) -> CreateTokenParams {
+    CreateTokenParams {
+        name: name.to_string(),
+        symbol: "CLAW".to_string(),
+        uri: "https://claw.fun/token.json".to_string(),
+        curve_kind: CurveKind::ConstantProduct,
+        launch_protection: None,
+        initial_buy_sol: None,
+        min_tokens_out: 0,
+    }
+}
+
+fn process_openclaw(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    // Anchor's entrypoint ties the account slice lifetime to the account infos
+    let accounts: &[AccountInfo] = unsafe { std::mem::transmute(accounts) };
+    openclaw::entry(program_id, accounts, data)
+}
+
+pub struct Harness {
+    pub ctx: ProgramTestContext,
+    pub authority: Keypair,
+    pub fee_recipient: Pubkey,
+}
+
+impl Harness {
+    /// Start a fresh validator with openclaw, a stub metadata program and a stand-in AMM
+    pub async fn start() -> Self {
+        let mut program_test =
+            ProgramTest::new("openclaw", openclaw::ID, processor!(process_openclaw));
+        program_test.add_program(
+            "mpl_token_metadata",
+            anchor_spl::metadata::ID,
+            processor!(stubs::process_metadata),
+        );
+        program_test.add_program("cpmm", openclaw::amm::ID, processor!(stubs::process_cpmm));
+
+        let fee_recipient = Pubkey::new_unique();
+        program_test.add_account(
+            fee_recipient,
+            Account {
+                lamports: LAMPORTS_PER_SOL,
+                owner: solana_sdk::system_program::ID,
+                ..Account::default()
+            },
+        );
+
+        let ctx = program_test.start_with_context().await;
+        let authority = Keypair::new();
+        let mut harness = Self {
+            ctx,
+            authority,
+            fee_recipient,
+        };
+        let authority = harness.authority.pubkey();
+        harness.fund(&authority, 10 * LAMPORTS_PER_SOL).await;
+        harness
+    }
+
+    /// Start and initialize the global config
+    pub async fn initialized(params: InitializeParams) -> Self {
+        let mut harness = Self::start().await;
+        harness.initialize(params).await.unwrap();
+        harness
+    }
+
+    pub fn payer(&self) -> Keypair {
+        self.ctx.payer.insecure_clone()
+    }
+
+    pub async fn send(
+        &mut self,
+        ixs: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<(), BanksClientError> {
+        let payer = self.payer();
+        let mut all_signers = vec![&payer];
+        all_signers.extend_from_slice(signers);
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await.unwrap();
+        let tx =
+            Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all_signers, blockhash);
+        self.ctx.banks_client.process_transaction(tx).await
+    }
+
+    /// Move to a fresh blockhash, so repeating a transaction isn't rejected as a duplicate
+    pub async fn refresh_blockhash(&mut self) {
+        let banks_client = &mut self.ctx.banks_client;
+        let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+        banks_client
+            .get_new_latest_blockhash(&blockhash)
+            .await
+            .unwrap();
+    }
+
+    /// Move the clock forward by `secs`
+    pub async fn advance_clock(&mut self, secs: i64) {
+        let mut clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.unix_timestamp += secs;
+        self.ctx.set_sysvar(&clock);
+    }
+
+    pub async fn slot(&mut self) -> u64 {
+        let clock: Clock = self.ctx.banks_client.get_sysvar().await.unwrap();
+        clock.slot
+    }
+
+    /// Move forward by `slots`
+    pub async fn advance_slots(&mut self, slots: u64) {
+        let slot = self.slot().await;
+        self.ctx.warp_to_slot(slot + slots).unwrap();
+    }
+
+    pub async fn fund(&mut self, to: &Pubkey, lamports: u64) {
+        let payer = self.payer();
+        let ix = system_instruction::transfer(&payer.pubkey(), to, lamports);
+        self.send(&[ix], &[]).await.unwrap();
+    }
+
+    /// Overwrite an account directly, e.g. to stand in for one written by an older program
+    pub fn set_account(&mut self, address: &Pubkey, account: Account) {
+        self.ctx.set_account(address, &account.into());
+    }
+
+    pub async fn new_user(&mut self, lamports: u64) -> Keypair {
+        let user = Keypair::new();
+        self.fund(&user.pubkey(), lamports).await;
+        user
+    }
+
+    // === Accounts ===
+
+    pub async fn account(&mut self, address: &Pubkey) -> Option<Account> {
+        self.ctx.banks_client.get_account(*address).await.unwrap()
+    }
+
+    pub async fn lamports(&mut self, address: &Pubkey) -> u64 {
+        self.account(address).await.map(|a| a.lamports).unwrap_or(0)
+    }
+
+    pub async fn global_config(&mut self) -> GlobalConfig {
+        let account = self.account(&global_config_pda()).await.unwrap();
+        GlobalConfig::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn bonding_curve(&mut self, mint: &Pubkey) -> BondingCurve {
+        let account = self.account(&bonding_curve_pda(mint)).await.unwrap();
+        BondingCurve::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn position(&mut self, mint: &Pubkey, owner: &Pubkey) -> Position {
+        let account = self.account(&position_pda(mint, owner)).await.unwrap();
+        Position::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn price_history(&mut self, mint: &Pubkey) -> PriceHistory {
+        let account = self.account(&price_history_pda(mint)).await.unwrap();
+        PriceHistory::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    pub async fn referrer(&mut self, wallet: &Pubkey) -> Referrer {
+        let account = self.account(&referrer_pda(wallet)).await.unwrap();
+        Referrer::try_deserialize(&mut &account.data[..]).unwrap()
+    }
+
+    /// Token account under either token program
+    pub async fn token_account(&mut self, address: &Pubkey) -> spl_token_2022::state::Account {
+        let account = self.account(address).await.unwrap();
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+            .unwrap()
+            .base
+    }
+
+    pub async fn token_balance(&mut self, address: &Pubkey) -> u64 {
+        match self.account(address).await {
+            Some(_) => self.token_account(address).await.amount,
+            None => 0,
+        }
+    }
+
+    /// Metadata recorded by the stub metadata program for `mint`
+    pub async fn metadata_args(&mut self, mint: &Pubkey) -> CreateMetadataAccountV3InstructionArgs {
+        let account = self.account(&metadata_pda(mint)).await.unwrap();
+        assert_eq!(
+            account.data[0], 33,
+            "not a CreateMetadataAccountV3 instruction"
+        );
+        CreateMetadataAccountV3InstructionArgs::try_from_slice(&account.data[1..]).unwrap()
+    }
+
+    /// Mint under either token program
+    pub async fn mint_account(&mut self, address: &Pubkey) -> spl_token_2022::state::Mint {
+        let account = self.account(address).await.unwrap();
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data)
+            .unwrap()
+            .base
+    }
+
+    /// Metadata stored in a Token-2022 mint's token metadata extension
+    pub async fn token_metadata(&mut self, mint: &Pubkey) -> TokenMetadata {
+        let account = self.account(mint).await.unwrap();
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data)
+            .unwrap()
+            .get_variable_len_extension::<TokenMetadata>()
+            .unwrap()
+    }
+
+    /// The token program owning `mint`
+    pub async fn token_program_of(&mut self, mint: &Pubkey) -> Pubkey {
+        self.account(mint).await.unwrap().owner
+    }
+
+    // === Instructions ===
+
+    pub async fn initialize(&mut self, params: InitializeParams) -> Result<(), BanksClientError> {
+        let authority = self.authority.insecure_clone();
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::Initialize {
+                authority: authority.pubkey(),
+                global_config: global_config_pda(),
+                fee_recipient: self.fee_recipient,
+                system_program: solana_sdk::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::Initialize { params }.data(),
+        };
+        self.send(&[ix], &[&authority]).await
+    }
+
+    pub async fn update_config(
+        &mut self,
+        signer: &Keypair,
+        params: UpdateConfigParams,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::UpdateConfig {
+                authority: signer.pubkey(),
+                global_config: global_config_pda(),
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::UpdateConfig { params }.data(),
+        };
+        self.send(&[ix], &[signer]).await
+    }
+
+    pub async fn create_token_with(
+        &mut self,
+        creator: &Keypair,
+        params: CreateTokenParams,
+    ) -> Result<Pubkey, BanksClientError> {
+        self.create_token_under(creator, params, spl_token::ID)
+            .await
+    }
+
+    /// Create a token with its mint owned by `token_program`, SPL Token or Token-2022
+    pub async fn create_token_under(
+        &mut self,
+        creator: &Keypair,
+        params: CreateTokenParams,
+        token_program: Pubkey,
+    ) -> Result<Pubkey, BanksClientError> {
+        let mint = Keypair::new();
+        let bonding_curve = bonding_curve_pda(&mint.pubkey());
+        let initial_buy = params.initial_buy_sol.is_some();
+        // Token-2022 mints carry their own metadata
+        let metaplex = token_program == spl_token::ID;
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::CreateToken {
+                creator: creator.pubkey(),
+                global_config: global_config_pda(),
+                mint: mint.pubkey(),
+                bonding_curve,
+                sol_vault: sol_vault_pda(&mint.pubkey()),
+                curve_token_vault: token_account_under(
+                    &bonding_curve,
+                    &mint.pubkey(),
+                    &token_program,
+                ),
+                migration_vault: migration_vault_pda(&mint.pubkey()),
+                metadata: metaplex.then(|| metadata_pda(&mint.pubkey())),
+                token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_metadata_program: metaplex.then_some(anchor_spl::metadata::ID),
+                system_program: solana_sdk::system_program::ID,
+                rent: solana_sdk::sysvar::rent::ID,
+                price_history: price_history_pda(&mint.pubkey()),
+                fee_recipient: initial_buy.then_some(self.fee_recipient),
+                creator_token_account: initial_buy.then(|| {
+                    token_account_under(&creator.pubkey(), &mint.pubkey(), &token_program)
+                }),
+                creator_position: initial_buy
+                    .then(|| position_pda(&mint.pubkey(), &creator.pubkey())),
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::CreateToken { params }.data(),
+        };
+        self.send(&[ix], &[creator, &mint]).await?;
+        Ok(mint.pubkey())
+    }
+
+    pub async fn create_token(&mut self, creator: &Keypair) -> Pubkey {
+        self.create_token_with(creator, token_params("Claw Token"))
+            .await
+            .unwrap()
+    }
+
+    async fn buy_accounts(
+        &mut self,
+        buyer: &Keypair,
+        mint: &Pubkey,
+        referrer: Option<&Pubkey>,
+        position: Option<Pubkey>,
+    ) -> Vec<AccountMeta> {
+        let bonding_curve = bonding_curve_pda(mint);
+        let token_program = self.token_program_of(mint).await;
+        openclaw::accounts::Buy {
+            buyer: buyer.pubkey(),
+            global_config: global_config_pda(),
+            bonding_curve,
+            mint: *mint,
+            sol_vault: sol_vault_pda(mint),
+            curve_token_vault: token_account_under(&bonding_curve, mint, &token_program),
+            buyer_token_account: token_account_under(&buyer.pubkey(), mint, &token_program),
+            fee_recipient: self.fee_recipient,
+            token_program,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: solana_sdk::system_program::ID,
+            referrer: referrer.map(referrer_pda),
+            referrer_wallet: referrer.copied(),
+            position,
+            price_history: price_history_pda(mint),
+        }
+        .to_account_metas(None)
+    }
+
+    /// The position PDA of `owner` on `mint`, if it has been opened
+    async fn open_position_of(&mut self, owner: &Pubkey, mint: &Pubkey) -> Option<Pubkey> {
+        let position = position_pda(mint, owner);
+        self.account(&position).await.map(|_| position)
+    }
+
+    async fn sell_accounts(
+        &mut self,
+        seller: &Keypair,
+        mint: &Pubkey,
+        referrer: Option<&Pubkey>,
+        position: Option<Pubkey>,
+    ) -> Vec<AccountMeta> {
+        let bonding_curve = bonding_curve_pda(mint);
+        let token_program = self.token_program_of(mint).await;
+        openclaw::accounts::Sell {
+            seller: seller.pubkey(),
+            global_config: global_config_pda(),
+            bonding_curve,
+            mint: *mint,
+            sol_vault: sol_vault_pda(mint),
+            curve_token_vault: token_account_under(&bonding_curve, mint, &token_program),
+            seller_token_account: token_account_under(&seller.pubkey(), mint, &token_program),
+            fee_recipient: self.fee_recipient,
+            token_program,
+            system_program: solana_sdk::system_program::ID,
+            referrer: referrer.map(referrer_pda),
+            referrer_wallet: referrer.copied(),
+            position,
+            price_history: price_history_pda(mint),
+        }
+        .to_account_metas(None)
+    }
+
+    pub async fn buy(
+        &mut self,
+        buyer: &Keypair,
+        mint: &Pubkey,
+        sol_amount: u64,
+        min_tokens_out: u64,
+    ) -> Result<(), BanksClientError> {
+        let position = self.open_position_of(&buyer.pubkey(), mint).await;
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: self.buy_accounts(buyer, mint, None, position).await,
+            data: openclaw::instruction::Buy {
+                sol_amount,
+                min_tokens_out,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[buyer]).await
+    }
+
+    pub async fn buy_exact_tokens(
+        &mut self,
+        buyer: &Keypair,
+        mint: &Pubkey,
+        tokens_out: u64,
+        max_sol_in: u64,
+    ) -> Result<(), BanksClientError> {
+        let position = self.open_position_of(&buyer.pubkey(), mint).await;
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: self.buy_accounts(buyer, mint, None, position).await,
+            data: openclaw::instruction::BuyExactTokens {
+                tokens_out,
+                max_sol_in,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[buyer]).await
+    }
+
+    pub async fn sell(
+        &mut self,
+        seller: &Keypair,
+        mint: &Pubkey,
+        token_amount: u64,
+        min_sol_out: u64,
+    ) -> Result<(), BanksClientError> {
+        let position = self.open_position_of(&seller.pubkey(), mint).await;
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: self.sell_accounts(seller, mint, None, position).await,
+            data: openclaw::instruction::Sell {
+                token_amount,
+                min_sol_out,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[seller]).await
+    }
+
+    pub async fn sell_for_exact_sol(
+        &mut self,
+        seller: &Keypair,
+        mint: &Pubkey,
+        sol_out: u64,
+        max_tokens_in: u64,
+    ) -> Result<(), BanksClientError> {
+        let position = self.open_position_of(&seller.pubkey(), mint).await;
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: self.sell_accounts(seller, mint, None, position).await,
+            data: openclaw::instruction::SellForExactSol {
+                sol_out,
+                max_tokens_in,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[seller]).await
+    }
+
+    /// Simulate `quote` and decode its return data, without touching the bank
+    pub async fn quote(
+        &mut self,
+        mint: &Pubkey,
+        side: TradeSide,
+        amount: u64,
+    ) -> Result<TradeQuote, BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::Quote {
+                global_config: global_config_pda(),
+                bonding_curve: bonding_curve_pda(mint),
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::Quote { side, amount }.data(),
+        };
+        let payer = self.payer();
+        let blockhash = self.ctx.banks_client.get_latest_blockhash().await.unwrap();
+        let tx =
+            Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer], blockhash);
+        let simulation = self.ctx.banks_client.simulate_transaction(tx).await?;
+        if let Some(Err(err)) = simulation.result {
+            return Err(BanksClientError::TransactionError(err));
+        }
+        let return_data = simulation
+            .simulation_details
+            .and_then(|details| details.return_data)
+            .expect("quote sets return data");
+        assert_eq!(return_data.program_id, openclaw::ID);
+        Ok(TradeQuote::try_from_slice(&return_data.data).unwrap())
+    }
+
+    pub async fn buy_with_referrer(
+        &mut self,
+        buyer: &Keypair,
+        mint: &Pubkey,
+        sol_amount: u64,
+        referrer: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let position = self.open_position_of(&buyer.pubkey(), mint).await;
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: self
+                .buy_accounts(buyer, mint, Some(referrer), position)
+                .await,
+            data: openclaw::instruction::Buy {
+                sol_amount,
+                min_tokens_out: 0,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[buyer]).await
+    }
+
+    pub async fn sell_with_referrer(
+        &mut self,
+        seller: &Keypair,
+        mint: &Pubkey,
+        token_amount: u64,
+        referrer: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let position = self.open_position_of(&seller.pubkey(), mint).await;
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: self
+                .sell_accounts(seller, mint, Some(referrer), position)
+                .await,
+            data: openclaw::instruction::Sell {
+                token_amount,
+                min_sol_out: 0,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[seller]).await
+    }
+
+    pub async fn open_position(
+        &mut self,
+        owner: &Keypair,
+        mint: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::OpenPosition {
+                owner: owner.pubkey(),
+                bonding_curve: bonding_curve_pda(mint),
+                position: position_pda(mint, &owner.pubkey()),
+                system_program: solana_sdk::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::OpenPosition {}.data(),
+        };
+        self.send(&[ix], &[owner]).await
+    }
+
+    pub async fn close_position(
+        &mut self,
+        owner: &Keypair,
+        mint: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::ClosePosition {
+                owner: owner.pubkey(),
+                bonding_curve: bonding_curve_pda(mint),
+                position: position_pda(mint, &owner.pubkey()),
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::ClosePosition {}.data(),
+        };
+        self.send(&[ix], &[owner]).await
+    }
+
+    pub async fn register_referrer(
+        &mut self,
+        wallet: &Keypair,
+        referral_bps: u16,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::RegisterReferrer {
+                wallet: wallet.pubkey(),
+                global_config: global_config_pda(),
+                referrer: referrer_pda(&wallet.pubkey()),
+                system_program: solana_sdk::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::RegisterReferrer { referral_bps }.data(),
+        };
+        self.send(&[ix], &[wallet]).await
+    }
+
+    pub async fn propose_authority(
+        &mut self,
+        signer: &Keypair,
+        new_authority: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::ProposeAuthority {
+                authority: signer.pubkey(),
+                global_config: global_config_pda(),
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::ProposeAuthority {
+                new_authority: *new_authority,
+            }
+            .data(),
+        };
+        self.send(&[ix], &[signer]).await
+    }
+
+    pub async fn accept_authority(&mut self, signer: &Keypair) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::AcceptAuthority {
+                new_authority: signer.pubkey(),
+                global_config: global_config_pda(),
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::AcceptAuthority {}.data(),
+        };
+        self.send(&[ix], &[signer]).await
+    }
+
+    pub async fn cancel_authority_proposal(
+        &mut self,
+        signer: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::ProposeAuthority {
+                authority: signer.pubkey(),
+                global_config: global_config_pda(),
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::CancelAuthorityProposal {}.data(),
+        };
+        self.send(&[ix], &[signer]).await
+    }
+
+    pub async fn set_paused(
+        &mut self,
+        signer: &Keypair,
+        paused: u8,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::SetPaused {
+                admin: signer.pubkey(),
+                global_config: global_config_pda(),
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::SetPaused { paused }.data(),
+        };
+        self.send(&[ix], &[signer]).await
+    }
+
+    pub async fn set_curve_frozen(
+        &mut self,
+        signer: &Keypair,
+        mint: &Pubkey,
+        frozen: bool,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::SetCurveFrozen {
+                admin: signer.pubkey(),
+                global_config: global_config_pda(),
+                bonding_curve: bonding_curve_pda(mint),
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::SetCurveFrozen { frozen }.data(),
+        };
+        self.send(&[ix], &[signer]).await
+    }
+
+    pub async fn upgrade_account(
+        &mut self,
+        signer: &Keypair,
+        account: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        self.upgrade_account_with(signer, account, None).await
+    }
+
+    /// Upgrade the bonding curve of `mint`, passing its SOL vault along
+    pub async fn upgrade_bonding_curve(
+        &mut self,
+        signer: &Keypair,
+        mint: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        self.upgrade_account_with(signer, &bonding_curve_pda(mint), Some(sol_vault_pda(mint)))
+            .await
+    }
+
+    pub async fn upgrade_account_with(
+        &mut self,
+        signer: &Keypair,
+        account: &Pubkey,
+        sol_vault: Option<Pubkey>,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::UpgradeAccount {
+                authority: signer.pubkey(),
+                global_config: global_config_pda(),
+                account: *account,
+                sol_vault,
+                system_program: solana_sdk::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::UpgradeAccount {}.data(),
+        };
+        self.send(&[ix], &[signer]).await
+    }
+
+    pub async fn claim_creator_fees(
+        &mut self,
+        creator: &Keypair,
+        mint: &Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::ClaimCreatorFees {
+                creator: creator.pubkey(),
+                bonding_curve: bonding_curve_pda(mint),
+                sol_vault: sol_vault_pda(mint),
+                system_program: solana_sdk::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::ClaimCreatorFees {}.data(),
+        };
+        self.send(&[ix], &[creator]).await
+    }
+
+    /// Pre-create the pool accounts the stand-in AMM expects and run `migrate`
+    pub async fn migrate(&mut self, mint: &Pubkey) -> Result<stubs::Pool, BanksClientError> {
+        self.migrate_with(mint, stubs::Pool::new(mint)).await
+    }
+
+    /// Run `migrate` into `pool`, whose vaults and LP mint are created here
+    pub async fn migrate_with(
+        &mut self,
+        mint: &Pubkey,
+        pool: stubs::Pool,
+    ) -> Result<stubs::Pool, BanksClientError> {
+        let payer = self.payer();
+        let mint_token_program = self.token_program_of(mint).await;
+        let mut setup = Vec::new();
+        let rent = self.ctx.banks_client.get_rent().await.unwrap();
+        let lp_mint = Keypair::new();
+        setup.push(system_instruction::create_account(
+            &payer.pubkey(),
+            &lp_mint.pubkey(),
+            rent.minimum_balance(spl_token::state::Mint::LEN),
+            spl_token::state::Mint::LEN as u64,
+            &spl_token::ID,
+        ));
+        setup.push(
+            spl_token::instruction::initialize_mint2(
+                &spl_token::ID,
+                &lp_mint.pubkey(),
+                &pool.authority,
+                None,
+                9,
+            )
+            .unwrap(),
+        );
+        let vault_0 = Keypair::new();
+        let vault_1 = Keypair::new();
+        for (vault, vault_mint) in [(&vault_0, pool.token_0_mint), (&vault_1, pool.token_1_mint)] {
+            let vault_token_program = if vault_mint == *mint {
+                mint_token_program
+            } else {
+                spl_token::ID
+            };
+            setup.push(system_instruction::create_account(
+                &payer.pubkey(),
+                &vault.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &vault_token_program,
+            ));
+            setup.push(
+                spl_token_2022::instruction::initialize_account3(
+                    &vault_token_program,
+                    &vault.pubkey(),
+                    &vault_mint,
+                    &pool.authority,
+                )
+                .unwrap(),
+            );
+        }
+        setup.push(
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &payer.pubkey(),
+                &payer.pubkey(),
+                &lp_mint.pubkey(),
+                &spl_token::ID,
+            ),
+        );
+        self.send(&setup, &[&lp_mint, &vault_0, &vault_1])
+            .await
+            .unwrap();
+
+        let pool = stubs::Pool {
+            lp_mint: lp_mint.pubkey(),
+            token_0_vault: vault_0.pubkey(),
+            token_1_vault: vault_1.pubkey(),
+            ..pool
+        };
+        let bonding_curve = bonding_curve_pda(mint);
+        let native_mint = spl_token::native_mint::ID;
+        // Curves created before the migration vault existed have none
+        let migration_vault = migration_vault_pda(mint);
+        let migration_vault = self
+            .account(&migration_vault)
+            .await
+            .map(|_| migration_vault);
+        let ix = Instruction {
+            program_id: openclaw::ID,
+            accounts: openclaw::accounts::Migrate {
+                payer: payer.pubkey(),
+                global_config: global_config_pda(),
+                bonding_curve,
+                sol_vault: sol_vault_pda(mint),
+                mint: *mint,
+                curve_token_vault: token_account_under(&bonding_curve, mint, &mint_token_program),
+                migration_vault,
+                fee_recipient: self.fee_recipient,
+                wsol_mint: native_mint,
+                payer_token_account: token_account_under(
+                    &payer.pubkey(),
+                    mint,
+                    &mint_token_program,
+                ),
+                payer_wsol_account: get_associated_token_address(&payer.pubkey(), &native_mint),
+                amm_program: openclaw::amm::ID,
+                amm_config: pool.amm_config,
+                amm_authority: pool.authority,
+                pool_state: pool.pool_state,
+                lp_mint: pool.lp_mint,
+                payer_lp_token: get_associated_token_address(&payer.pubkey(), &pool.lp_mint),
+                token_0_vault: pool.token_0_vault,
+                token_1_vault: pool.token_1_vault,
+                create_pool_fee: pool.create_pool_fee,
+                observation_state: pool.observation_state,
+                token_program: spl_token::ID,
+                mint_token_program,
+                associated_token_program: anchor_spl::associated_token::ID,
+                system_program: solana_sdk::system_program::ID,
+                rent: solana_sdk::sysvar::rent::ID,
+            }
+            .to_account_metas(None),
+            data: openclaw::instruction::Migrate {}.data(),
+        };
+        self.send(&[ix], &[]).await?;
+        Ok(pool)
+    }
+}
+
+// === PDAs ===
+
+pub fn global_config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[GLOBAL_CONFIG_SEED], &openclaw::ID).0
+}
+
+pub fn bonding_curve_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[BONDING_CURVE_SEED, mint.as_ref()], &openclaw::ID).0
+}
+
+pub fn sol_vault_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[CURVE_SOL_VAULT_SEED, mint.as_ref()], &openclaw::ID).0
+}
+
+pub fn migration_vault_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[MIGRATION_VAULT_SEED, mint.as_ref()], &openclaw::ID).0
+}
+
+pub fn price_history_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[PRICE_HISTORY_SEED, mint.as_ref()], &openclaw::ID).0
+}
+
+pub fn position_pda(mint: &Pubkey, owner: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[POSITION_SEED, mint.as_ref(), owner.as_ref()],
+        &openclaw::ID,
+    )
+    .0
+}
+
+pub fn referrer_pda(wallet: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[REFERRER_SEED, wallet.as_ref()], &openclaw::ID).0
+}
+
+pub fn token_account(owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, mint)
+}
+
+/// Associated token account of `owner` for a mint owned by `token_program`
+pub fn token_account_under(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    get_associated_token_address_with_program_id(owner, mint, token_program)
+}
+
+pub fn metadata_pda(mint: &Pubkey) -> Pubkey {
+    let program_id = anchor_spl::metadata::ID;
+    Pubkey::find_program_address(
+        &[METADATA_SEED, program_id.as_ref(), mint.as_ref()],
+        &program_id,
+    )
+    .0
+}
+
+// === Assertions ===
+
+pub fn assert_openclaw_error(
+    result: Result<impl std::fmt::Debug, BanksClientError>,
+    expected: OpenClawError,
+) {
+    let code = u32::from(expected);
+    match result {
+        Err(BanksClientError::TransactionError(TransactionError::InstructionError(
+            _,
+            InstructionError::Custom(actual),
+        ))) => assert_eq!(actual, code, "expected {:?}", expected),
+        other => panic!("expected {:?}, got {:?}", expected, other),
+    }
+}
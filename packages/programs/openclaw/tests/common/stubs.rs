@@ -0,0 +1,189 @@
+//! Stand-ins for external programs openclaw CPIs into.
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::entrypoint::ProgramResult;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::rent::Rent;
+use anchor_lang::solana_program::system_instruction;
+use anchor_lang::solana_program::sysvar::Sysvar;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use openclaw::state::METADATA_SEED;
+
+/// Token metadata stand-in: creates the metadata PDA and stores the raw
+/// `CreateMetadataAccountV3` instruction data in it so tests can inspect it.
+pub fn process_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> ProgramResult {
+    let metadata = &accounts[0];
+    let mint = &accounts[1];
+    let payer = &accounts[3];
+    let system_program = &accounts[5];
+
+    let (address, bump) = Pubkey::find_program_address(
+        &[METADATA_SEED, program_id.as_ref(), mint.key.as_ref()],
+        program_id,
+    );
+    assert_eq!(address, *metadata.key);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            metadata.key,
+            Rent::get()?.minimum_balance(data.len()),
+            data.len() as u64,
+            program_id,
+        ),
+        &[payer.clone(), metadata.clone(), system_program.clone()],
+        &[&[
+            METADATA_SEED,
+            program_id.as_ref(),
+            mint.key.as_ref(),
+            &[bump],
+        ]],
+    )?;
+    metadata.try_borrow_mut_data()?.copy_from_slice(data);
+    Ok(())
+}
+
+pub const AMM_AUTHORITY_SEED: &[u8] = b"vault_and_lp_mint_auth_seed";
+
+/// CP-Swap config the harness initializes the protocol with
+pub const AMM_CONFIG: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// Accounts of a stand-in pool. The vaults and LP mint are created by the test
+/// beforehand, owned by the AMM authority PDA.
+#[derive(Clone, Copy, Debug)]
+pub struct Pool {
+    pub amm_config: Pubkey,
+    pub authority: Pubkey,
+    pub pool_state: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub create_pool_fee: Pubkey,
+    pub observation_state: Pubkey,
+}
+
+impl Pool {
+    pub fn new(mint: &Pubkey) -> Self {
+        let native_mint = spl_token::native_mint::ID;
+        let (token_0_mint, token_1_mint) = if *mint < native_mint {
+            (*mint, native_mint)
+        } else {
+            (native_mint, *mint)
+        };
+        Self {
+            amm_config: AMM_CONFIG,
+            authority: Pubkey::find_program_address(&[AMM_AUTHORITY_SEED], &openclaw::amm::ID).0,
+            pool_state: Pubkey::new_unique(),
+            token_0_mint,
+            token_1_mint,
+            lp_mint: Pubkey::default(),
+            token_0_vault: Pubkey::default(),
+            token_1_vault: Pubkey::default(),
+            create_pool_fee: Pubkey::new_unique(),
+            observation_state: Pubkey::new_unique(),
+        }
+    }
+
+    /// Vault holding `mint` in this pool
+    pub fn vault_for(&self, mint: &Pubkey) -> Pubkey {
+        if *mint == self.token_0_mint {
+            self.token_0_vault
+        } else {
+            self.token_1_vault
+        }
+    }
+}
+
+/// Constant-product AMM stand-in implementing the CP-Swap `initialize` interface:
+/// pulls both initial amounts from the creator, each with its mint's token program, and
+/// mints sqrt(x * y) LP tokens.
+pub fn process_cpmm(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    assert_eq!(data[..8], openclaw::amm::INITIALIZE_DISCRIMINATOR);
+    let amount_0 = u64::from_le_bytes(data[8..16].try_into().unwrap());
+    let amount_1 = u64::from_le_bytes(data[16..24].try_into().unwrap());
+
+    let creator = &accounts[0];
+    let authority = &accounts[2];
+    let token_0_mint = &accounts[4];
+    let token_1_mint = &accounts[5];
+    let lp_mint = &accounts[6];
+    let creator_token_0 = &accounts[7];
+    let creator_token_1 = &accounts[8];
+    let creator_lp_token = &accounts[9];
+    let token_0_vault = &accounts[10];
+    let token_1_vault = &accounts[11];
+    let token_program = &accounts[14];
+    let token_0_program = &accounts[15];
+    let token_1_program = &accounts[16];
+
+    assert!(token_0_mint.key < token_1_mint.key, "mints must be ordered");
+    let (expected_authority, bump) =
+        Pubkey::find_program_address(&[AMM_AUTHORITY_SEED], program_id);
+    assert_eq!(expected_authority, *authority.key);
+
+    for (from, mint, to, program, amount) in [
+        (
+            creator_token_0,
+            token_0_mint,
+            token_0_vault,
+            token_0_program,
+            amount_0,
+        ),
+        (
+            creator_token_1,
+            token_1_mint,
+            token_1_vault,
+            token_1_program,
+            amount_1,
+        ),
+    ] {
+        // Decimals sit at the same offset in both token programs' mints
+        let decimals = mint.try_borrow_data()?[44];
+        invoke(
+            &spl_token_2022::instruction::transfer_checked(
+                program.key,
+                from.key,
+                mint.key,
+                to.key,
+                creator.key,
+                &[],
+                amount,
+                decimals,
+            )?,
+            &[
+                from.clone(),
+                mint.clone(),
+                to.clone(),
+                creator.clone(),
+                program.clone(),
+            ],
+        )?;
+    }
+
+    let lp_amount = ((amount_0 as u128 * amount_1 as u128) as f64).sqrt() as u64;
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            token_program.key,
+            lp_mint.key,
+            creator_lp_token.key,
+            authority.key,
+            &[],
+            lp_amount,
+        )?,
+        &[
+            lp_mint.clone(),
+            creator_lp_token.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        &[&[AMM_AUTHORITY_SEED, &[bump]]],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use common::*;
+use openclaw::error::OpenClawError;
+use solana_program_test::tokio;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn final_buy_is_capped_at_threshold() {
+    let params = default_initialize_params();
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let buyer = h.new_user(20 * LAMPORTS_PER_SOL).await;
+
+    let sol_amount = 15 * LAMPORTS_PER_SOL;
+    let curve = h.bonding_curve(&mint).await;
+    let (sol_in, fee, tokens_out) = curve
+        .get_capped_buy(sol_amount, params.fee_bps, params.migration_threshold)
+        .unwrap();
+    assert_eq!(sol_in - fee, params.migration_threshold);
+    let buyer_before = h.lamports(&buyer.pubkey()).await;
+
+    // Slippage is checked against the filled part of the order
+    let (full_tokens_out, _) = curve.get_tokens_out(sol_amount, params.fee_bps).unwrap();
+    h.buy(&buyer, &mint, sol_amount, full_tokens_out)
+        .await
+        .unwrap();
+
+    // Only the capped amount leaves the buyer's wallet
+    let ata_rent = Rent::default().minimum_balance(165);
+    assert_eq!(
+        h.lamports(&buyer.pubkey()).await,
+        buyer_before - sol_in - ata_rent
+    );
+    assert_eq!(
+        h.token_balance(&token_account(&buyer.pubkey(), &mint))
+            .await,
+        tokens_out
+    );
+
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.real_sol_reserves, params.migration_threshold);
+    assert!(curve.complete);
+    assert!(!curve.migrated);
+}
+
+#[tokio::test]
+async fn complete_curve_rejects_trades_until_migrated() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&trader, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+    assert!(h.bonding_curve(&mint).await.complete);
+
+    let result = h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await;
+    assert_openclaw_error(result, OpenClawError::CurveComplete);
+    let result = h.sell(&trader, &mint, 1_000, 0).await;
+    assert_openclaw_error(result, OpenClawError::CurveComplete);
+    let result = h.buy_exact_tokens(&trader, &mint, 1_000, u64::MAX).await;
+    assert_openclaw_error(result, OpenClawError::CurveComplete);
+    let result = h.sell_for_exact_sol(&trader, &mint, 1_000, u64::MAX).await;
+    assert_openclaw_error(result, OpenClawError::CurveComplete);
+
+    h.migrate(&mint).await.unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    assert!(curve.migrated);
+}
+
+#[tokio::test]
+async fn complete_curve_migrates_after_threshold_is_raised() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&trader, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    let authority = h.authority.insecure_clone();
+    h.update_config(
+        &authority,
+        openclaw::instructions::UpdateConfigParams {
+            migration_threshold: Some(50 * LAMPORTS_PER_SOL),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    h.migrate(&mint).await.unwrap();
+}
+
+#[tokio::test]
+async fn exact_buy_cannot_pass_threshold() {
+    let params = default_initialize_params();
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&trader, &mint, 9 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    let curve = h.bonding_curve(&mint).await;
+    let tokens_out = curve
+        .get_capped_buy(u64::MAX / 2, params.fee_bps, params.migration_threshold)
+        .unwrap()
+        .2;
+
+    let result = h
+        .buy_exact_tokens(&trader, &mint, tokens_out + 1_000_000, u64::MAX)
+        .await;
+    assert_openclaw_error(result, OpenClawError::MigrationThresholdExceeded);
+
+    // Anything up to the threshold is fine
+    h.buy_exact_tokens(&trader, &mint, tokens_out, u64::MAX)
+        .await
+        .unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    assert!(curve.real_sol_reserves <= params.migration_threshold);
+}
@@ -0,0 +1,286 @@
+mod common;
+
+use common::*;
+use openclaw::curve::{CurveKind, MAX_DOUBLINGS};
+use openclaw::error::OpenClawError;
+use openclaw::instructions::{CreateTokenParams, InitializeParams, UpdateConfigParams};
+use solana_program_test::tokio;
+use solana_sdk::program_option::COption;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn create_token_initializes_curve() {
+    let params = default_initialize_params();
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.mint, mint);
+    assert_eq!(curve.creator, creator.pubkey());
+    assert_eq!(curve.virtual_sol_reserves, params.initial_virtual_sol);
+    assert_eq!(curve.virtual_token_reserves, params.initial_virtual_tokens);
+    assert_eq!(curve.real_sol_reserves, 0);
+    assert_eq!(curve.real_token_reserves, params.initial_virtual_tokens);
+    assert_eq!(curve.tokens_sold, 0);
+    assert!(!curve.migrated);
+
+    let vault = token_account(&bonding_curve_pda(&mint), &mint);
+    assert_eq!(h.token_balance(&vault).await, params.initial_virtual_tokens);
+    assert_eq!(curve.migration_tokens, 0);
+    assert_eq!(h.token_balance(&migration_vault_pda(&mint)).await, 0);
+
+    let mint_account = h.mint_account(&mint).await;
+    assert_eq!(mint_account.decimals, 6);
+    assert_eq!(mint_account.supply, params.initial_virtual_tokens);
+    assert_eq!(
+        mint_account.mint_authority,
+        COption::Some(bonding_curve_pda(&mint))
+    );
+
+    assert_eq!(h.global_config().await.total_tokens, 1);
+}
+
+#[tokio::test]
+async fn create_token_writes_metadata() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h
+        .create_token_with(&creator, token_params("Claw Token"))
+        .await
+        .unwrap();
+
+    let args = h.metadata_args(&mint).await;
+    assert_eq!(args.data.name, "Claw Token");
+    assert_eq!(args.data.symbol, "CLAW");
+    assert_eq!(args.data.uri, "https://claw.fun/token.json");
+    assert!(!args.is_mutable);
+}
+
+#[tokio::test]
+async fn create_token_validates_metadata() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+
+    let result = h
+        .create_token_with(&creator, token_params(&"x".repeat(33)))
+        .await;
+    assert_openclaw_error(result, OpenClawError::InvalidTokenName);
+
+    let result = h.create_token_with(&creator, token_params("")).await;
+    assert_openclaw_error(result, OpenClawError::InvalidTokenName);
+
+    let params = CreateTokenParams {
+        symbol: "TOOLONGSYMBOL".to_string(),
+        ..token_params("Claw Token")
+    };
+    let result = h.create_token_with(&creator, params).await;
+    assert_openclaw_error(result, OpenClawError::InvalidTokenSymbol);
+
+    let params = CreateTokenParams {
+        uri: "x".repeat(201),
+        ..token_params("Claw Token")
+    };
+    let result = h.create_token_with(&creator, params).await;
+    assert_openclaw_error(result, OpenClawError::InvalidTokenUri);
+}
+
+#[tokio::test]
+async fn create_token_with_allowed_curve_kind() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let params = CreateTokenParams {
+        curve_kind: LINEAR,
+        ..token_params("Claw Token")
+    };
+    let mint = h.create_token_with(&creator, params).await.unwrap();
+
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.curve_kind, LINEAR);
+    assert_eq!(curve.get_price(6).unwrap(), 27);
+}
+
+#[tokio::test]
+async fn create_token_rejects_disallowed_curve_kind() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let params = CreateTokenParams {
+        curve_kind: CurveKind::Linear {
+            start_price: 1,
+            end_price: 2,
+        },
+        ..token_params("Claw Token")
+    };
+    let result = h.create_token_with(&creator, params).await;
+    assert_openclaw_error(result, OpenClawError::CurveKindNotAllowed);
+}
+
+#[tokio::test]
+async fn curve_that_overflows_supply_is_rejected() {
+    let steep = CurveKind::Exponential {
+        start_price: u64::MAX,
+        doublings: MAX_DOUBLINGS,
+    };
+    let mut h = Harness::start().await;
+    let result = h
+        .initialize(InitializeParams {
+            allowed_curve_kinds: vec![steep],
+            ..default_initialize_params()
+        })
+        .await;
+    assert_openclaw_error(result, OpenClawError::InvalidCurveKind);
+}
+
+#[tokio::test]
+async fn launch_parameters_apply_to_new_curves_only() {
+    let params = default_initialize_params();
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let old_mint = h.create_token(&creator).await;
+    assert_eq!(h.bonding_curve(&old_mint).await.config_version, 0);
+
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            initial_virtual_sol: Some(45 * LAMPORTS_PER_SOL),
+            initial_virtual_tokens: Some(900_000_000_000_000),
+            token_decimals: Some(9),
+            total_supply: Some(1_000_000_000_000_000),
+            curve_supply: Some(800_000_000_000_000),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+    let new_mint = h
+        .create_token_with(&creator, token_params("New Claw"))
+        .await
+        .unwrap();
+
+    let old_curve = h.bonding_curve(&old_mint).await;
+    assert_eq!(old_curve.virtual_sol_reserves, params.initial_virtual_sol);
+    assert_eq!(
+        old_curve.virtual_token_reserves,
+        params.initial_virtual_tokens
+    );
+    assert_eq!(old_curve.config_version, 0);
+
+    let new_curve = h.bonding_curve(&new_mint).await;
+    assert_eq!(new_curve.virtual_sol_reserves, 45 * LAMPORTS_PER_SOL);
+    assert_eq!(new_curve.virtual_token_reserves, 900_000_000_000_000);
+    assert_eq!(new_curve.real_token_reserves, 800_000_000_000_000);
+    assert_eq!(new_curve.config_version, 1);
+    let new_mint_account = h.mint_account(&new_mint).await;
+    assert_eq!(new_mint_account.supply, 1_000_000_000_000_000);
+    assert_eq!(new_mint_account.decimals, 9);
+    assert_eq!(
+        h.token_balance(&token_account(&bonding_curve_pda(&new_mint), &new_mint))
+            .await,
+        800_000_000_000_000
+    );
+    // The rest of the supply is locked away for the DEX pool
+    assert_eq!(new_curve.migration_tokens, 200_000_000_000_000);
+    let migration_vault = h.token_account(&migration_vault_pda(&new_mint)).await;
+    assert_eq!(migration_vault.amount, 200_000_000_000_000);
+    assert_eq!(migration_vault.owner, bonding_curve_pda(&new_mint));
+    assert_eq!(migration_vault.mint, new_mint);
+    assert_eq!(h.mint_account(&old_mint).await.decimals, 6);
+
+    // Launch parameters are the authority's, not the fee admin's
+    let fee_admin = h.new_user(LAMPORTS_PER_SOL).await;
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            fee_admin: Some(fee_admin.pubkey()),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+    let result = h
+        .update_config(
+            &fee_admin,
+            UpdateConfigParams {
+                initial_virtual_sol: Some(LAMPORTS_PER_SOL),
+                ..no_config_changes()
+            },
+        )
+        .await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+    assert_eq!(h.global_config().await.config_version, 2);
+}
+
+#[tokio::test]
+async fn create_token_with_initial_buy() {
+    let mut h = Harness::initialized(InitializeParams {
+        creator_fee_bps: 50,
+        ..default_initialize_params()
+    })
+    .await;
+    let fee_recipient = h.fee_recipient;
+    let creator = h.new_user(5 * LAMPORTS_PER_SOL).await;
+    let params = CreateTokenParams {
+        initial_buy_sol: Some(LAMPORTS_PER_SOL),
+        min_tokens_out: 1,
+        ..token_params("Claw Token")
+    };
+    let fee_recipient_before = h.lamports(&fee_recipient).await;
+    let mint = h.create_token_with(&creator, params).await.unwrap();
+
+    // Priced and charged exactly like a buy on a fresh curve
+    let trader = h.new_user(5 * LAMPORTS_PER_SOL).await;
+    let other = h.create_token(&creator).await;
+    let fee_recipient_after = h.lamports(&fee_recipient).await;
+    h.buy(&trader, &other, LAMPORTS_PER_SOL, 0).await.unwrap();
+    let bought = h
+        .token_balance(&token_account(&trader.pubkey(), &other))
+        .await;
+    let expected = h.bonding_curve(&other).await;
+
+    assert_eq!(
+        h.token_balance(&token_account(&creator.pubkey(), &mint))
+            .await,
+        bought
+    );
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.tokens_sold, bought);
+    assert_eq!(curve.real_sol_reserves, expected.real_sol_reserves);
+    assert_eq!(curve.creator_fees_accrued, expected.creator_fees_accrued);
+    assert_eq!(
+        fee_recipient_after - fee_recipient_before,
+        h.lamports(&fee_recipient).await - fee_recipient_after
+    );
+    assert_eq!(h.global_config().await.total_volume, 2 * LAMPORTS_PER_SOL);
+
+    // The creator's position records the initial buy like any other
+    let position = h.position(&mint, &creator.pubkey()).await;
+    assert_eq!(position.tokens_bought, bought);
+    assert_eq!(position.sol_spent, LAMPORTS_PER_SOL);
+    assert_eq!(position.cost_basis, LAMPORTS_PER_SOL);
+
+    // So selling realizes PnL against what the creator paid
+    let creator_before = h.lamports(&creator.pubkey()).await;
+    h.sell(&creator, &mint, bought, 0).await.unwrap();
+    let received = h.lamports(&creator.pubkey()).await - creator_before;
+    let position = h.position(&mint, &creator.pubkey()).await;
+    assert_eq!(position.cost_basis, 0);
+    assert_eq!(
+        position.realized_pnl().unwrap(),
+        received as i64 - LAMPORTS_PER_SOL as i64
+    );
+}
+
+#[tokio::test]
+async fn initial_buy_respects_slippage() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(5 * LAMPORTS_PER_SOL).await;
+    let params = CreateTokenParams {
+        initial_buy_sol: Some(LAMPORTS_PER_SOL),
+        min_tokens_out: u64::MAX,
+        ..token_params("Claw Token")
+    };
+    let result = h.create_token_with(&creator, params).await;
+    assert_openclaw_error(result, OpenClawError::SlippageExceeded);
+    assert_eq!(h.global_config().await.total_tokens, 0);
+}
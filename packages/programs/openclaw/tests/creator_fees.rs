@@ -0,0 +1,184 @@
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::{InitializeParams, UpdateConfigParams};
+use openclaw::state::MAX_CREATOR_FEE_BPS;
+use solana_program_test::tokio;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+
+fn creator_fee_params() -> InitializeParams {
+    InitializeParams {
+        creator_fee_bps: 50,
+        ..default_initialize_params()
+    }
+}
+
+#[tokio::test]
+async fn trades_accrue_creator_fees() {
+    let mut h = Harness::initialized(creator_fee_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = h.fee_recipient;
+    let vault = sol_vault_pda(&mint);
+    let vault_rent = Rent::default().minimum_balance(0);
+
+    let before = h.bonding_curve(&mint).await;
+    assert_eq!(before.creator_fee_bps, 50);
+    let sol_amount = 2 * LAMPORTS_PER_SOL;
+    let (_, fee) = before.get_tokens_out(sol_amount, 150).unwrap();
+    let (platform_fee, creator_fee) = before.split_fee(fee, 100).unwrap();
+    assert_eq!(creator_fee, sol_amount * 50 / 10_000);
+    let fee_recipient_before = h.lamports(&fee_recipient).await;
+
+    h.buy(&trader, &mint, sol_amount, 0).await.unwrap();
+
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.creator_fees_accrued, creator_fee);
+    assert_eq!(curve.real_sol_reserves, sol_amount - fee);
+    assert_eq!(
+        h.lamports(&fee_recipient).await,
+        fee_recipient_before + platform_fee
+    );
+    assert_eq!(
+        h.lamports(&vault).await,
+        curve.real_sol_reserves + creator_fee + vault_rent
+    );
+
+    let tokens = h
+        .token_balance(&get_associated_token_address(&trader.pubkey(), &mint))
+        .await;
+    let (_, sell_fee) = curve.get_sol_out(tokens / 2, 150).unwrap();
+    let (_, sell_creator_fee) = curve.split_fee(sell_fee, 100).unwrap();
+    h.sell(&trader, &mint, tokens / 2, 0).await.unwrap();
+
+    let after = h.bonding_curve(&mint).await;
+    assert_eq!(after.creator_fees_accrued, creator_fee + sell_creator_fee);
+    assert_eq!(
+        h.lamports(&vault).await,
+        after.real_sol_reserves + after.creator_fees_accrued + vault_rent
+    );
+}
+
+#[tokio::test]
+async fn creator_claims_accrued_fees() {
+    let mut h = Harness::initialized(creator_fee_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&trader, &mint, 3 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    let accrued = h.bonding_curve(&mint).await.creator_fees_accrued;
+    assert!(accrued > 0);
+    let creator_before = h.lamports(&creator.pubkey()).await;
+
+    h.claim_creator_fees(&creator, &mint).await.unwrap();
+
+    // The payer covers the transaction fee, so the creator receives the whole accrual
+    assert_eq!(
+        h.lamports(&creator.pubkey()).await,
+        creator_before + accrued
+    );
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.creator_fees_accrued, 0);
+    assert_eq!(curve.creator_fees_claimed, accrued);
+    assert_eq!(
+        h.lamports(&sol_vault_pda(&mint)).await,
+        curve.real_sol_reserves + Rent::default().minimum_balance(0)
+    );
+
+    h.refresh_blockhash().await;
+    let result = h.claim_creator_fees(&creator, &mint).await;
+    assert_openclaw_error(result, OpenClawError::NoCreatorFees);
+}
+
+#[tokio::test]
+async fn only_creator_can_claim() {
+    let mut h = Harness::initialized(creator_fee_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+
+    let result = h.claim_creator_fees(&trader, &mint).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+    assert!(h.bonding_curve(&mint).await.creator_fees_accrued > 0);
+}
+
+#[tokio::test]
+async fn creator_fees_survive_migration() {
+    let mut h = Harness::initialized(creator_fee_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&trader, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    let accrued = h.bonding_curve(&mint).await.creator_fees_accrued;
+    h.migrate(&mint).await.unwrap();
+    assert_eq!(
+        h.lamports(&sol_vault_pda(&mint)).await,
+        accrued + Rent::default().minimum_balance(0)
+    );
+
+    let creator_before = h.lamports(&creator.pubkey()).await;
+    h.claim_creator_fees(&creator, &mint).await.unwrap();
+    assert_eq!(
+        h.lamports(&creator.pubkey()).await,
+        creator_before + accrued
+    );
+}
+
+#[tokio::test]
+async fn creator_fee_is_bounded_and_fixed_per_curve() {
+    let mut h = Harness::start().await;
+    let result = h
+        .initialize(InitializeParams {
+            creator_fee_bps: MAX_CREATOR_FEE_BPS + 1,
+            ..default_initialize_params()
+        })
+        .await;
+    assert_openclaw_error(result, OpenClawError::CreatorFeeTooHigh);
+
+    h.initialize(creator_fee_params()).await.unwrap();
+    let authority = h.authority.insecure_clone();
+    let result = h
+        .update_config(
+            &authority,
+            UpdateConfigParams {
+                creator_fee_bps: Some(MAX_CREATOR_FEE_BPS + 1),
+                ..no_config_changes()
+            },
+        )
+        .await;
+    assert_openclaw_error(result, OpenClawError::CreatorFeeTooHigh);
+
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            creator_fee_bps: Some(MAX_CREATOR_FEE_BPS),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    // Existing curves keep the creator fee they were created with
+    assert_eq!(h.bonding_curve(&mint).await.creator_fee_bps, 50);
+    let mint = h
+        .create_token_with(&creator, token_params("Second Claw"))
+        .await
+        .unwrap();
+    assert_eq!(
+        h.bonding_curve(&mint).await.creator_fee_bps,
+        MAX_CREATOR_FEE_BPS
+    );
+}
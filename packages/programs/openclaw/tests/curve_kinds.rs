@@ -0,0 +1,73 @@
+mod common;
+
+use common::*;
+use openclaw::curve::CurveKind;
+use openclaw::instructions::CreateTokenParams;
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+
+async fn create(h: &mut Harness, curve_kind: CurveKind) -> Pubkey {
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let params = CreateTokenParams {
+        curve_kind,
+        ..token_params("Claw Token")
+    };
+    h.create_token_with(&creator, params).await.unwrap()
+}
+
+#[tokio::test]
+async fn supply_curves_trade_along_their_cost() {
+    for kind in [LINEAR, EXPONENTIAL] {
+        let mut h = Harness::initialized(default_initialize_params()).await;
+        let mint = create(&mut h, kind).await;
+        let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+        let trader_ata = token_account(&trader.pubkey(), &mint);
+
+        let curve = h.bonding_curve(&mint).await;
+        let (tokens_out, _) = curve.get_tokens_out(2 * LAMPORTS_PER_SOL, 100).unwrap();
+        h.buy(&trader, &mint, 2 * LAMPORTS_PER_SOL, tokens_out)
+            .await
+            .unwrap();
+        assert_eq!(h.token_balance(&trader_ata).await, tokens_out);
+
+        // The price has moved up the curve
+        let curve = h.bonding_curve(&mint).await;
+        assert!(curve.get_price(6).unwrap() > 27);
+
+        let (sol_out, fee) = curve.get_sol_out(tokens_out / 2, 100).unwrap();
+        let trader_before = h.lamports(&trader.pubkey()).await;
+        h.sell(&trader, &mint, tokens_out / 2, sol_out)
+            .await
+            .unwrap();
+        assert_eq!(h.lamports(&trader.pubkey()).await, trader_before + sol_out);
+
+        let after = h.bonding_curve(&mint).await;
+        assert_eq!(
+            after.real_sol_reserves,
+            curve.real_sol_reserves - sol_out - fee
+        );
+        assert_eq!(
+            h.lamports(&sol_vault_pda(&mint)).await,
+            after.real_sol_reserves + Rent::default().minimum_balance(0)
+        );
+    }
+}
+
+#[tokio::test]
+async fn supply_curves_complete_and_migrate() {
+    for kind in [LINEAR, EXPONENTIAL] {
+        let mut h = Harness::initialized(default_initialize_params()).await;
+        let mint = create(&mut h, kind).await;
+        let trader = h.new_user(20 * LAMPORTS_PER_SOL).await;
+
+        h.buy(&trader, &mint, 15 * LAMPORTS_PER_SOL, 0)
+            .await
+            .unwrap();
+        assert!(h.bonding_curve(&mint).await.complete);
+
+        h.migrate(&mint).await.unwrap();
+        assert!(h.bonding_curve(&mint).await.migrated);
+    }
+}
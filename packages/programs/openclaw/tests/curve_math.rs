@@ -0,0 +1,513 @@
+use openclaw::curve::{CurveKind, CURVE_PRICE_SCALE, MAX_DOUBLINGS};
+use openclaw::error::OpenClawError;
+use openclaw::state::BondingCurve;
+use proptest::prelude::*;
+
+const INITIAL_VIRTUAL_SOL: u64 = 30_000_000_000;
+const INITIAL_VIRTUAL_TOKENS: u64 = 1_073_000_000_000_000;
+
+fn curve(virtual_sol_reserves: u64, virtual_token_reserves: u64) -> BondingCurve {
+    BondingCurve {
+        virtual_sol_reserves,
+        virtual_token_reserves,
+        ..Default::default()
+    }
+}
+
+fn fresh_curve() -> BondingCurve {
+    BondingCurve {
+        virtual_sol_reserves: INITIAL_VIRTUAL_SOL,
+        virtual_token_reserves: INITIAL_VIRTUAL_TOKENS,
+        real_token_reserves: INITIAL_VIRTUAL_TOKENS,
+        ..Default::default()
+    }
+}
+
+fn fresh_curve_of(curve_kind: CurveKind) -> BondingCurve {
+    BondingCurve {
+        curve_kind,
+        ..fresh_curve()
+    }
+}
+
+fn supply_kind() -> impl Strategy<Value = CurveKind> {
+    prop_oneof![
+        (1u64..=1_000_000, 1u64..=100).prop_map(|(start_price, ratio)| CurveKind::Linear {
+            start_price,
+            end_price: start_price * ratio,
+        }),
+        (1u64..=1_000_000, 1..=MAX_DOUBLINGS).prop_map(|(start_price, doublings)| {
+            CurveKind::Exponential {
+                start_price,
+                doublings,
+            }
+        }),
+    ]
+}
+
+fn k(curve: &BondingCurve) -> u128 {
+    curve.virtual_sol_reserves as u128 * curve.virtual_token_reserves as u128
+}
+
+fn assert_error<T: std::fmt::Debug>(result: anchor_lang::Result<T>, expected: OpenClawError) {
+    assert_eq!(result.unwrap_err(), expected.into());
+}
+
+/// Mirrors the reserve updates done by the buy handler, `None` where it would fail
+fn apply_buy(curve: &mut BondingCurve, sol_in: u64, fee_bps: u16) -> Option<u64> {
+    let (tokens_out, fee) = curve.get_tokens_out(sol_in, fee_bps).ok()?;
+    if tokens_out > curve.real_token_reserves {
+        return None;
+    }
+    let sol_after_fee = sol_in - fee;
+    curve.virtual_sol_reserves = curve.virtual_sol_reserves.checked_add(sol_after_fee)?;
+    curve.virtual_token_reserves -= tokens_out;
+    curve.real_sol_reserves = curve.real_sol_reserves.checked_add(sol_after_fee)?;
+    curve.real_token_reserves -= tokens_out;
+    curve.tokens_sold += tokens_out;
+    Some(tokens_out)
+}
+
+/// Mirrors the reserve updates done by the sell handler
+fn apply_sell(curve: &mut BondingCurve, tokens_in: u64, fee_bps: u16) -> u64 {
+    let (sol_out, fee) = curve.get_sol_out(tokens_in, fee_bps).unwrap();
+    let gross = sol_out + fee;
+    assert!(
+        gross <= curve.real_sol_reserves,
+        "sell pays out more than the curve holds"
+    );
+    curve.virtual_sol_reserves -= gross;
+    curve.virtual_token_reserves += tokens_in;
+    curve.real_sol_reserves -= gross;
+    curve.real_token_reserves += tokens_in;
+    curve.tokens_sold -= tokens_in;
+    sol_out
+}
+
+#[derive(Debug, Clone)]
+enum Op {
+    Buy(u64),
+    /// Sell this many parts per thousand of the holder's balance
+    Sell(u16),
+}
+
+fn op() -> impl Strategy<Value = Op> {
+    prop_oneof![
+        (1u64..=100_000_000_000).prop_map(Op::Buy),
+        (1u16..=1000).prop_map(Op::Sell),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2_000))]
+
+    #[test]
+    fn buy_never_decreases_k(
+        vs in 1u64..=u64::MAX,
+        vt in 1u64..=u64::MAX,
+        sol_in in any::<u64>(),
+        fee_bps in 0u16..=10_000,
+    ) {
+        let before = curve(vs, vt);
+        let Ok((tokens_out, fee)) = before.get_tokens_out(sol_in, fee_bps) else {
+            return Ok(());
+        };
+        prop_assert!(fee <= sol_in);
+        prop_assert!(tokens_out < vt);
+
+        let new_sol = vs as u128 + (sol_in - fee) as u128;
+        let new_tokens = (vt - tokens_out) as u128;
+        prop_assert!(new_sol * new_tokens >= k(&before));
+    }
+
+    #[test]
+    fn sell_never_decreases_k(
+        vs in 1u64..=u64::MAX,
+        vt in 1u64..=u64::MAX,
+        tokens_in in any::<u64>(),
+        fee_bps in 0u16..=10_000,
+    ) {
+        let before = curve(vs, vt);
+        let Ok((sol_out, fee)) = before.get_sol_out(tokens_in, fee_bps) else {
+            return Ok(());
+        };
+        let gross = sol_out as u128 + fee as u128;
+        prop_assert!(gross < vs as u128);
+
+        let new_sol = vs as u128 - gross;
+        let new_tokens = vt as u128 + tokens_in as u128;
+        prop_assert!(new_sol * new_tokens >= k(&before));
+    }
+
+    #[test]
+    fn exact_buy_covers_tokens_out(
+        vs in 1u64..=u64::MAX,
+        vt in 1u64..=u64::MAX,
+        tokens_out in any::<u64>(),
+        fee_bps in 0u16..10_000,
+    ) {
+        let before = curve(vs, vt);
+        let Ok((sol_in, fee)) = before.get_sol_in(tokens_out, fee_bps) else {
+            return Ok(());
+        };
+        let new_sol = vs as u128 + (sol_in - fee) as u128;
+        let new_tokens = (vt - tokens_out) as u128;
+        prop_assert!(new_sol * new_tokens >= k(&before));
+
+        // Paying the same SOL through the regular path buys at least as much, for no more fee
+        let (forward_tokens, forward_fee) = before.get_tokens_out(sol_in, fee_bps).unwrap();
+        prop_assert!(forward_tokens >= tokens_out);
+        prop_assert!(forward_fee <= fee);
+    }
+
+    #[test]
+    fn exact_sell_covers_sol_out(
+        vs in 1u64..=u64::MAX,
+        vt in 1u64..=u64::MAX,
+        sol_out in any::<u64>(),
+        fee_bps in 0u16..10_000,
+    ) {
+        let before = curve(vs, vt);
+        let Ok((tokens_in, fee)) = before.get_tokens_in(sol_out, fee_bps) else {
+            return Ok(());
+        };
+        let new_sol = vs as u128 - sol_out as u128 - fee as u128;
+        let new_tokens = vt as u128 + tokens_in as u128;
+        prop_assert!(new_sol * new_tokens >= k(&before));
+
+        // Selling the same tokens through the regular path pays out at least as much
+        let (forward_sol, _) = before.get_sol_out(tokens_in, fee_bps).unwrap();
+        prop_assert!(forward_sol >= sol_out);
+    }
+
+    #[test]
+    fn capped_buy_stops_at_threshold(
+        real_sol in 0u64..=100_000_000_000,
+        sol_in in any::<u64>(),
+        threshold in 0u64..=100_000_000_000,
+        fee_bps in 0u16..10_000,
+    ) {
+        let mut c = fresh_curve();
+        c.virtual_sol_reserves += real_sol;
+        c.real_sol_reserves = real_sol;
+        let (capped, fee, tokens_out) = c.get_capped_buy(sol_in, fee_bps, threshold).unwrap();
+        prop_assert!(capped <= sol_in);
+        if capped < sol_in {
+            prop_assert_eq!(c.real_sol_reserves + (capped - fee), threshold.max(real_sol));
+        } else {
+            prop_assert_eq!((tokens_out, fee), c.get_tokens_out(sol_in, fee_bps).unwrap());
+        }
+    }
+
+    #[test]
+    fn supply_cost_is_monotone(
+        kind in supply_kind(),
+        a in 0..=INITIAL_VIRTUAL_TOKENS,
+        b in 0..=INITIAL_VIRTUAL_TOKENS,
+    ) {
+        prop_assume!(fresh_curve_of(kind).check_curve().is_ok());
+        let (low, high) = (a.min(b), a.max(b));
+        let cost = |sold| kind.cost(INITIAL_VIRTUAL_TOKENS, sold).unwrap();
+        prop_assert_eq!(cost(0), 0);
+        prop_assert!(cost(low) <= cost(high));
+        prop_assert!(
+            kind.price(INITIAL_VIRTUAL_TOKENS, low).unwrap()
+                <= kind.price(INITIAL_VIRTUAL_TOKENS, high).unwrap()
+        );
+    }
+
+    #[test]
+    fn supply_trade_sequences_stay_backed(
+        kind in supply_kind(),
+        ops in prop::collection::vec(op(), 1..30),
+        fee_bps in 0u16..=1_000,
+    ) {
+        let mut c = fresh_curve_of(kind);
+        prop_assume!(c.check_curve().is_ok());
+        let mut held = 0u64;
+        for op in ops {
+            match op {
+                Op::Buy(sol_in) => {
+                    if let Some(tokens_out) = apply_buy(&mut c, sol_in, fee_bps) {
+                        held += tokens_out;
+                    }
+                }
+                Op::Sell(parts) => {
+                    let tokens_in = (held as u128 * parts as u128 / 1000) as u64;
+                    apply_sell(&mut c, tokens_in, fee_bps);
+                    held -= tokens_in;
+                }
+            }
+            let cost = kind.cost(INITIAL_VIRTUAL_TOKENS, c.tokens_sold).unwrap();
+            prop_assert!(c.real_sol_reserves >= cost);
+            prop_assert_eq!(c.real_token_reserves + held, INITIAL_VIRTUAL_TOKENS);
+        }
+
+        apply_sell(&mut c, held, fee_bps);
+        prop_assert_eq!(c.tokens_sold, 0);
+    }
+
+    #[test]
+    fn supply_exact_trades_cover_amounts(
+        kind in supply_kind(),
+        bought in 0u64..=100_000_000_000,
+        amount in 0u64..=10_000_000_000,
+        fee_bps in 0u16..10_000,
+    ) {
+        let mut c = fresh_curve_of(kind);
+        prop_assume!(c.check_curve().is_ok());
+        prop_assume!(apply_buy(&mut c, bought, 0).is_some());
+
+        if let Ok((sol_in, fee)) = c.get_sol_in(amount, fee_bps) {
+            let (tokens_out, forward_fee) = c.get_tokens_out(sol_in, fee_bps).unwrap();
+            prop_assert!(tokens_out >= amount);
+            prop_assert!(forward_fee <= fee);
+        }
+        if let Ok((tokens_in, _)) = c.get_tokens_in(amount, fee_bps) {
+            prop_assert!(tokens_in <= c.tokens_sold);
+            let (sol_out, _) = c.get_sol_out(tokens_in, fee_bps).unwrap();
+            prop_assert!(sol_out >= amount);
+        }
+    }
+
+    #[test]
+    fn math_never_panics(
+        vs in any::<u64>(),
+        vt in any::<u64>(),
+        amount in any::<u64>(),
+        fee_bps in any::<u16>(),
+        kind in prop_oneof![Just(CurveKind::ConstantProduct), supply_kind()],
+        tokens_sold in any::<u64>(),
+    ) {
+        let mut c = curve(vs, vt);
+        c.curve_kind = kind;
+        c.tokens_sold = tokens_sold;
+        c.real_token_reserves = vt;
+        let _ = c.check_curve();
+        let _ = c.get_tokens_out(amount, fee_bps);
+        let _ = c.get_sol_out(amount, fee_bps);
+        let _ = c.get_capped_buy(amount, fee_bps, amount / 2);
+        let _ = c.get_sol_in(amount, fee_bps);
+        let _ = c.get_tokens_in(amount, fee_bps);
+        let _ = c.get_price(6);
+    }
+
+    #[test]
+    fn round_trip_is_never_profitable(
+        vs in 1u64..=u64::MAX / 2,
+        vt in 1u64..=u64::MAX,
+        sol_in in 0u64..=u64::MAX / 2,
+        fee_bps in 0u16..=10_000,
+    ) {
+        let mut c = curve(vs, vt);
+        c.real_token_reserves = vt;
+        let Some(tokens_out) = apply_buy(&mut c, sol_in, fee_bps) else {
+            return Ok(());
+        };
+        let (sol_out, fee) = c.get_sol_out(tokens_out, fee_bps).unwrap();
+        prop_assert!(sol_out + fee <= c.real_sol_reserves);
+        prop_assert!(sol_out <= sol_in);
+    }
+
+    #[test]
+    fn trade_sequences_stay_backed(ops in prop::collection::vec(op(), 1..40), fee_bps in 0u16..=1_000) {
+        let mut c = fresh_curve();
+        let k0 = k(&c);
+        let mut held = 0u64;
+        for op in ops {
+            match op {
+                Op::Buy(sol_in) => {
+                    if let Some(tokens_out) = apply_buy(&mut c, sol_in, fee_bps) {
+                        held += tokens_out;
+                    }
+                }
+                Op::Sell(parts) => {
+                    let tokens_in = (held as u128 * parts as u128 / 1000) as u64;
+                    apply_sell(&mut c, tokens_in, fee_bps);
+                    held -= tokens_in;
+                }
+            }
+            prop_assert!(k(&c) >= k0);
+            prop_assert_eq!(c.tokens_sold, held);
+            prop_assert_eq!(c.real_token_reserves + held, INITIAL_VIRTUAL_TOKENS);
+            prop_assert_eq!(c.virtual_sol_reserves, INITIAL_VIRTUAL_SOL + c.real_sol_reserves);
+        }
+
+        // Everyone exiting can always be paid from real reserves
+        apply_sell(&mut c, held, fee_bps);
+        prop_assert_eq!(c.virtual_token_reserves, INITIAL_VIRTUAL_TOKENS);
+    }
+}
+
+#[test]
+fn buy_rounds_tokens_out_down() {
+    // k = 30; 30 / 4 = 7.5 -> the curve keeps 8 tokens, not 7
+    let (tokens_out, _) = curve(3, 10).get_tokens_out(1, 0).unwrap();
+    assert_eq!(tokens_out, 2);
+}
+
+#[test]
+fn sell_rounds_sol_out_down() {
+    // k = 30; 30 / 4 = 7.5 -> the curve keeps 8 lamports, not 7
+    let (sol_out, _) = curve(10, 3).get_sol_out(1, 0).unwrap();
+    assert_eq!(sol_out, 2);
+}
+
+#[test]
+fn dust_buy_rounds_to_zero() {
+    let (tokens_out, _) = curve(u64::MAX, 1).get_tokens_out(1, 0).unwrap();
+    assert_eq!(tokens_out, 0);
+}
+
+#[test]
+fn empty_reserves_are_rejected() {
+    assert_error(
+        curve(0, 10).get_tokens_out(0, 0),
+        OpenClawError::InsufficientLiquidity,
+    );
+    assert_error(
+        curve(10, 0).get_tokens_out(1, 0),
+        OpenClawError::InsufficientLiquidity,
+    );
+    assert_error(
+        curve(0, 0).get_sol_out(0, 0),
+        OpenClawError::InsufficientLiquidity,
+    );
+    assert_error(
+        curve(10, 0).get_sol_out(1, 0),
+        OpenClawError::InsufficientLiquidity,
+    );
+}
+
+#[test]
+fn fee_above_100_percent_is_rejected() {
+    assert_error(
+        fresh_curve().get_tokens_out(1_000, 10_001),
+        OpenClawError::InvalidFeeConfig,
+    );
+    let mut c = fresh_curve();
+    apply_buy(&mut c, 1_000_000_000, 0).unwrap();
+    assert_error(
+        c.get_sol_out(1_000, u16::MAX),
+        OpenClawError::InvalidFeeConfig,
+    );
+}
+
+#[test]
+fn price_does_not_truncate() {
+    assert_error(curve(u64::MAX, 1).get_price(6), OpenClawError::MathOverflow);
+    assert_eq!(fresh_curve().get_price(6).unwrap(), 27);
+}
+
+#[test]
+fn extreme_reserves_stay_in_range() {
+    let c = curve(u64::MAX, u64::MAX);
+    let (tokens_out, fee) = c.get_tokens_out(u64::MAX, 0).unwrap();
+    assert_eq!(fee, 0);
+    assert!(tokens_out <= u64::MAX / 2 + 1);
+    let (sol_out, _) = c.get_sol_out(u64::MAX, 0).unwrap();
+    assert!(sol_out <= u64::MAX / 2 + 1);
+}
+
+#[test]
+fn exact_amounts_round_against_the_trader() {
+    // k = 30; leaving 8 tokens needs 30 / 8 = 3.75 -> 4 SOL in the curve
+    assert_eq!(curve(3, 10).get_sol_in(2, 0).unwrap(), (1, 0));
+    // 1 lamport net at 1% grosses up to 2, the extra lamport is fee
+    assert_eq!(curve(3, 10).get_sol_in(2, 100).unwrap(), (2, 1));
+    // k = 30; leaving 8 lamports needs 30 / 8 = 3.75 -> 4 tokens in the curve
+    assert_eq!(curve(10, 3).get_tokens_in(2, 0).unwrap(), (1, 0));
+}
+
+#[test]
+fn exact_amounts_beyond_reserves_are_rejected() {
+    let c = curve(10, 3);
+    assert_error(c.get_sol_in(3, 0), OpenClawError::InsufficientLiquidity);
+    assert_error(c.get_tokens_in(10, 0), OpenClawError::InsufficientLiquidity);
+    assert_error(c.get_sol_in(1, 10_000), OpenClawError::InvalidFeeConfig);
+    assert_error(c.get_tokens_in(1, 10_000), OpenClawError::InvalidFeeConfig);
+}
+
+#[test]
+fn linear_cost_is_the_average_price() {
+    let kind = CurveKind::Linear {
+        start_price: 1_000,
+        end_price: 3_000,
+    };
+    let supply = 1_000_000_000;
+    assert_eq!(kind.cost(supply, 0).unwrap(), 0);
+    // Average price of 2_000 over the whole supply
+    let expected = 2_000 * supply as u128 / CURVE_PRICE_SCALE;
+    assert_eq!(kind.cost(supply, supply).unwrap() as u128, expected);
+    assert_eq!(kind.price(supply, supply / 2).unwrap(), 2_000);
+    assert_eq!(kind.price(supply, supply).unwrap(), 3_000);
+}
+
+#[test]
+fn exponential_doubles_each_segment() {
+    let kind = CurveKind::Exponential {
+        start_price: 1_000,
+        doublings: 4,
+    };
+    let supply = 4_000_000_000;
+    let step = supply / 4;
+    assert_eq!(kind.price(supply, 0).unwrap(), 1_000);
+    assert_eq!(kind.price(supply, step / 2).unwrap(), 1_500);
+    assert_eq!(kind.price(supply, step).unwrap(), 2_000);
+    assert_eq!(kind.price(supply, supply).unwrap(), 16_000);
+    // Each segment averages 1.5x its starting price
+    assert_eq!(
+        kind.cost(supply, step).unwrap() as u128,
+        1_500 * step as u128 / CURVE_PRICE_SCALE
+    );
+    assert_eq!(
+        kind.cost(supply, supply).unwrap() as u128,
+        1_500 * 15 * step as u128 / CURVE_PRICE_SCALE
+    );
+}
+
+#[test]
+fn supply_curve_price_is_per_token() {
+    let c = fresh_curve_of(CurveKind::Linear {
+        start_price: 27_958,
+        end_price: 279_580,
+    });
+    // Same starting price as the default constant-product curve
+    assert_eq!(c.get_price(6).unwrap(), fresh_curve().get_price(6).unwrap());
+}
+
+#[test]
+fn supply_curve_cannot_sell_more_than_sold() {
+    let mut c = fresh_curve_of(CurveKind::Linear {
+        start_price: 27_958,
+        end_price: 279_580,
+    });
+    let tokens_out = apply_buy(&mut c, 1_000_000_000, 0).unwrap();
+    assert_error(
+        c.get_sol_out(tokens_out + 1, 0),
+        OpenClawError::InsufficientLiquidity,
+    );
+    let proceeds = c.get_sol_out(tokens_out, 0).unwrap().0;
+    assert!(proceeds <= c.real_sol_reserves);
+    assert_error(
+        c.get_tokens_in(proceeds + 1, 0),
+        OpenClawError::InsufficientLiquidity,
+    );
+}
+
+#[test]
+fn supply_curve_cannot_buy_past_supply() {
+    let c = fresh_curve_of(CurveKind::Linear {
+        start_price: 27_958,
+        end_price: 279_580,
+    });
+    assert_error(
+        c.get_tokens_out(u64::MAX / 2, 0),
+        OpenClawError::InsufficientLiquidity,
+    );
+    assert_error(
+        c.get_sol_in(INITIAL_VIRTUAL_TOKENS + 1, 0),
+        OpenClawError::InsufficientLiquidity,
+    );
+    assert!(c.get_sol_in(INITIAL_VIRTUAL_TOKENS, 0).is_ok());
+}
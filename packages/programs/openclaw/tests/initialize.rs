@@ -0,0 +1,333 @@
+mod common;
+
+use common::*;
+use openclaw::curve::{CurveKind, MAX_DOUBLINGS};
+use openclaw::error::OpenClawError;
+use openclaw::instructions::{InitializeParams, UpdateConfigParams};
+use openclaw::state::{MAX_CURVE_KINDS, MAX_FEE_BPS};
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn initialize_sets_config() {
+    let params = default_initialize_params();
+    let mut h = Harness::initialized(default_initialize_params()).await;
+
+    let config = h.global_config().await;
+    assert_eq!(config.authority, h.authority.pubkey());
+    assert_eq!(config.pending_authority, Pubkey::default());
+    assert_eq!(config.fee_admin, Pubkey::default());
+    assert_eq!(config.pause_admin, Pubkey::default());
+    assert_eq!(config.fee_recipient, h.fee_recipient);
+    assert_eq!(config.fee_bps, params.fee_bps);
+    assert_eq!(config.creator_fee_bps, params.creator_fee_bps);
+    assert_eq!(config.max_referral_bps, params.max_referral_bps);
+    assert_eq!(config.migration_threshold, params.migration_threshold);
+    assert_eq!(config.migration_fee, params.migration_fee);
+    assert_eq!(config.initial_virtual_sol, params.initial_virtual_sol);
+    assert_eq!(config.initial_virtual_tokens, params.initial_virtual_tokens);
+    assert_eq!(config.allowed_curve_kinds, params.allowed_curve_kinds);
+    assert_eq!(config.amm_config, params.amm_config);
+    assert_eq!(config.config_version, 0);
+    assert_eq!(config.total_tokens, 0);
+    assert_eq!(config.total_volume, 0);
+}
+
+#[tokio::test]
+async fn initialize_only_once() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    // Different params, so the retry isn't deduplicated as the same transaction
+    let params = InitializeParams {
+        fee_bps: 200,
+        ..default_initialize_params()
+    };
+    assert!(h.initialize(params).await.is_err());
+    assert_eq!(h.global_config().await.fee_bps, 100);
+}
+
+#[tokio::test]
+async fn update_config_by_authority() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let new_recipient = Pubkey::new_unique();
+    let fee_admin = Pubkey::new_unique();
+    let pause_admin = Pubkey::new_unique();
+    let amm_config = Pubkey::new_unique();
+
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            fee_bps: Some(250),
+            creator_fee_bps: Some(75),
+            max_referral_bps: Some(3_000),
+            migration_threshold: Some(50 * LAMPORTS_PER_SOL),
+            migration_fee: Some(LAMPORTS_PER_SOL),
+            initial_virtual_sol: Some(40 * LAMPORTS_PER_SOL),
+            initial_virtual_tokens: Some(800_000_000_000_000),
+            token_decimals: Some(9),
+            total_supply: Some(1_000_000_000_000_000),
+            curve_supply: Some(700_000_000_000_000),
+            fee_recipient: Some(new_recipient),
+            amm_config: Some(amm_config),
+            allowed_curve_kinds: Some(vec![LINEAR]),
+            fee_admin: Some(fee_admin),
+            pause_admin: Some(pause_admin),
+        },
+    )
+    .await
+    .unwrap();
+
+    let config = h.global_config().await;
+    assert_eq!(config.fee_bps, 250);
+    assert_eq!(config.creator_fee_bps, 75);
+    assert_eq!(config.max_referral_bps, 3_000);
+    assert_eq!(config.migration_threshold, 50 * LAMPORTS_PER_SOL);
+    assert_eq!(config.migration_fee, LAMPORTS_PER_SOL);
+    assert_eq!(config.fee_recipient, new_recipient);
+    assert_eq!(config.amm_config, amm_config);
+    assert_eq!(config.allowed_curve_kinds, vec![LINEAR]);
+    assert_eq!(config.initial_virtual_sol, 40 * LAMPORTS_PER_SOL);
+    assert_eq!(config.initial_virtual_tokens, 800_000_000_000_000);
+    assert_eq!(config.token_decimals, 9);
+    assert_eq!(config.total_supply, 1_000_000_000_000_000);
+    assert_eq!(config.curve_supply, 700_000_000_000_000);
+    assert_eq!(config.config_version, 1);
+    assert_eq!(config.fee_admin, fee_admin);
+    assert_eq!(config.pause_admin, pause_admin);
+}
+
+#[tokio::test]
+async fn update_config_rejects_other_signers() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let intruder = h.new_user(LAMPORTS_PER_SOL).await;
+
+    let result = h
+        .update_config(
+            &intruder,
+            UpdateConfigParams {
+                fee_bps: Some(0),
+                ..no_config_changes()
+            },
+        )
+        .await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+}
+
+#[tokio::test]
+async fn update_config_caps_fee() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+
+    let result = h
+        .update_config(
+            &authority,
+            UpdateConfigParams {
+                fee_bps: Some(1001),
+                ..no_config_changes()
+            },
+        )
+        .await;
+    assert_openclaw_error(result, OpenClawError::FeeTooHigh);
+}
+
+#[tokio::test]
+async fn initialize_rejects_nonsensical_config() {
+    let mut h = Harness::start().await;
+    let cases = [
+        (
+            InitializeParams {
+                fee_bps: 10_001,
+                ..default_initialize_params()
+            },
+            OpenClawError::FeeTooHigh,
+        ),
+        (
+            InitializeParams {
+                fee_bps: MAX_FEE_BPS + 1,
+                ..default_initialize_params()
+            },
+            OpenClawError::FeeTooHigh,
+        ),
+        (
+            InitializeParams {
+                max_referral_bps: 10_001,
+                ..default_initialize_params()
+            },
+            OpenClawError::ReferralFeeTooHigh,
+        ),
+        (
+            InitializeParams {
+                initial_virtual_sol: 0,
+                ..default_initialize_params()
+            },
+            OpenClawError::ZeroVirtualReserves,
+        ),
+        (
+            InitializeParams {
+                initial_virtual_tokens: 0,
+                ..default_initialize_params()
+            },
+            OpenClawError::ZeroVirtualReserves,
+        ),
+        (
+            InitializeParams {
+                migration_fee: 10 * LAMPORTS_PER_SOL,
+                ..default_initialize_params()
+            },
+            OpenClawError::MigrationFeeTooHigh,
+        ),
+        (
+            InitializeParams {
+                token_decimals: 8,
+                ..default_initialize_params()
+            },
+            OpenClawError::InvalidTokenDecimals,
+        ),
+        (
+            // All zero is how configs from before these fields read, not a valid setting
+            InitializeParams {
+                token_decimals: 0,
+                total_supply: 0,
+                curve_supply: 0,
+                ..default_initialize_params()
+            },
+            OpenClawError::InvalidTokenDecimals,
+        ),
+        (
+            InitializeParams {
+                curve_supply: 0,
+                ..default_initialize_params()
+            },
+            OpenClawError::InvalidTokenSupply,
+        ),
+        (
+            InitializeParams {
+                total_supply: 1_000_000_000_000_000,
+                ..default_initialize_params()
+            },
+            OpenClawError::InvalidTokenSupply,
+        ),
+        (
+            InitializeParams {
+                total_supply: 2_000_000_000_000_000,
+                curve_supply: 1_500_000_000_000_000,
+                ..default_initialize_params()
+            },
+            OpenClawError::InvalidTokenSupply,
+        ),
+        (
+            // The linear curve raises about 165 SOL over its whole supply
+            InitializeParams {
+                migration_threshold: 200 * LAMPORTS_PER_SOL,
+                ..default_initialize_params()
+            },
+            OpenClawError::MigrationThresholdUnreachable,
+        ),
+        (
+            // Selling out 100M tokens on a constant-product curve raises about 3 SOL
+            InitializeParams {
+                total_supply: 1_000_000_000_000_000,
+                curve_supply: 100_000_000_000_000,
+                allowed_curve_kinds: vec![CurveKind::ConstantProduct],
+                ..default_initialize_params()
+            },
+            OpenClawError::MigrationThresholdUnreachable,
+        ),
+    ];
+    for (params, expected) in cases {
+        let result = h.initialize(params).await;
+        assert_openclaw_error(result, expected);
+    }
+
+    // Only a constant-product curve selling its whole supply, which can always raise more
+    h.initialize(InitializeParams {
+        migration_threshold: 200 * LAMPORTS_PER_SOL,
+        allowed_curve_kinds: vec![CurveKind::ConstantProduct],
+        ..default_initialize_params()
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn update_config_is_validated_as_a_whole() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+
+    // Each change is checked against the rest of the config
+    let result = h
+        .update_config(
+            &authority,
+            UpdateConfigParams {
+                migration_threshold: Some(LAMPORTS_PER_SOL / 20),
+                ..no_config_changes()
+            },
+        )
+        .await;
+    assert_openclaw_error(result, OpenClawError::MigrationFeeTooHigh);
+
+    let result = h
+        .update_config(
+            &authority,
+            UpdateConfigParams {
+                migration_threshold: Some(200 * LAMPORTS_PER_SOL),
+                ..no_config_changes()
+            },
+        )
+        .await;
+    assert_openclaw_error(result, OpenClawError::MigrationThresholdUnreachable);
+
+    // Dropping the linear curve in the same update makes the threshold reachable
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            migration_threshold: Some(200 * LAMPORTS_PER_SOL),
+            allowed_curve_kinds: Some(vec![CurveKind::ConstantProduct, EXPONENTIAL]),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        h.global_config().await.migration_threshold,
+        200 * LAMPORTS_PER_SOL
+    );
+}
+
+#[tokio::test]
+async fn curve_kinds_are_validated() {
+    let mut h = Harness::start().await;
+    let result = h
+        .initialize(InitializeParams {
+            allowed_curve_kinds: vec![],
+            ..default_initialize_params()
+        })
+        .await;
+    assert_openclaw_error(result, OpenClawError::InvalidCurveKind);
+
+    h.initialize(default_initialize_params()).await.unwrap();
+    let authority = h.authority.insecure_clone();
+    for kinds in [
+        vec![CurveKind::Linear {
+            start_price: 10,
+            end_price: 9,
+        }],
+        vec![CurveKind::Exponential {
+            start_price: 10,
+            doublings: 0,
+        }],
+        vec![CurveKind::Exponential {
+            start_price: 10,
+            doublings: MAX_DOUBLINGS + 1,
+        }],
+        vec![CurveKind::ConstantProduct; MAX_CURVE_KINDS + 1],
+    ] {
+        let params = UpdateConfigParams {
+            allowed_curve_kinds: Some(kinds),
+            ..no_config_changes()
+        };
+        let result = h.update_config(&authority, params).await;
+        assert_openclaw_error(result, OpenClawError::InvalidCurveKind);
+    }
+}
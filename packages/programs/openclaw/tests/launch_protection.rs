@@ -0,0 +1,152 @@
+mod common;
+
+use common::*;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::CreateTokenParams;
+use openclaw::state::{LaunchProtection, MAX_LAUNCH_WINDOW_SECS};
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+
+fn protection() -> LaunchProtection {
+    LaunchProtection {
+        window_secs: 60,
+        max_sol_per_tx: LAMPORTS_PER_SOL,
+        max_sol_per_wallet: 3 * LAMPORTS_PER_SOL / 2,
+    }
+}
+
+fn protected_params() -> CreateTokenParams {
+    CreateTokenParams {
+        launch_protection: Some(protection()),
+        ..token_params("Claw Token")
+    }
+}
+
+#[tokio::test]
+async fn launch_window_caps_buys() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h
+        .create_token_with(&creator, protected_params())
+        .await
+        .unwrap();
+    assert_eq!(h.bonding_curve(&mint).await.launch_protection, protection());
+    let sniper = h.new_user(10 * LAMPORTS_PER_SOL).await;
+
+    // Limits are tracked on the buyer's position
+    let result = h.buy(&sniper, &mint, LAMPORTS_PER_SOL, 0).await;
+    assert_openclaw_error(result, OpenClawError::PositionRequired);
+    h.open_position(&sniper, &mint).await.unwrap();
+
+    let result = h.buy(&sniper, &mint, 2 * LAMPORTS_PER_SOL, 0).await;
+    assert_openclaw_error(result, OpenClawError::LaunchLimitExceeded);
+
+    h.buy(&sniper, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+    let result = h.buy(&sniper, &mint, 600_000_000, 0).await;
+    assert_openclaw_error(result, OpenClawError::LaunchLimitExceeded);
+    h.buy(&sniper, &mint, LAMPORTS_PER_SOL / 2, 0)
+        .await
+        .unwrap();
+    let position = h.position(&mint, &sniper.pubkey()).await;
+    assert_eq!(position.owner, sniper.pubkey());
+    assert_eq!(position.launch_sol_spent, 3 * LAMPORTS_PER_SOL / 2);
+
+    // Other wallets have their own allowance
+    let other = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.open_position(&other, &mint).await.unwrap();
+    h.buy(&other, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+
+    // Once the window closes, buys are unlimited and need no position
+    h.advance_clock(60).await;
+    h.refresh_blockhash().await;
+    let late = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&late, &mint, 2 * LAMPORTS_PER_SOL, 0).await.unwrap();
+    h.buy(&sniper, &mint, 2 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        h.position(&mint, &sniper.pubkey()).await.launch_sol_spent,
+        3 * LAMPORTS_PER_SOL / 2
+    );
+}
+
+#[tokio::test]
+async fn launch_window_applies_to_exact_token_buys() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h
+        .create_token_with(&creator, protected_params())
+        .await
+        .unwrap();
+    let sniper = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.open_position(&sniper, &mint).await.unwrap();
+
+    let curve = h.bonding_curve(&mint).await;
+    let (tokens_out, _) = curve.get_tokens_out(2 * LAMPORTS_PER_SOL, 100).unwrap();
+    let result = h
+        .buy_exact_tokens(&sniper, &mint, tokens_out, 3 * LAMPORTS_PER_SOL)
+        .await;
+    assert_openclaw_error(result, OpenClawError::LaunchLimitExceeded);
+}
+
+#[tokio::test]
+async fn launch_window_caps_creator_initial_buy() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(10 * LAMPORTS_PER_SOL).await;
+
+    let params = CreateTokenParams {
+        initial_buy_sol: Some(2 * LAMPORTS_PER_SOL),
+        ..protected_params()
+    };
+    let result = h.create_token_with(&creator, params).await;
+    assert_openclaw_error(result, OpenClawError::LaunchLimitExceeded);
+
+    // The initial buy is charged to the creator's allowance for the window
+    let params = CreateTokenParams {
+        initial_buy_sol: Some(LAMPORTS_PER_SOL),
+        ..protected_params()
+    };
+    let mint = h.create_token_with(&creator, params).await.unwrap();
+    assert_eq!(
+        h.position(&mint, &creator.pubkey()).await.launch_sol_spent,
+        LAMPORTS_PER_SOL
+    );
+    let result = h.buy(&creator, &mint, LAMPORTS_PER_SOL, 0).await;
+    assert_openclaw_error(result, OpenClawError::LaunchLimitExceeded);
+    h.buy(&creator, &mint, LAMPORTS_PER_SOL / 2, 0)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn invalid_launch_protection_is_rejected() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+
+    let invalid = [
+        LaunchProtection {
+            window_secs: 0,
+            ..protection()
+        },
+        LaunchProtection {
+            window_secs: MAX_LAUNCH_WINDOW_SECS + 1,
+            ..protection()
+        },
+        LaunchProtection {
+            max_sol_per_tx: 0,
+            ..protection()
+        },
+        LaunchProtection {
+            max_sol_per_wallet: LAMPORTS_PER_SOL - 1,
+            ..protection()
+        },
+    ];
+    for launch_protection in invalid {
+        let params = CreateTokenParams {
+            launch_protection: Some(launch_protection),
+            ..token_params("Claw Token")
+        };
+        let result = h.create_token_with(&creator, params).await;
+        assert_openclaw_error(result, OpenClawError::InvalidLaunchProtection);
+    }
+}
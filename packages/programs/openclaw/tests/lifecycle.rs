@@ -0,0 +1,92 @@
+mod common;
+
+use common::*;
+use openclaw::error::OpenClawError;
+use solana_program_test::tokio;
+use solana_sdk::native_token::LAMPORTS_PER_SOL as SOL;
+use solana_sdk::signature::Signer;
+
+/// initialize -> create_token -> trades up to the threshold -> migrate
+#[tokio::test]
+async fn full_lifecycle() {
+    let params = default_initialize_params();
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let fee_recipient = h.fee_recipient;
+    let fee_recipient_start = h.lamports(&fee_recipient).await;
+
+    let creator = h.new_user(SOL).await;
+    let mint = h.create_token(&creator).await;
+
+    let alice = h.new_user(20 * SOL).await;
+    let bob = h.new_user(20 * SOL).await;
+    let mut trading_fees = 0;
+    let fee_bps = params.fee_bps;
+
+    // Trade below the threshold: migrate must be refused
+    for (trader, sol_amount) in [(&alice, 3 * SOL), (&bob, 2 * SOL), (&alice, SOL)] {
+        let curve = h.bonding_curve(&mint).await;
+        trading_fees += curve.get_tokens_out(sol_amount, fee_bps).unwrap().1;
+        h.buy(trader, &mint, sol_amount, 0).await.unwrap();
+    }
+    let bob_tokens = h.token_balance(&token_account(&bob.pubkey(), &mint)).await;
+    let curve = h.bonding_curve(&mint).await;
+    trading_fees += curve.get_sol_out(bob_tokens / 2, fee_bps).unwrap().1;
+    h.sell(&bob, &mint, bob_tokens / 2, 0).await.unwrap();
+
+    assert!(h.bonding_curve(&mint).await.real_sol_reserves < params.migration_threshold);
+    assert_openclaw_error(
+        h.migrate(&mint).await,
+        OpenClawError::MigrationThresholdNotReached,
+    );
+
+    // Reach the threshold: the final buy is capped and the curve completes
+    let curve = h.bonding_curve(&mint).await;
+    let (sol_in, fee, _) = curve
+        .get_capped_buy(8 * SOL, fee_bps, params.migration_threshold)
+        .unwrap();
+    assert!(sol_in < 8 * SOL);
+    trading_fees += fee;
+    h.buy(&bob, &mint, 8 * SOL, 0).await.unwrap();
+
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.real_sol_reserves, params.migration_threshold);
+    assert!(curve.complete);
+    assert_openclaw_error(
+        h.sell(&alice, &mint, 1_000, 0).await,
+        OpenClawError::CurveComplete,
+    );
+    let mut held = 0;
+    for trader in [&alice, &bob] {
+        held += h
+            .token_balance(&token_account(&trader.pubkey(), &mint))
+            .await;
+    }
+    assert_eq!(curve.tokens_sold, held);
+    assert_eq!(
+        curve.real_token_reserves + held,
+        params.initial_virtual_tokens
+    );
+
+    let pool = h.migrate(&mint).await.unwrap();
+    assert_eq!(
+        h.token_balance(&pool.vault_for(&anchor_spl::token::spl_token::native_mint::ID))
+            .await,
+        curve.real_sol_reserves - params.migration_fee
+    );
+    assert_eq!(
+        h.token_balance(&pool.vault_for(&mint)).await,
+        curve.real_token_reserves
+    );
+    assert_eq!(
+        h.lamports(&fee_recipient).await,
+        fee_recipient_start + trading_fees + params.migration_fee
+    );
+    assert!(h.bonding_curve(&mint).await.migrated);
+
+    // Holders keep their tokens, but the curve is closed
+    assert_openclaw_error(
+        h.buy(&alice, &mint, SOL, 0).await,
+        OpenClawError::AlreadyMigrated,
+    );
+    assert_openclaw_error(h.migrate(&mint).await, OpenClawError::AlreadyMigrated);
+}
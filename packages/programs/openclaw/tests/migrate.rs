@@ -0,0 +1,154 @@
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token;
+use common::*;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::{InitializeParams, UpdateConfigParams};
+use solana_program_test::tokio;
+use solana_sdk::program_option::COption;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn migrate_moves_liquidity_into_pool() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+
+    let buyer = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&buyer, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    let curve = h.bonding_curve(&mint).await;
+    let config = h.global_config().await;
+    let fee_recipient_before = h.lamports(&h.fee_recipient.clone()).await;
+
+    let pool = h.migrate(&mint).await.unwrap();
+
+    let sol_to_pool = curve.real_sol_reserves - config.migration_fee;
+    let native_mint = spl_token::native_mint::ID;
+    assert_eq!(
+        h.token_balance(&pool.vault_for(&native_mint)).await,
+        sol_to_pool
+    );
+    assert_eq!(
+        h.token_balance(&pool.vault_for(&mint)).await,
+        curve.real_token_reserves
+    );
+    assert_eq!(
+        h.lamports(&h.fee_recipient.clone()).await,
+        fee_recipient_before + config.migration_fee
+    );
+
+    // LP tokens are burned and the curve can no longer mint
+    let payer = h.payer().pubkey();
+    assert_eq!(
+        h.token_balance(&get_associated_token_address(&payer, &pool.lp_mint))
+            .await,
+        0
+    );
+    assert_eq!(h.mint_account(&pool.lp_mint).await.supply, 0);
+    assert_eq!(h.mint_account(&mint).await.mint_authority, COption::None);
+
+    let curve_vault = get_associated_token_address(&bonding_curve_pda(&mint), &mint);
+    assert_eq!(h.token_balance(&curve_vault).await, 0);
+
+    let curve = h.bonding_curve(&mint).await;
+    assert!(curve.migrated);
+    assert_eq!(curve.real_sol_reserves, 0);
+    assert_eq!(curve.real_token_reserves, 0);
+}
+
+#[tokio::test]
+async fn migrate_requires_threshold() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+
+    let result = h.migrate(&mint).await;
+    assert_openclaw_error(result, OpenClawError::MigrationThresholdNotReached);
+}
+
+#[tokio::test]
+async fn migrate_requires_pinned_amm_config() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let buyer = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&buyer, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    // The caller can't pick another fee tier
+    let other_config = Pubkey::new_unique();
+    let pool = stubs::Pool {
+        amm_config: other_config,
+        ..stubs::Pool::new(&mint)
+    };
+    let result = h.migrate_with(&mint, pool).await;
+    assert_openclaw_error(result, OpenClawError::InvalidAmmConfig);
+
+    // Only the authority moves the pin
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            amm_config: Some(other_config),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(h.global_config().await.amm_config, other_config);
+    h.migrate_with(&mint, pool).await.unwrap();
+    assert!(h.bonding_curve(&mint).await.migrated);
+}
+
+#[tokio::test]
+async fn migrate_only_once() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let buyer = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&buyer, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    h.migrate(&mint).await.unwrap();
+    let result = h.migrate(&mint).await;
+    assert_openclaw_error(result, OpenClawError::AlreadyMigrated);
+}
+
+#[tokio::test]
+async fn migrate_adds_migration_allocation_to_pool() {
+    let mut h = Harness::initialized(InitializeParams {
+        total_supply: 1_000_000_000_000_000,
+        curve_supply: 800_000_000_000_000,
+        ..default_initialize_params()
+    })
+    .await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    assert_eq!(
+        h.bonding_curve(&mint).await.real_token_reserves,
+        800_000_000_000_000
+    );
+
+    let buyer = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&buyer, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    let pool = h.migrate(&mint).await.unwrap();
+
+    assert_eq!(
+        h.token_balance(&pool.vault_for(&mint)).await,
+        curve.real_token_reserves + 200_000_000_000_000
+    );
+    let curve_vault = get_associated_token_address(&bonding_curve_pda(&mint), &mint);
+    assert_eq!(h.token_balance(&curve_vault).await, 0);
+    assert_eq!(h.token_balance(&migration_vault_pda(&mint)).await, 0);
+    assert_eq!(h.bonding_curve(&mint).await.migration_tokens, 0);
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::UpdateConfigParams;
+use openclaw::state::{PAUSE_ALL, PAUSE_CREATION, PAUSE_MIGRATION, PAUSE_TRADING};
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn paused_operations_are_rejected() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let trader = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+    let tokens = h
+        .token_balance(&get_associated_token_address(&trader.pubkey(), &mint))
+        .await;
+
+    h.set_paused(&authority, PAUSE_CREATION).await.unwrap();
+    assert_eq!(h.global_config().await.paused, PAUSE_CREATION);
+    let result = h.create_token_with(&creator, token_params("Paused")).await;
+    assert_openclaw_error(result, OpenClawError::Paused);
+    // Trading is unaffected by a creation pause
+    h.buy(&trader, &mint, LAMPORTS_PER_SOL / 2, 0)
+        .await
+        .unwrap();
+
+    h.set_paused(&authority, PAUSE_TRADING).await.unwrap();
+    // Not deduplicated against the identical buy above
+    h.refresh_blockhash().await;
+    let result = h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await;
+    assert_openclaw_error(result, OpenClawError::Paused);
+    let result = h.buy_exact_tokens(&trader, &mint, 1_000, u64::MAX).await;
+    assert_openclaw_error(result, OpenClawError::Paused);
+    let result = h.sell(&trader, &mint, tokens, 0).await;
+    assert_openclaw_error(result, OpenClawError::Paused);
+    let result = h.sell_for_exact_sol(&trader, &mint, 1_000, u64::MAX).await;
+    assert_openclaw_error(result, OpenClawError::Paused);
+    h.create_token_with(&creator, token_params("Unpaused"))
+        .await
+        .unwrap();
+
+    // Take the curve to the threshold, then block its migration
+    h.set_paused(&authority, 0).await.unwrap();
+    h.buy(&trader, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+    h.set_paused(&authority, PAUSE_MIGRATION).await.unwrap();
+    let result = h.migrate(&mint).await;
+    assert_openclaw_error(result, OpenClawError::Paused);
+
+    h.refresh_blockhash().await;
+    h.set_paused(&authority, 0).await.unwrap();
+    h.migrate(&mint).await.unwrap();
+}
+
+#[tokio::test]
+async fn frozen_curve_rejects_trading_and_migration() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let trader = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    let frozen_mint = h.create_token(&creator).await;
+    let other_mint = h
+        .create_token_with(&creator, token_params("Other Claw"))
+        .await
+        .unwrap();
+    h.buy(&trader, &frozen_mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    h.set_curve_frozen(&authority, &frozen_mint, true)
+        .await
+        .unwrap();
+    assert!(h.bonding_curve(&frozen_mint).await.frozen);
+
+    let result = h.buy(&trader, &frozen_mint, LAMPORTS_PER_SOL, 0).await;
+    assert_openclaw_error(result, OpenClawError::Paused);
+    let result = h.migrate(&frozen_mint).await;
+    assert_openclaw_error(result, OpenClawError::Paused);
+    // Other curves keep trading
+    h.buy(&trader, &other_mint, LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    h.set_curve_frozen(&authority, &frozen_mint, false)
+        .await
+        .unwrap();
+    h.migrate(&frozen_mint).await.unwrap();
+}
+
+#[tokio::test]
+async fn only_pause_admins_control_pauses() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let pause_admin = h.new_user(LAMPORTS_PER_SOL).await;
+
+    let result = h.set_paused(&pause_admin, PAUSE_ALL).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+    let result = h.set_curve_frozen(&creator, &mint, true).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            pause_admin: Some(pause_admin.pubkey()),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+    h.refresh_blockhash().await;
+    h.set_paused(&pause_admin, PAUSE_ALL).await.unwrap();
+    h.set_curve_frozen(&pause_admin, &mint, true).await.unwrap();
+    assert_eq!(h.global_config().await.paused, PAUSE_ALL);
+    assert!(h.bonding_curve(&mint).await.frozen);
+
+    let result = h.set_paused(&authority, PAUSE_ALL + 1).await;
+    assert_openclaw_error(result, OpenClawError::InvalidPauseFlags);
+}
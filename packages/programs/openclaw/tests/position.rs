@@ -0,0 +1,135 @@
+mod common;
+
+use common::*;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::{CreateTokenParams, InitializeParams};
+use openclaw::state::{LaunchProtection, Position};
+use solana_program_test::tokio;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn position_tracks_trades() {
+    let mut h = Harness::initialized(InitializeParams {
+        creator_fee_bps: 50,
+        ..default_initialize_params()
+    })
+    .await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.open_position(&trader, &mint).await.unwrap();
+
+    h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+    let first = h.position(&mint, &trader.pubkey()).await;
+    h.advance_clock(10).await;
+    h.buy(&trader, &mint, 2 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    let position = h.position(&mint, &trader.pubkey()).await;
+    let bought = h
+        .token_balance(&token_account(&trader.pubkey(), &mint))
+        .await;
+    assert_eq!(position.tokens_bought, bought);
+    assert_eq!(position.sol_spent, 3 * LAMPORTS_PER_SOL);
+    assert_eq!(position.cost_basis, 3 * LAMPORTS_PER_SOL);
+    assert_eq!(position.fees_paid, 3 * LAMPORTS_PER_SOL * 150 / 10_000);
+    assert_eq!(position.first_trade_at, first.last_trade_at);
+    assert_eq!(position.last_trade_at, position.first_trade_at + 10);
+    assert_eq!(position.realized_pnl().unwrap(), 0);
+
+    // Selling half releases half the cost basis
+    let curve = h.bonding_curve(&mint).await;
+    let (sol_out, fee) = curve.get_sol_out(bought / 2, 150).unwrap();
+    h.sell(&trader, &mint, bought / 2, 0).await.unwrap();
+    let position = h.position(&mint, &trader.pubkey()).await;
+    let remaining_cost = 3 * LAMPORTS_PER_SOL
+        - (3 * LAMPORTS_PER_SOL as u128 * (bought / 2) as u128 / bought as u128) as u64;
+    assert_eq!(position.tokens_held(), bought - bought / 2);
+    assert_eq!(position.cost_basis, remaining_cost);
+    assert_eq!(position.sol_received, sol_out);
+    assert_eq!(
+        position.fees_paid,
+        3 * LAMPORTS_PER_SOL * 150 / 10_000 + fee
+    );
+    assert_eq!(
+        position.realized_pnl().unwrap(),
+        sol_out as i64 - (3 * LAMPORTS_PER_SOL - remaining_cost) as i64
+    );
+
+    let result = h.close_position(&trader, &mint).await;
+    assert_openclaw_error(result, OpenClawError::PositionNotEmpty);
+
+    h.sell(&trader, &mint, bought - bought / 2, 0)
+        .await
+        .unwrap();
+    let position = h.position(&mint, &trader.pubkey()).await;
+    assert_eq!(position.tokens_held(), 0);
+    assert_eq!(position.cost_basis, 0);
+    // Fees on both sides make the round trip a loss
+    assert_eq!(
+        position.realized_pnl().unwrap(),
+        position.sol_received as i64 - 3 * LAMPORTS_PER_SOL as i64
+    );
+    assert!(position.realized_pnl().unwrap() < 0);
+
+    h.refresh_blockhash().await;
+    let before = h.lamports(&trader.pubkey()).await;
+    h.close_position(&trader, &mint).await.unwrap();
+    assert_eq!(
+        h.lamports(&trader.pubkey()).await,
+        before + Rent::default().minimum_balance(Position::LEN)
+    );
+    assert!(h
+        .account(&position_pda(&mint, &trader.pubkey()))
+        .await
+        .is_none());
+}
+
+#[tokio::test]
+async fn position_stays_open_through_launch_window() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let params = CreateTokenParams {
+        launch_protection: Some(LaunchProtection {
+            window_secs: 60,
+            max_sol_per_tx: LAMPORTS_PER_SOL,
+            max_sol_per_wallet: LAMPORTS_PER_SOL,
+        }),
+        ..token_params("Claw Token")
+    };
+    let mint = h.create_token_with(&creator, params).await.unwrap();
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.open_position(&trader, &mint).await.unwrap();
+
+    // Closing and reopening would reset the wallet's launch allowance
+    let result = h.close_position(&trader, &mint).await;
+    assert_openclaw_error(result, OpenClawError::PositionLocked);
+
+    // A fresh blockhash, or the retry is deduplicated against the rejected close
+    h.advance_clock(60).await;
+    h.refresh_blockhash().await;
+    h.close_position(&trader, &mint).await.unwrap();
+}
+
+#[test]
+fn realized_pnl_overflow_is_an_error() {
+    let position = Position {
+        sol_received: u64::MAX,
+        ..Position::default()
+    };
+    assert_eq!(
+        position.realized_pnl().unwrap_err(),
+        OpenClawError::MathOverflow.into()
+    );
+
+    let position = Position {
+        sol_spent: u64::MAX,
+        ..Position::default()
+    };
+    assert_eq!(
+        position.realized_pnl().unwrap_err(),
+        OpenClawError::MathOverflow.into()
+    );
+}
@@ -0,0 +1,120 @@
+mod common;
+
+use common::*;
+use openclaw::error::OpenClawError;
+use openclaw::state::{OBSERVATIONS, PRICE_HISTORY_SEED};
+use solana_program_test::tokio;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn twap_weights_prices_by_slots() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(20 * LAMPORTS_PER_SOL).await;
+
+    let history = h.price_history(&mint).await;
+    assert_eq!(history.mint, mint);
+    assert_eq!(history.count, 1);
+    let opened = history.latest().unwrap();
+    assert_eq!(opened.cumulative_price, 0);
+    let p0 = h.bonding_curve(&mint).await.spot_price().unwrap();
+
+    h.advance_slots(10).await;
+    h.buy(&trader, &mint, 2 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+    let bought = h.price_history(&mint).await.latest().unwrap();
+    assert_eq!(bought.slot, opened.slot + 10);
+    assert_eq!(bought.cumulative_price, p0 * 10);
+    let curve = h.bonding_curve(&mint).await;
+    let p1 = curve.spot_price().unwrap();
+    assert!(p1 > p0);
+
+    h.advance_slots(30).await;
+    let now = h.slot().await;
+    let history = h.price_history(&mint).await;
+    let twap = |window: u64| history.twap(&curve, now, window).unwrap();
+    assert_eq!(twap(40), (p0 * 10 + p1 * 30) / 40);
+    assert_eq!(twap(30), p1);
+    // Windows starting between observations interpolate
+    assert_eq!(twap(35), (p0 * 5 + p1 * 30) / 35);
+    assert_eq!(
+        history.twap(&curve, now, 41).unwrap_err(),
+        OpenClawError::InsufficientPriceHistory.into()
+    );
+
+    // Trades within a slot carry no weight, so a round trip can't move the average
+    let before = twap(40);
+    h.buy(&trader, &mint, 10 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+    let pumped = h.bonding_curve(&mint).await;
+    assert!(pumped.spot_price().unwrap() > p1);
+    let history = h.price_history(&mint).await;
+    assert_eq!(history.latest().unwrap().slot, now);
+    assert_eq!(history.twap(&pumped, now, 40).unwrap(), before);
+}
+
+#[tokio::test]
+async fn history_keeps_latest_observations() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+
+    for i in 0..OBSERVATIONS as u64 + 8 {
+        h.advance_slots(1).await;
+        h.buy(&trader, &mint, 10_000_000 + i, 0).await.unwrap();
+    }
+    let history = h.price_history(&mint).await;
+    assert_eq!(history.count as usize, OBSERVATIONS);
+    let latest = history.latest().unwrap();
+    assert_eq!(latest.slot, h.slot().await);
+    let oldest = history.observations[(history.head as usize + 1) % OBSERVATIONS];
+    assert_eq!(oldest.slot, latest.slot - OBSERVATIONS as u64 + 1);
+
+    let curve = h.bonding_curve(&mint).await;
+    let window = latest.slot - oldest.slot;
+    assert!(history.twap(&curve, latest.slot, window).is_ok());
+    assert!(history.twap(&curve, latest.slot, window + 1).is_err());
+}
+
+#[tokio::test]
+async fn first_trade_starts_missing_price_history() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+
+    // Buying and selling both keep working on curves without a price history
+    for buy in [true, false] {
+        let mint = h
+            .create_token_with(&creator, token_params("Claw Token"))
+            .await
+            .unwrap();
+        if !buy {
+            h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+        }
+        h.set_account(&price_history_pda(&mint), Account::default());
+        h.advance_slots(1).await;
+        if buy {
+            h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+        } else {
+            let held = h
+                .token_balance(&token_account(&trader.pubkey(), &mint))
+                .await;
+            h.sell(&trader, &mint, held, 0).await.unwrap();
+        }
+
+        let history = h.price_history(&mint).await;
+        assert_eq!(history.mint, mint);
+        assert_eq!(
+            history.bump,
+            Pubkey::find_program_address(&[PRICE_HISTORY_SEED, mint.as_ref()], &openclaw::ID,).1
+        );
+        assert_eq!(history.count, 1);
+        assert_eq!(history.latest().unwrap().slot, h.slot().await);
+    }
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use openclaw::curve::CurveKind;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::{CreateTokenParams, InitializeParams, TradeSide, UpdateConfigParams};
+use openclaw::state::PAUSE_TRADING;
+use solana_program_test::tokio;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn quote_matches_executed_trades() {
+    let mut h = Harness::initialized(InitializeParams {
+        creator_fee_bps: 50,
+        ..default_initialize_params()
+    })
+    .await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = h.fee_recipient;
+
+    let sol_amount = 2 * LAMPORTS_PER_SOL;
+    let before = h.bonding_curve(&mint).await;
+    let quote = h.quote(&mint, TradeSide::Buy, sol_amount).await.unwrap();
+    assert_eq!(quote.sol_amount, sol_amount);
+    assert_eq!(quote.fee + quote.creator_fee, sol_amount * 150 / 10_000);
+    assert!(quote.price_after > quote.price_before);
+    assert_eq!(
+        quote.price_impact_bps as u128,
+        (quote.price_after - quote.price_before) * 10_000 / quote.price_before
+    );
+    assert!(!quote.completes_curve);
+    // Simulating leaves the curve untouched
+    assert_eq!(
+        h.bonding_curve(&mint).await.real_sol_reserves,
+        before.real_sol_reserves
+    );
+
+    let fee_recipient_before = h.lamports(&fee_recipient).await;
+    h.buy(&trader, &mint, sol_amount, 0).await.unwrap();
+    let ata = get_associated_token_address(&trader.pubkey(), &mint);
+    let tokens = h.token_balance(&ata).await;
+    assert_eq!(tokens, quote.token_amount);
+    assert_eq!(
+        h.lamports(&fee_recipient).await,
+        fee_recipient_before + quote.fee
+    );
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.creator_fees_accrued, quote.creator_fee);
+    assert_eq!(curve.spot_price().unwrap(), quote.price_after);
+
+    let quote = h.quote(&mint, TradeSide::Sell, tokens).await.unwrap();
+    assert_eq!(quote.token_amount, tokens);
+    assert!(quote.price_after < quote.price_before);
+    let trader_before = h.lamports(&trader.pubkey()).await;
+    h.sell(&trader, &mint, tokens, quote.sol_amount)
+        .await
+        .unwrap();
+    // The harness payer covers the transaction fee
+    assert_eq!(
+        h.lamports(&trader.pubkey()).await,
+        trader_before + quote.sol_amount
+    );
+    assert_eq!(
+        h.bonding_curve(&mint).await.spot_price().unwrap(),
+        quote.price_after
+    );
+}
+
+#[tokio::test]
+async fn quote_caps_buy_at_migration_threshold() {
+    for curve_kind in [CurveKind::ConstantProduct, LINEAR, EXPONENTIAL] {
+        let mut h = Harness::initialized(default_initialize_params()).await;
+        let creator = h.new_user(LAMPORTS_PER_SOL).await;
+        let params = CreateTokenParams {
+            curve_kind,
+            ..token_params("Claw Token")
+        };
+        let mint = h.create_token_with(&creator, params).await.unwrap();
+
+        let quote = h
+            .quote(&mint, TradeSide::Buy, 50 * LAMPORTS_PER_SOL)
+            .await
+            .unwrap();
+        assert!(quote.sol_amount < 50 * LAMPORTS_PER_SOL);
+        assert!(quote.completes_curve);
+
+        let trader = h.new_user(60 * LAMPORTS_PER_SOL).await;
+        h.buy(&trader, &mint, 50 * LAMPORTS_PER_SOL, 0)
+            .await
+            .unwrap();
+        let ata = get_associated_token_address(&trader.pubkey(), &mint);
+        assert_eq!(h.token_balance(&ata).await, quote.token_amount);
+        assert!(h.bonding_curve(&mint).await.complete);
+
+        let result = h.quote(&mint, TradeSide::Sell, 1).await;
+        assert_openclaw_error(result, OpenClawError::CurveComplete);
+    }
+}
+
+#[tokio::test]
+async fn quote_flags_buy_that_sells_out_the_curve() {
+    // Few enough base units that a lamport buys less than one, so some buy takes exactly
+    // what is left
+    let mut h = Harness::initialized(InitializeParams {
+        migration_threshold: 5 * LAMPORTS_PER_SOL,
+        initial_virtual_tokens: 1_073_000_000,
+        total_supply: 1_000_000_000,
+        curve_supply: 200_000_000,
+        allowed_curve_kinds: vec![CurveKind::ConstantProduct],
+        ..default_initialize_params()
+    })
+    .await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+
+    // Raise the threshold past what the curve's own allocation can raise, so buying it
+    // out completes the curve short of the threshold
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            migration_threshold: Some(10 * LAMPORTS_PER_SOL),
+            curve_supply: Some(800_000_000),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    let curve = h.bonding_curve(&mint).await;
+    let fee_bps = h.global_config().await.fee_bps;
+    // The least SOL that buys out the curve
+    let (mut low, mut high) = (0, 10 * LAMPORTS_PER_SOL);
+    while low < high {
+        let mid = (low + high) / 2;
+        let (tokens_out, _) = curve.get_tokens_out(mid, fee_bps).unwrap();
+        if tokens_out < curve.real_token_reserves {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    let sol_in = low;
+    let quote = h.quote(&mint, TradeSide::Buy, sol_in).await.unwrap();
+    assert_eq!(quote.token_amount, curve.real_token_reserves);
+    assert!(quote.completes_curve);
+
+    let buyer = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&buyer, &mint, sol_in, quote.token_amount)
+        .await
+        .unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.real_token_reserves, 0);
+    assert!(curve.real_sol_reserves < 10 * LAMPORTS_PER_SOL);
+    assert!(curve.complete);
+}
+
+#[tokio::test]
+async fn quote_fails_like_the_trade() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+
+    let result = h.quote(&mint, TradeSide::Sell, 1_000_000).await;
+    assert_openclaw_error(result, OpenClawError::InsufficientLiquidity);
+
+    h.set_paused(&authority, PAUSE_TRADING).await.unwrap();
+    let result = h.quote(&mint, TradeSide::Buy, LAMPORTS_PER_SOL).await;
+    assert_openclaw_error(result, OpenClawError::Paused);
+}
@@ -0,0 +1,135 @@
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::UpdateConfigParams;
+use solana_program_test::tokio;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn referrer_gets_share_of_platform_fee() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let partner = h.new_user(LAMPORTS_PER_SOL).await;
+    h.register_referrer(&partner, 1_000).await.unwrap();
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = h.fee_recipient;
+
+    let sol_amount = 2 * LAMPORTS_PER_SOL;
+    let (_, fee) = h
+        .bonding_curve(&mint)
+        .await
+        .get_tokens_out(sol_amount, 100)
+        .unwrap();
+    let referral_fee = fee / 10;
+    let partner_before = h.lamports(&partner.pubkey()).await;
+    let fee_recipient_before = h.lamports(&fee_recipient).await;
+
+    h.buy_with_referrer(&trader, &mint, sol_amount, &partner.pubkey())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        h.lamports(&partner.pubkey()).await,
+        partner_before + referral_fee
+    );
+    assert_eq!(
+        h.lamports(&fee_recipient).await,
+        fee_recipient_before + fee - referral_fee
+    );
+
+    let tokens = h
+        .token_balance(&get_associated_token_address(&trader.pubkey(), &mint))
+        .await;
+    let (sol_out, sell_fee) = h
+        .bonding_curve(&mint)
+        .await
+        .get_sol_out(tokens, 100)
+        .unwrap();
+    h.sell_with_referrer(&trader, &mint, tokens, &partner.pubkey())
+        .await
+        .unwrap();
+
+    let referrer = h.referrer(&partner.pubkey()).await;
+    assert_eq!(referrer.wallet, partner.pubkey());
+    assert_eq!(referrer.trade_count, 2);
+    assert_eq!(referrer.total_volume, sol_amount + sol_out + sell_fee);
+    assert_eq!(referrer.total_fees, referral_fee + sell_fee / 10);
+    assert_eq!(
+        h.lamports(&partner.pubkey()).await,
+        partner_before + referrer.total_fees
+    );
+}
+
+#[tokio::test]
+async fn referral_share_is_capped_by_config() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let partner = h.new_user(LAMPORTS_PER_SOL).await;
+
+    let result = h.register_referrer(&partner, 2_001).await;
+    assert_openclaw_error(result, OpenClawError::ReferralFeeTooHigh);
+    h.register_referrer(&partner, 2_000).await.unwrap();
+
+    // Lowering the cap applies to referrers already registered above it
+    let authority = h.authority.insecure_clone();
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            max_referral_bps: Some(500),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    let (_, fee) = h
+        .bonding_curve(&mint)
+        .await
+        .get_tokens_out(LAMPORTS_PER_SOL, 100)
+        .unwrap();
+    h.buy_with_referrer(&trader, &mint, LAMPORTS_PER_SOL, &partner.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        h.referrer(&partner.pubkey()).await.total_fees,
+        fee * 500 / 10_000
+    );
+
+    let result = h
+        .update_config(
+            &authority,
+            UpdateConfigParams {
+                max_referral_bps: Some(10_001),
+                ..no_config_changes()
+            },
+        )
+        .await;
+    assert_openclaw_error(result, OpenClawError::ReferralFeeTooHigh);
+}
+
+#[tokio::test]
+async fn referrer_must_be_registered_and_not_the_trader() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+
+    // No referrer account for this wallet
+    let stranger = Pubkey::new_unique();
+    let result = h
+        .buy_with_referrer(&trader, &mint, LAMPORTS_PER_SOL, &stranger)
+        .await;
+    assert!(result.is_err());
+
+    h.register_referrer(&trader, 1_000).await.unwrap();
+    let result = h
+        .buy_with_referrer(&trader, &mint, LAMPORTS_PER_SOL, &trader.pubkey())
+        .await;
+    assert_openclaw_error(result, OpenClawError::InvalidReferrer);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use anchor_spl::associated_token::get_associated_token_address;
+use common::*;
+use solana_program_test::tokio;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn sol_vault_backs_real_reserves() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let vault = sol_vault_pda(&mint);
+    let vault_rent = Rent::default().minimum_balance(0);
+    let curve_account_lamports = h.lamports(&bonding_curve_pda(&mint)).await;
+
+    assert_eq!(h.lamports(&vault).await, vault_rent);
+
+    let buyer = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&buyer, &mint, 2 * LAMPORTS_PER_SOL, 0).await.unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    assert!(curve.real_sol_reserves > 0);
+    assert_eq!(
+        h.lamports(&vault).await,
+        curve.real_sol_reserves + vault_rent
+    );
+
+    let tokens = h
+        .token_balance(&get_associated_token_address(&buyer.pubkey(), &mint))
+        .await;
+    h.sell(&buyer, &mint, tokens / 2, 0).await.unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(
+        h.lamports(&vault).await,
+        curve.real_sol_reserves + vault_rent
+    );
+
+    // Trading liquidity never touches the curve data account
+    assert_eq!(
+        h.lamports(&bonding_curve_pda(&mint)).await,
+        curve_account_lamports
+    );
+}
+
+#[tokio::test]
+async fn sol_vault_tolerates_donations() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let vault = sol_vault_pda(&mint);
+
+    h.fund(&vault, 12_345).await;
+
+    let buyer = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&buyer, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(
+        h.lamports(&vault).await,
+        curve.real_sol_reserves + Rent::default().minimum_balance(0) + 12_345
+    );
+}
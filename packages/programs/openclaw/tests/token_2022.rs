@@ -0,0 +1,189 @@
+mod common;
+
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::metadata_pointer::MetadataPointer;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, StateWithExtensions,
+};
+use common::*;
+use openclaw::instructions::{CreateTokenParams, InitializeParams};
+use solana_program_test::tokio;
+use solana_sdk::program_option::COption;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn create_token_under_token_2022() {
+    let params = default_initialize_params();
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h
+        .create_token_under(&creator, token_params("Claw Token"), spl_token_2022::ID)
+        .await
+        .unwrap();
+
+    assert_eq!(h.token_program_of(&mint).await, spl_token_2022::ID);
+    let mint_account = h.mint_account(&mint).await;
+    assert_eq!(mint_account.decimals, 6);
+    assert_eq!(mint_account.supply, params.initial_virtual_tokens);
+    assert_eq!(
+        mint_account.mint_authority,
+        COption::Some(bonding_curve_pda(&mint))
+    );
+
+    let vault = token_account_under(&bonding_curve_pda(&mint), &mint, &spl_token_2022::ID);
+    assert_eq!(h.token_balance(&vault).await, params.initial_virtual_tokens);
+    assert_eq!(
+        h.bonding_curve(&mint).await.real_token_reserves,
+        params.initial_virtual_tokens
+    );
+
+    // Metadata lives in the mint itself, not in a Metaplex account
+    let account = h.account(&mint).await.unwrap();
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&account.data).unwrap();
+    let pointer = state.get_extension::<MetadataPointer>().unwrap();
+    assert_eq!(Option::<Pubkey>::from(pointer.metadata_address), Some(mint));
+    assert_eq!(Option::<Pubkey>::from(pointer.authority), None);
+
+    let metadata = h.token_metadata(&mint).await;
+    assert_eq!(metadata.mint, mint);
+    assert_eq!(metadata.name, "Claw Token");
+    assert_eq!(metadata.symbol, "CLAW");
+    assert_eq!(metadata.uri, "https://claw.fun/token.json");
+    assert_eq!(
+        Option::<Pubkey>::from(metadata.update_authority),
+        Some(bonding_curve_pda(&mint))
+    );
+    assert!(h.account(&metadata_pda(&mint)).await.is_none());
+}
+
+#[tokio::test]
+async fn trade_on_both_token_programs() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let classic = h
+        .create_token_under(&creator, token_params("Claw Token"), spl_token::ID)
+        .await
+        .unwrap();
+    let token_2022 = h
+        .create_token_under(&creator, token_params("Claw Token"), spl_token_2022::ID)
+        .await
+        .unwrap();
+
+    // The same trades move the same amounts whichever program owns the mint
+    let mut results = Vec::new();
+    for (mint, token_program) in [(classic, spl_token::ID), (token_2022, spl_token_2022::ID)] {
+        let trader = h.new_user(5 * LAMPORTS_PER_SOL).await;
+        let trader_tokens = token_account_under(&trader.pubkey(), &mint, &token_program);
+        let vault = token_account_under(&bonding_curve_pda(&mint), &mint, &token_program);
+
+        h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+        let bought = h.token_balance(&trader_tokens).await;
+        assert!(bought > 0);
+        assert_eq!(h.token_account(&trader_tokens).await.owner, trader.pubkey());
+        assert_eq!(
+            h.account(&trader_tokens).await.unwrap().owner,
+            token_program
+        );
+
+        h.sell(&trader, &mint, bought / 2, 0).await.unwrap();
+        let curve = h.bonding_curve(&mint).await;
+        assert_eq!(h.token_balance(&trader_tokens).await, bought - bought / 2);
+        assert_eq!(h.token_balance(&vault).await, curve.real_token_reserves);
+        results.push((bought, curve.real_sol_reserves));
+    }
+    assert_eq!(results[0], results[1]);
+}
+
+#[tokio::test]
+async fn initial_buy_under_token_2022() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(5 * LAMPORTS_PER_SOL).await;
+    let mint = h
+        .create_token_under(
+            &creator,
+            CreateTokenParams {
+                initial_buy_sol: Some(LAMPORTS_PER_SOL),
+                ..token_params("Claw Token")
+            },
+            spl_token_2022::ID,
+        )
+        .await
+        .unwrap();
+
+    let curve = h.bonding_curve(&mint).await;
+    let creator_tokens = token_account_under(&creator.pubkey(), &mint, &spl_token_2022::ID);
+    assert!(curve.tokens_sold > 0);
+    assert_eq!(h.token_balance(&creator_tokens).await, curve.tokens_sold);
+}
+
+#[tokio::test]
+async fn migrate_token_2022_curve() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h
+        .create_token_under(&creator, token_params("Claw Token"), spl_token_2022::ID)
+        .await
+        .unwrap();
+    let buyer = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&buyer, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    let curve = h.bonding_curve(&mint).await;
+    let config = h.global_config().await;
+    let pool = h.migrate(&mint).await.unwrap();
+
+    let token_vault = pool.vault_for(&mint);
+    assert_eq!(
+        h.account(&token_vault).await.unwrap().owner,
+        spl_token_2022::ID
+    );
+    assert_eq!(
+        h.token_balance(&token_vault).await,
+        curve.real_token_reserves
+    );
+    assert_eq!(
+        h.token_balance(&pool.vault_for(&spl_token::native_mint::ID))
+            .await,
+        curve.real_sol_reserves - config.migration_fee
+    );
+    assert_eq!(h.mint_account(&mint).await.mint_authority, COption::None);
+    assert!(h.bonding_curve(&mint).await.migrated);
+}
+
+#[tokio::test]
+async fn migration_vault_under_token_2022() {
+    let mut h = Harness::initialized(InitializeParams {
+        total_supply: 1_000_000_000_000_000,
+        curve_supply: 800_000_000_000_000,
+        ..default_initialize_params()
+    })
+    .await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h
+        .create_token_under(&creator, token_params("Claw Token"), spl_token_2022::ID)
+        .await
+        .unwrap();
+
+    let migration_vault = migration_vault_pda(&mint);
+    assert_eq!(
+        h.account(&migration_vault).await.unwrap().owner,
+        spl_token_2022::ID
+    );
+    assert_eq!(h.token_balance(&migration_vault).await, 200_000_000_000_000);
+
+    let buyer = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&buyer, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    let pool = h.migrate(&mint).await.unwrap();
+
+    assert_eq!(
+        h.token_balance(&pool.vault_for(&mint)).await,
+        curve.real_token_reserves + 200_000_000_000_000
+    );
+    assert_eq!(h.token_balance(&migration_vault).await, 0);
+}
@@ -0,0 +1,393 @@
+mod common;
+
+use common::*;
+use openclaw::curve::CurveKind;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::{InitializeParams, UpdateConfigParams};
+use solana_program_test::tokio;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+
+#[tokio::test]
+async fn buy_transfers_tokens_and_fees() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let buyer = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = h.fee_recipient;
+
+    let sol_amount = LAMPORTS_PER_SOL;
+    let before = h.bonding_curve(&mint).await;
+    let (tokens_out, fee) = before
+        .get_tokens_out(sol_amount, h.global_config().await.fee_bps)
+        .unwrap();
+    let buyer_before = h.lamports(&buyer.pubkey()).await;
+    let fee_recipient_before = h.lamports(&fee_recipient).await;
+
+    h.buy(&buyer, &mint, sol_amount, tokens_out).await.unwrap();
+
+    let buyer_ata = token_account(&buyer.pubkey(), &mint);
+    assert_eq!(h.token_balance(&buyer_ata).await, tokens_out);
+    let ata_rent = Rent::default().minimum_balance(165);
+    assert_eq!(
+        h.lamports(&buyer.pubkey()).await,
+        buyer_before - sol_amount - ata_rent
+    );
+    assert_eq!(h.lamports(&fee_recipient).await, fee_recipient_before + fee);
+
+    let after = h.bonding_curve(&mint).await;
+    let sol_in = sol_amount - fee;
+    assert_eq!(
+        after.virtual_sol_reserves,
+        before.virtual_sol_reserves + sol_in
+    );
+    assert_eq!(
+        after.virtual_token_reserves,
+        before.virtual_token_reserves - tokens_out
+    );
+    assert_eq!(after.real_sol_reserves, sol_in);
+    assert_eq!(
+        after.real_token_reserves,
+        before.real_token_reserves - tokens_out
+    );
+    assert_eq!(after.tokens_sold, tokens_out);
+
+    let vault = token_account(&bonding_curve_pda(&mint), &mint);
+    assert_eq!(h.token_balance(&vault).await, after.real_token_reserves);
+    assert_eq!(h.global_config().await.total_volume, sol_amount);
+}
+
+#[tokio::test]
+async fn sell_returns_sol_and_fees() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let seller = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = h.fee_recipient;
+    h.buy(&seller, &mint, 2 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    let seller_ata = token_account(&seller.pubkey(), &mint);
+    let held = h.token_balance(&seller_ata).await;
+    let token_amount = held / 2;
+    let before = h.bonding_curve(&mint).await;
+    let (sol_out, fee) = before
+        .get_sol_out(token_amount, h.global_config().await.fee_bps)
+        .unwrap();
+    let seller_before = h.lamports(&seller.pubkey()).await;
+    let fee_recipient_before = h.lamports(&fee_recipient).await;
+
+    h.sell(&seller, &mint, token_amount, sol_out).await.unwrap();
+
+    assert_eq!(h.token_balance(&seller_ata).await, held - token_amount);
+    assert_eq!(h.lamports(&seller.pubkey()).await, seller_before + sol_out);
+    assert_eq!(h.lamports(&fee_recipient).await, fee_recipient_before + fee);
+
+    let after = h.bonding_curve(&mint).await;
+    assert_eq!(
+        after.virtual_sol_reserves,
+        before.virtual_sol_reserves - sol_out - fee
+    );
+    assert_eq!(
+        after.virtual_token_reserves,
+        before.virtual_token_reserves + token_amount
+    );
+    assert_eq!(
+        after.real_sol_reserves,
+        before.real_sol_reserves - sol_out - fee
+    );
+    assert_eq!(
+        after.real_token_reserves,
+        before.real_token_reserves + token_amount
+    );
+    assert_eq!(after.tokens_sold, before.tokens_sold - token_amount);
+}
+
+#[tokio::test]
+async fn buy_enforces_slippage() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let buyer = h.new_user(10 * LAMPORTS_PER_SOL).await;
+
+    let curve = h.bonding_curve(&mint).await;
+    let (tokens_out, _) = curve
+        .get_tokens_out(LAMPORTS_PER_SOL, h.global_config().await.fee_bps)
+        .unwrap();
+
+    let result = h.buy(&buyer, &mint, LAMPORTS_PER_SOL, tokens_out + 1).await;
+    assert_openclaw_error(result, OpenClawError::SlippageExceeded);
+    assert_eq!(h.bonding_curve(&mint).await.real_sol_reserves, 0);
+}
+
+#[tokio::test]
+async fn sell_enforces_slippage() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let seller = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&seller, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+
+    let token_amount = h
+        .token_balance(&token_account(&seller.pubkey(), &mint))
+        .await;
+    let curve = h.bonding_curve(&mint).await;
+    let (sol_out, _) = curve
+        .get_sol_out(token_amount, h.global_config().await.fee_bps)
+        .unwrap();
+
+    let result = h.sell(&seller, &mint, token_amount, sol_out + 1).await;
+    assert_openclaw_error(result, OpenClawError::SlippageExceeded);
+}
+
+#[tokio::test]
+async fn buy_exact_tokens_delivers_requested_amount() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let buyer = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = h.fee_recipient;
+
+    let tokens_out = 50_000_000_000_000;
+    let before = h.bonding_curve(&mint).await;
+    let (sol_in, fee) = before.get_sol_in(tokens_out, 100).unwrap();
+    let buyer_before = h.lamports(&buyer.pubkey()).await;
+    let fee_recipient_before = h.lamports(&fee_recipient).await;
+
+    let result = h
+        .buy_exact_tokens(&buyer, &mint, tokens_out, sol_in - 1)
+        .await;
+    assert_openclaw_error(result, OpenClawError::SlippageExceeded);
+
+    h.buy_exact_tokens(&buyer, &mint, tokens_out, sol_in)
+        .await
+        .unwrap();
+
+    let buyer_ata = token_account(&buyer.pubkey(), &mint);
+    assert_eq!(h.token_balance(&buyer_ata).await, tokens_out);
+    let ata_rent = Rent::default().minimum_balance(165);
+    assert_eq!(
+        h.lamports(&buyer.pubkey()).await,
+        buyer_before - sol_in - ata_rent
+    );
+    assert_eq!(h.lamports(&fee_recipient).await, fee_recipient_before + fee);
+
+    let after = h.bonding_curve(&mint).await;
+    assert_eq!(after.real_sol_reserves, sol_in - fee);
+    assert_eq!(after.tokens_sold, tokens_out);
+    assert_eq!(
+        h.lamports(&sol_vault_pda(&mint)).await,
+        after.real_sol_reserves + Rent::default().minimum_balance(0)
+    );
+}
+
+#[tokio::test]
+async fn sell_for_exact_sol_pays_requested_amount() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let seller = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    let fee_recipient = h.fee_recipient;
+    h.buy(&seller, &mint, 2 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+
+    let sol_out = LAMPORTS_PER_SOL / 2;
+    let seller_ata = token_account(&seller.pubkey(), &mint);
+    let held = h.token_balance(&seller_ata).await;
+    let before = h.bonding_curve(&mint).await;
+    let (tokens_in, fee) = before.get_tokens_in(sol_out, 100).unwrap();
+    let seller_before = h.lamports(&seller.pubkey()).await;
+    let fee_recipient_before = h.lamports(&fee_recipient).await;
+
+    let result = h
+        .sell_for_exact_sol(&seller, &mint, sol_out, tokens_in - 1)
+        .await;
+    assert_openclaw_error(result, OpenClawError::SlippageExceeded);
+
+    h.sell_for_exact_sol(&seller, &mint, sol_out, tokens_in)
+        .await
+        .unwrap();
+
+    assert_eq!(h.token_balance(&seller_ata).await, held - tokens_in);
+    assert_eq!(h.lamports(&seller.pubkey()).await, seller_before + sol_out);
+    assert_eq!(h.lamports(&fee_recipient).await, fee_recipient_before + fee);
+
+    let after = h.bonding_curve(&mint).await;
+    assert_eq!(
+        after.real_sol_reserves,
+        before.real_sol_reserves - sol_out - fee
+    );
+    assert_eq!(
+        after.real_token_reserves,
+        before.real_token_reserves + tokens_in
+    );
+}
+
+#[tokio::test]
+async fn exact_trades_reject_zero_amounts() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+
+    let result = h
+        .buy_exact_tokens(&trader, &mint, 0, LAMPORTS_PER_SOL)
+        .await;
+    assert_openclaw_error(result, OpenClawError::InsufficientTokenAmount);
+    let result = h.sell_for_exact_sol(&trader, &mint, 0, u64::MAX).await;
+    assert_openclaw_error(result, OpenClawError::InsufficientSolAmount);
+}
+
+#[tokio::test]
+async fn many_trades_keep_curve_consistent() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let fee_recipient = h.fee_recipient;
+    let fee_recipient_before = h.lamports(&fee_recipient).await;
+    let fee_bps = h.global_config().await.fee_bps;
+
+    let mut traders = Vec::new();
+    for _ in 0..3 {
+        traders.push(h.new_user(5 * LAMPORTS_PER_SOL).await);
+    }
+
+    let mut fees = 0;
+    for round in 0..4u64 {
+        for trader in &traders {
+            let sol_amount = (round + 1) * LAMPORTS_PER_SOL / 10;
+            let curve = h.bonding_curve(&mint).await;
+            let (_, fee) = curve.get_tokens_out(sol_amount, fee_bps).unwrap();
+            h.buy(trader, &mint, sol_amount, 0).await.unwrap();
+            fees += fee;
+        }
+        for trader in &traders {
+            let balance = h
+                .token_balance(&token_account(&trader.pubkey(), &mint))
+                .await;
+            let curve = h.bonding_curve(&mint).await;
+            let (_, fee) = curve.get_sol_out(balance / 3, fee_bps).unwrap();
+            h.sell(trader, &mint, balance / 3, 0).await.unwrap();
+            fees += fee;
+        }
+    }
+
+    let curve = h.bonding_curve(&mint).await;
+    let mut held = 0;
+    for trader in &traders {
+        held += h
+            .token_balance(&token_account(&trader.pubkey(), &mint))
+            .await;
+    }
+    let vault = token_account(&bonding_curve_pda(&mint), &mint);
+    assert_eq!(curve.tokens_sold, held);
+    assert_eq!(h.token_balance(&vault).await, curve.real_token_reserves);
+    assert_eq!(
+        h.lamports(&sol_vault_pda(&mint)).await,
+        curve.real_sol_reserves + Rent::default().minimum_balance(0)
+    );
+    assert_eq!(
+        h.lamports(&fee_recipient).await,
+        fee_recipient_before + fees
+    );
+}
+
+#[tokio::test]
+async fn sell_more_than_held_fails() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let seller = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&seller, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+
+    let balance = h
+        .token_balance(&token_account(&seller.pubkey(), &mint))
+        .await;
+    assert!(h.sell(&seller, &mint, balance + 1, 0).await.is_err());
+}
+
+#[tokio::test]
+async fn sold_out_curve_completes_and_migrates() {
+    let mut h = Harness::initialized(InitializeParams {
+        migration_threshold: 5 * LAMPORTS_PER_SOL,
+        total_supply: 1_000_000_000_000_000,
+        curve_supply: 200_000_000_000_000,
+        allowed_curve_kinds: vec![CurveKind::ConstantProduct],
+        ..default_initialize_params()
+    })
+    .await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+
+    // Raise the threshold past what the curve's own allocation can raise
+    h.update_config(
+        &authority,
+        UpdateConfigParams {
+            migration_threshold: Some(10 * LAMPORTS_PER_SOL),
+            curve_supply: Some(800_000_000_000_000),
+            ..no_config_changes()
+        },
+    )
+    .await
+    .unwrap();
+
+    let buyer = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    let result = h.buy(&buyer, &mint, 8 * LAMPORTS_PER_SOL, 0).await;
+    assert_openclaw_error(result, OpenClawError::InsufficientLiquidity);
+    assert_eq!(
+        h.token_balance(&migration_vault_pda(&mint)).await,
+        800_000_000_000_000
+    );
+
+    // Buying out the curve's allocation completes it short of the threshold, and leaves
+    // the migration allocation whole
+    let curve = h.bonding_curve(&mint).await;
+    let fee_bps = h.global_config().await.fee_bps;
+    let (sol_in, _) = curve
+        .get_sol_in(curve.real_token_reserves, fee_bps)
+        .unwrap();
+    h.buy_exact_tokens(&buyer, &mint, curve.real_token_reserves, sol_in)
+        .await
+        .unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(curve.real_token_reserves, 0);
+    assert!(curve.real_sol_reserves < 10 * LAMPORTS_PER_SOL);
+    assert!(curve.complete);
+    assert_eq!(
+        h.token_balance(&migration_vault_pda(&mint)).await,
+        800_000_000_000_000
+    );
+
+    let result = h.buy(&buyer, &mint, LAMPORTS_PER_SOL, 0).await;
+    assert_openclaw_error(result, OpenClawError::CurveComplete);
+
+    // The pool gets the migration allocation
+    let pool = h.migrate(&mint).await.unwrap();
+    assert_eq!(
+        h.token_balance(&pool.vault_for(&mint)).await,
+        800_000_000_000_000
+    );
+    assert!(h.bonding_curve(&mint).await.migrated);
+}
+
+#[tokio::test]
+async fn trading_stops_after_migration() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(20 * LAMPORTS_PER_SOL).await;
+    h.buy(&trader, &mint, 12 * LAMPORTS_PER_SOL, 0)
+        .await
+        .unwrap();
+    h.migrate(&mint).await.unwrap();
+
+    let result = h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await;
+    assert_openclaw_error(result, OpenClawError::AlreadyMigrated);
+
+    let result = h.sell(&trader, &mint, 1_000, 0).await;
+    assert_openclaw_error(result, OpenClawError::AlreadyMigrated);
+}
@@ -0,0 +1,420 @@
+mod common;
+
+use anchor_lang::prelude::borsh;
+use anchor_lang::{AnchorSerialize, Discriminator};
+use common::*;
+use openclaw::curve::CurveKind;
+use openclaw::error::OpenClawError;
+use openclaw::instructions::{InitializeParams, UpdateConfigParams};
+use openclaw::state::{BondingCurve, GlobalConfig, ACCOUNT_VERSION, MAX_CURVE_KINDS};
+use solana_program_test::tokio;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::rent::Rent;
+use solana_sdk::signature::Signer;
+
+/// `GlobalConfig` as the first release laid it out
+#[derive(AnchorSerialize)]
+struct GlobalConfigV1 {
+    authority: Pubkey,
+    fee_recipient: Pubkey,
+    fee_bps: u16,
+    migration_threshold: u64,
+    initial_virtual_sol: u64,
+    initial_virtual_tokens: u64,
+    total_tokens: u64,
+    total_volume: u64,
+    bump: u8,
+}
+
+const GLOBAL_CONFIG_V1_LEN: usize = 115;
+
+/// `GlobalConfig` at version 2 before it stored launch supplies, which came out of its
+/// reserved space
+#[derive(AnchorSerialize)]
+struct GlobalConfigV2NoSupply {
+    authority: Pubkey,
+    pending_authority: Pubkey,
+    fee_admin: Pubkey,
+    pause_admin: Pubkey,
+    fee_recipient: Pubkey,
+    fee_bps: u16,
+    creator_fee_bps: u16,
+    max_referral_bps: u16,
+    migration_threshold: u64,
+    migration_fee: u64,
+    initial_virtual_sol: u64,
+    initial_virtual_tokens: u64,
+    total_tokens: u64,
+    total_volume: u64,
+    bump: u8,
+    allowed_curve_kinds: Vec<CurveKind>,
+    paused: u8,
+    config_version: u32,
+    version: u8,
+    reserved: [u64; 16],
+}
+
+/// Size `GlobalConfigV2NoSupply` was allocated at, with room for every curve kind
+const GLOBAL_CONFIG_V2_NO_SUPPLY_LEN: usize =
+    8 + 32 * 5 + 2 * 3 + 8 * 6 + 1 + 4 + MAX_CURVE_KINDS * CurveKind::LEN + 1 + 4 + 1 + 8 * 16;
+
+/// `BondingCurve` as the first release laid it out, holding its SOL itself
+#[derive(AnchorSerialize)]
+struct BondingCurveV1 {
+    mint: Pubkey,
+    creator: Pubkey,
+    virtual_sol_reserves: u64,
+    virtual_token_reserves: u64,
+    real_sol_reserves: u64,
+    real_token_reserves: u64,
+    tokens_sold: u64,
+    migrated: bool,
+    created_at: i64,
+    bump: u8,
+}
+
+const BONDING_CURVE_V1_LEN: usize = 122;
+
+/// Raw account data the way Anchor wrote it: discriminator then fields, nothing after
+fn v1_account(discriminator: [u8; 8], fields: impl AnchorSerialize, len: usize) -> Account {
+    let mut data = discriminator.to_vec();
+    fields.serialize(&mut data).unwrap();
+    assert_eq!(data.len(), len);
+    Account {
+        lamports: Rent::default().minimum_balance(len),
+        data,
+        owner: openclaw::ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn global_config_v1(config: &GlobalConfig) -> Account {
+    let fields = GlobalConfigV1 {
+        authority: config.authority,
+        fee_recipient: config.fee_recipient,
+        fee_bps: config.fee_bps,
+        migration_threshold: config.migration_threshold,
+        initial_virtual_sol: config.initial_virtual_sol,
+        initial_virtual_tokens: config.initial_virtual_tokens,
+        total_tokens: config.total_tokens,
+        total_volume: config.total_volume,
+        bump: config.bump,
+    };
+    v1_account(GlobalConfig::DISCRIMINATOR, fields, GLOBAL_CONFIG_V1_LEN)
+}
+
+/// A version 1 curve with the same state as `curve`, its real SOL reserves held in the
+/// curve account on top of rent
+fn bonding_curve_v1(curve: &BondingCurve) -> Account {
+    let fields = BondingCurveV1 {
+        mint: curve.mint,
+        creator: curve.creator,
+        virtual_sol_reserves: curve.virtual_sol_reserves,
+        virtual_token_reserves: curve.virtual_token_reserves,
+        real_sol_reserves: curve.real_sol_reserves,
+        real_token_reserves: curve.real_token_reserves,
+        tokens_sold: curve.tokens_sold,
+        migrated: curve.migrated,
+        created_at: curve.created_at,
+        bump: curve.bump,
+    };
+    let mut account = v1_account(BondingCurve::DISCRIMINATOR, fields, BONDING_CURVE_V1_LEN);
+    account.lamports += curve.real_sol_reserves;
+    account
+}
+
+#[tokio::test]
+async fn new_accounts_are_current() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+
+    assert_eq!(h.global_config().await.version, ACCOUNT_VERSION);
+    assert_eq!(h.bonding_curve(&mint).await.version, ACCOUNT_VERSION);
+    let config_account = h.account(&global_config_pda()).await.unwrap();
+    assert_eq!(config_account.data.len(), GlobalConfig::LEN);
+
+    let authority = h.authority.insecure_clone();
+    let result = h
+        .upgrade_account(&authority, &bonding_curve_pda(&mint))
+        .await;
+    assert_openclaw_error(result, OpenClawError::AccountUpToDate);
+}
+
+#[tokio::test]
+async fn upgrade_v1_global_config() {
+    let mut h = Harness::initialized(InitializeParams {
+        creator_fee_bps: 50,
+        max_referral_bps: 2_000,
+        ..default_initialize_params()
+    })
+    .await;
+    let authority = h.authority.insecure_clone();
+    let before = h.global_config().await;
+    h.set_account(&global_config_pda(), global_config_v1(&before));
+
+    // The old layout is too short for the current program
+    let params = UpdateConfigParams {
+        fee_bps: Some(150),
+        ..no_config_changes()
+    };
+    assert!(h.update_config(&authority, params).await.is_err());
+
+    let intruder = h.new_user(LAMPORTS_PER_SOL).await;
+    let result = h.upgrade_account(&intruder, &global_config_pda()).await;
+    assert_openclaw_error(result, OpenClawError::Unauthorized);
+
+    h.upgrade_account(&authority, &global_config_pda())
+        .await
+        .unwrap();
+
+    let account = h.account(&global_config_pda()).await.unwrap();
+    assert_eq!(account.data.len(), GlobalConfig::LEN);
+    assert!(account.lamports >= Rent::default().minimum_balance(GlobalConfig::LEN));
+    let after = h.global_config().await;
+    assert_eq!(after.version, ACCOUNT_VERSION);
+    assert_eq!(after.authority, before.authority);
+    assert_eq!(after.fee_recipient, before.fee_recipient);
+    assert_eq!(after.fee_bps, before.fee_bps);
+    assert_eq!(after.migration_threshold, before.migration_threshold);
+    assert_eq!(after.initial_virtual_sol, before.initial_virtual_sol);
+    assert_eq!(after.initial_virtual_tokens, before.initial_virtual_tokens);
+    assert_eq!(after.total_tokens, before.total_tokens);
+    assert_eq!(after.bump, before.bump);
+
+    // Fields the first release didn't have start out unset
+    assert_eq!(after.pending_authority, Pubkey::default());
+    assert_eq!(after.fee_admin, Pubkey::default());
+    assert_eq!(after.pause_admin, Pubkey::default());
+    assert_eq!(after.creator_fee_bps, 0);
+    assert_eq!(after.max_referral_bps, 0);
+    assert_eq!(after.migration_fee, 0);
+    assert_eq!(after.paused, 0);
+    assert_eq!(after.allowed_curve_kinds, vec![CurveKind::ConstantProduct]);
+    assert_eq!(after.amm_config, Pubkey::default());
+    assert_eq!(
+        after.launch_supply(),
+        (
+            6,
+            before.initial_virtual_tokens,
+            before.initial_virtual_tokens
+        )
+    );
+
+    // Not the same transaction as the upgrade, so it isn't deduplicated
+    h.refresh_blockhash().await;
+    let result = h.upgrade_account(&authority, &global_config_pda()).await;
+    assert_openclaw_error(result, OpenClawError::AccountUpToDate);
+
+    let params = UpdateConfigParams {
+        fee_bps: Some(150),
+        ..no_config_changes()
+    };
+    h.update_config(&authority, params).await.unwrap();
+    assert_eq!(h.global_config().await.fee_bps, 150);
+
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    assert_eq!(
+        h.mint_account(&mint).await.supply,
+        before.initial_virtual_tokens
+    );
+}
+
+#[tokio::test]
+async fn v2_global_config_without_launch_supply_is_current() {
+    let mut h = Harness::initialized(InitializeParams {
+        creator_fee_bps: 50,
+        ..default_initialize_params()
+    })
+    .await;
+    let authority = h.authority.insecure_clone();
+    let before = h.global_config().await;
+
+    let mut data = GlobalConfig::DISCRIMINATOR.to_vec();
+    GlobalConfigV2NoSupply {
+        authority: before.authority,
+        pending_authority: before.pending_authority,
+        fee_admin: before.fee_admin,
+        pause_admin: before.pause_admin,
+        fee_recipient: before.fee_recipient,
+        fee_bps: before.fee_bps,
+        creator_fee_bps: before.creator_fee_bps,
+        max_referral_bps: before.max_referral_bps,
+        migration_threshold: before.migration_threshold,
+        migration_fee: before.migration_fee,
+        initial_virtual_sol: before.initial_virtual_sol,
+        initial_virtual_tokens: before.initial_virtual_tokens,
+        total_tokens: before.total_tokens,
+        total_volume: before.total_volume,
+        bump: before.bump,
+        allowed_curve_kinds: before.allowed_curve_kinds.clone(),
+        paused: before.paused,
+        config_version: before.config_version,
+        version: before.version,
+        reserved: [0; 16],
+    }
+    .serialize(&mut data)
+    .unwrap();
+    data.resize(GLOBAL_CONFIG_V2_NO_SUPPLY_LEN, 0);
+    assert_eq!(data.len(), GlobalConfig::LEN);
+    h.set_account(
+        &global_config_pda(),
+        Account {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner: openclaw::ID,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+
+    // The launch supply fields took reserved space, so the layout didn't change
+    let result = h.upgrade_account(&authority, &global_config_pda()).await;
+    assert_openclaw_error(result, OpenClawError::AccountUpToDate);
+    let config = h.global_config().await;
+    assert_eq!(config.fee_bps, before.fee_bps);
+    assert_eq!(config.creator_fee_bps, before.creator_fee_bps);
+    assert_eq!(config.allowed_curve_kinds, before.allowed_curve_kinds);
+    assert_eq!(config.version, ACCOUNT_VERSION);
+    assert_eq!(config.amm_config, Pubkey::default());
+    assert_eq!(
+        config.launch_supply(),
+        (
+            6,
+            before.initial_virtual_tokens,
+            before.initial_virtual_tokens
+        )
+    );
+
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    assert_eq!(
+        h.mint_account(&mint).await.supply,
+        before.initial_virtual_tokens
+    );
+
+    // The first update stores the supply the config has been minting
+    let params = UpdateConfigParams {
+        fee_bps: Some(150),
+        ..no_config_changes()
+    };
+    h.update_config(&authority, params).await.unwrap();
+    let config = h.global_config().await;
+    assert_eq!(config.fee_bps, 150);
+    assert_eq!(config.token_decimals, 6);
+    assert_eq!(config.total_supply, before.initial_virtual_tokens);
+    assert_eq!(config.curve_supply, before.initial_virtual_tokens);
+}
+
+#[tokio::test]
+async fn upgrade_v1_bonding_curve() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+    let trader = h.new_user(10 * LAMPORTS_PER_SOL).await;
+    h.buy(&trader, &mint, LAMPORTS_PER_SOL, 0).await.unwrap();
+
+    // Put the curve back the way the first release left it: its SOL in the curve
+    // account, and no SOL vault or price history
+    let before = h.bonding_curve(&mint).await;
+    h.set_account(&bonding_curve_pda(&mint), bonding_curve_v1(&before));
+    h.set_account(&sol_vault_pda(&mint), Account::default());
+    h.set_account(&price_history_pda(&mint), Account::default());
+    assert!(h
+        .buy(&trader, &mint, LAMPORTS_PER_SOL / 2, 0)
+        .await
+        .is_err());
+
+    // The reserves move into the vault, so it has to come along
+    let result = h
+        .upgrade_account(&authority, &bonding_curve_pda(&mint))
+        .await;
+    assert!(result.is_err());
+    let result = h
+        .upgrade_account_with(
+            &authority,
+            &bonding_curve_pda(&mint),
+            Some(sol_vault_pda(&Pubkey::new_unique())),
+        )
+        .await;
+    assert!(result.is_err());
+
+    h.upgrade_bonding_curve(&authority, &mint).await.unwrap();
+
+    let account = h.account(&bonding_curve_pda(&mint)).await.unwrap();
+    assert_eq!(account.data.len(), BondingCurve::LEN);
+    assert_eq!(
+        account.lamports,
+        Rent::default().minimum_balance(BondingCurve::LEN)
+    );
+    assert_eq!(
+        h.account(&sol_vault_pda(&mint)).await.unwrap().lamports,
+        Rent::default().minimum_balance(0) + before.real_sol_reserves
+    );
+
+    let after = h.bonding_curve(&mint).await;
+    assert_eq!(after.version, ACCOUNT_VERSION);
+    assert_eq!(after.mint, before.mint);
+    assert_eq!(after.creator, before.creator);
+    assert_eq!(after.virtual_sol_reserves, before.virtual_sol_reserves);
+    assert_eq!(after.virtual_token_reserves, before.virtual_token_reserves);
+    assert_eq!(after.real_sol_reserves, before.real_sol_reserves);
+    assert_eq!(after.real_token_reserves, before.real_token_reserves);
+    assert_eq!(after.tokens_sold, before.tokens_sold);
+    assert_eq!(after.created_at, before.created_at);
+    assert_eq!(after.bump, before.bump);
+    assert_eq!(after.sol_vault_bump, before.sol_vault_bump);
+    assert_eq!(after.curve_kind, CurveKind::ConstantProduct);
+    assert_eq!(after.creator_fee_bps, 0);
+    assert!(!after.complete);
+    assert!(!after.frozen);
+
+    // Trading picks up where it left off, paying out of the vault
+    h.refresh_blockhash().await;
+    h.buy(&trader, &mint, LAMPORTS_PER_SOL / 2, 0)
+        .await
+        .unwrap();
+    assert!(h.bonding_curve(&mint).await.tokens_sold > before.tokens_sold);
+    assert_eq!(h.price_history(&mint).await.count, 1);
+    let held = h
+        .token_balance(&token_account(&trader.pubkey(), &mint))
+        .await;
+    h.sell(&trader, &mint, held, 0).await.unwrap();
+    let curve = h.bonding_curve(&mint).await;
+    assert_eq!(
+        h.account(&sol_vault_pda(&mint)).await.unwrap().lamports,
+        Rent::default().minimum_balance(0) + curve.real_sol_reserves
+    );
+}
+
+#[tokio::test]
+async fn upgrade_rejects_unknown_layouts() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let creator = h.new_user(LAMPORTS_PER_SOL).await;
+    let mint = h.create_token(&creator).await;
+
+    // A size no release used
+    let mut account = h.account(&bonding_curve_pda(&mint)).await.unwrap();
+    account.data.truncate(BONDING_CURVE_V1_LEN + 8);
+    h.set_account(&bonding_curve_pda(&mint), account);
+    let result = h.upgrade_bonding_curve(&authority, &mint).await;
+    assert_openclaw_error(result, OpenClawError::UnsupportedAccount);
+}
+
+#[tokio::test]
+async fn upgrade_rejects_other_accounts() {
+    let mut h = Harness::initialized(default_initialize_params()).await;
+    let authority = h.authority.insecure_clone();
+    let referrer = h.new_user(LAMPORTS_PER_SOL).await;
+    h.register_referrer(&referrer, 100).await.unwrap();
+
+    let result = h
+        .upgrade_account(&authority, &referrer_pda(&referrer.pubkey()))
+        .await;
+    assert_openclaw_error(result, OpenClawError::UnsupportedAccount);
+}
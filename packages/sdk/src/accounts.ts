@@ -0,0 +1,114 @@
+import { BorshReader } from "./borsh";
+import { BondingCurve, GlobalConfig } from "./types";
+
+/**
+ * Decode a `GlobalConfig` account, discriminator included
+ */
+export function decodeGlobalConfig(data: Buffer): GlobalConfig {
+  const reader = new BorshReader(data).skip(8);
+
+  const authority = reader.publicKey();
+  const pendingAuthority = reader.publicKey();
+  const feeAdmin = reader.publicKey();
+  const pauseAdmin = reader.publicKey();
+  const feeRecipient = reader.publicKey();
+  const feeBps = reader.u16();
+  const creatorFeeBps = reader.u16();
+  const maxReferralBps = reader.u16();
+  const migrationThreshold = reader.u64();
+  const migrationFee = reader.u64();
+  const initialVirtualSol = reader.u64();
+  const initialVirtualTokens = reader.u64();
+  const totalTokens = reader.u64();
+  const totalVolume = reader.u64();
+  const bump = reader.u8();
+  const allowedCurveKinds = Array.from({ length: reader.u32() }, () =>
+    reader.curveKind()
+  );
+  const paused = reader.u8();
+  const configVersion = reader.u32();
+  const version = reader.u8();
+  const tokenDecimals = reader.u8();
+  const totalSupply = reader.u64();
+  const curveSupply = reader.u64();
+  const ammConfig = reader.publicKey();
+
+  return {
+    authority,
+    pendingAuthority,
+    feeAdmin,
+    pauseAdmin,
+    feeRecipient,
+    feeBps,
+    creatorFeeBps,
+    maxReferralBps,
+    migrationThreshold,
+    migrationFee,
+    initialVirtualSol,
+    initialVirtualTokens,
+    totalTokens,
+    totalVolume,
+    bump,
+    allowedCurveKinds,
+    paused,
+    configVersion,
+    version,
+    tokenDecimals,
+    totalSupply,
+    curveSupply,
+    ammConfig,
+  };
+}
+
+/**
+ * Decode a `BondingCurve` account, discriminator included
+ */
+export function decodeBondingCurve(data: Buffer): BondingCurve {
+  const reader = new BorshReader(data).skip(8);
+
+  const mint = reader.publicKey();
+  const creator = reader.publicKey();
+  const virtualSolReserves = reader.u64();
+  const virtualTokenReserves = reader.u64();
+  const realSolReserves = reader.u64();
+  const realTokenReserves = reader.u64();
+  const tokensSold = reader.u64();
+  const migrated = reader.bool();
+  const complete = reader.bool();
+  const createdAt = reader.i64();
+  const bump = reader.u8();
+  const solVaultBump = reader.u8();
+  const curveKind = reader.curveKind();
+  const creatorFeeBps = reader.u16();
+  const creatorFeesAccrued = reader.u64();
+  const creatorFeesClaimed = reader.u64();
+  const frozen = reader.bool();
+  const configVersion = reader.u32();
+  const version = reader.u8();
+  const launchProtection = reader.launchProtection();
+  const migrationTokens = reader.u64();
+
+  return {
+    mint,
+    creator,
+    virtualSolReserves,
+    virtualTokenReserves,
+    realSolReserves,
+    realTokenReserves,
+    tokensSold,
+    migrated,
+    complete,
+    createdAt,
+    bump,
+    solVaultBump,
+    curveKind,
+    creatorFeeBps,
+    creatorFeesAccrued,
+    creatorFeesClaimed,
+    frozen,
+    configVersion,
+    version,
+    launchProtection,
+    migrationTokens,
+  };
+}
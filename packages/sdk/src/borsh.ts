@@ -0,0 +1,85 @@
+import { PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { CurveKind, LaunchProtection } from "./types";
+
+/**
+ * Sequential reader over Borsh-encoded accounts and events. Offsets can't be fixed because
+ * `GlobalConfig.allowed_curve_kinds` is a `Vec` and `CurveKind` variants and `Option`s
+ * differ in size.
+ */
+export class BorshReader {
+  private offset = 0;
+
+  constructor(private readonly data: Buffer) {}
+
+  skip(bytes: number): this {
+    this.offset += bytes;
+    return this;
+  }
+
+  u8(): number {
+    const value = this.data.readUInt8(this.offset);
+    this.offset += 1;
+    return value;
+  }
+
+  u16(): number {
+    const value = this.data.readUInt16LE(this.offset);
+    this.offset += 2;
+    return value;
+  }
+
+  u32(): number {
+    const value = this.data.readUInt32LE(this.offset);
+    this.offset += 4;
+    return value;
+  }
+
+  u64(): BN {
+    const value = new BN(this.data.readBigUInt64LE(this.offset).toString());
+    this.offset += 8;
+    return value;
+  }
+
+  i64(): BN {
+    const value = new BN(this.data.readBigInt64LE(this.offset).toString());
+    this.offset += 8;
+    return value;
+  }
+
+  bool(): boolean {
+    return this.u8() !== 0;
+  }
+
+  option<T>(read: () => T): T | null {
+    return this.bool() ? read() : null;
+  }
+
+  publicKey(): PublicKey {
+    const value = new PublicKey(this.data.subarray(this.offset, this.offset + 32));
+    this.offset += 32;
+    return value;
+  }
+
+  curveKind(): CurveKind {
+    const variant = this.u8();
+    switch (variant) {
+      case 0:
+        return { kind: "constantProduct" };
+      case 1:
+        return { kind: "linear", startPrice: this.u64(), endPrice: this.u64() };
+      case 2:
+        return { kind: "exponential", startPrice: this.u64(), doublings: this.u8() };
+      default:
+        throw new Error(`Unknown curve kind ${variant}`);
+    }
+  }
+
+  launchProtection(): LaunchProtection {
+    return {
+      windowSecs: this.u64(),
+      maxSolPerTx: this.u64(),
+      maxSolPerWallet: this.u64(),
+    };
+  }
+}
@@ -1,4 +1,5 @@
 import {
+  AccountMeta,
   Connection,
   PublicKey,
   Transaction,
@@ -11,29 +12,51 @@ import {
   TOKEN_PROGRAM_ID,
   ASSOCIATED_TOKEN_PROGRAM_ID,
   getAssociatedTokenAddressSync,
-  createAssociatedTokenAccountInstruction,
 } from "@solana/spl-token";
 import BN from "bn.js";
+import { BorshCoder, Idl } from "@coral-xyz/anchor";
+import IDL from "./idl/openclaw.json";
 import {
   OPENCLAW_PROGRAM_ID,
   DEFAULT_FEE_BPS,
+  DEFAULT_CREATOR_FEE_BPS,
+  DEFAULT_MAX_REFERRAL_BPS,
   DEFAULT_MIGRATION_THRESHOLD,
+  DEFAULT_MIGRATION_FEE,
   DEFAULT_INITIAL_VIRTUAL_SOL,
   DEFAULT_INITIAL_VIRTUAL_TOKENS,
+  TOKEN_DECIMALS,
+  TOKEN_METADATA_PROGRAM_ID,
 } from "./constants";
-import { findGlobalConfigPDA, findBondingCurvePDA } from "./pda";
+import {
+  findGlobalConfigPDA,
+  findBondingCurvePDA,
+  findMetadataPDA,
+  findMigrationVaultPDA,
+  findPositionPDA,
+  findPriceHistoryPDA,
+  findSolVaultPDA,
+} from "./pda";
+import { decodeBondingCurve, decodeGlobalConfig } from "./accounts";
 import {
   BondingCurve,
+  CurveKind,
   GlobalConfig,
   CreateTokenParams,
   InitializeParams,
   TradeResult,
 } from "./types";
-import { calculateTokensOut, calculateSolOut } from "./utils";
+import {
+  calculateTokensOut,
+  calculateSolOut,
+  calculatePriceImpact,
+  totalFeeBps,
+} from "./utils";
 
 export class OpenClawClient {
   public connection: Connection;
   public programId: PublicKey;
+  private coder = new BorshCoder(IDL as Idl);
 
   constructor(connection: Connection, programId?: PublicKey) {
     this.connection = connection;
@@ -48,9 +71,7 @@ export class OpenClawClient {
 
     if (!accountInfo) return null;
 
-    // Parse account data (simplified - in production use Anchor's IDL)
-    const data = accountInfo.data;
-    return this.parseGlobalConfig(data);
+    return decodeGlobalConfig(accountInfo.data);
   }
 
   async getBondingCurve(mint: PublicKey): Promise<BondingCurve | null> {
@@ -59,7 +80,14 @@ export class OpenClawClient {
 
     if (!accountInfo) return null;
 
-    return this.parseBondingCurve(accountInfo.data);
+    return decodeBondingCurve(accountInfo.data);
+  }
+
+  /** Token program owning `mint`, SPL Token or Token-2022 */
+  async getTokenProgram(mint: PublicKey): Promise<PublicKey> {
+    const accountInfo = await this.connection.getAccountInfo(mint);
+    if (!accountInfo) throw new Error("Mint not found");
+    return accountInfo.owner;
   }
 
   // === Instructions ===
@@ -67,32 +95,34 @@ export class OpenClawClient {
   createInitializeInstruction(
     authority: PublicKey,
     feeRecipient: PublicKey,
-    params?: Partial<InitializeParams>
+    params: Partial<InitializeParams> & Pick<InitializeParams, "ammConfig">
   ): TransactionInstruction {
     const [configPDA] = findGlobalConfigPDA(this.programId);
 
-    const feeBps = params?.feeBps ?? DEFAULT_FEE_BPS;
-    const migrationThreshold =
-      params?.migrationThreshold ?? new BN(DEFAULT_MIGRATION_THRESHOLD.toString());
-    const initialVirtualSol =
-      params?.initialVirtualSol ?? new BN(DEFAULT_INITIAL_VIRTUAL_SOL.toString());
     const initialVirtualTokens =
-      params?.initialVirtualTokens ?? new BN(DEFAULT_INITIAL_VIRTUAL_TOKENS.toString());
-
-    // Instruction data layout:
-    // 0: instruction discriminator (8 bytes)
-    // 8: fee_bps (2 bytes)
-    // 10: migration_threshold (8 bytes)
-    // 18: initial_virtual_sol (8 bytes)
-    // 26: initial_virtual_tokens (8 bytes)
-
-    const data = Buffer.alloc(34);
-    // Discriminator for "initialize" (first 8 bytes of sha256("global:initialize"))
-    data.write("7afaf34e42a03d97", 0, "hex");
-    data.writeUInt16LE(feeBps, 8);
-    data.writeBigUInt64LE(BigInt(migrationThreshold.toString()), 10);
-    data.writeBigUInt64LE(BigInt(initialVirtualSol.toString()), 18);
-    data.writeBigUInt64LE(BigInt(initialVirtualTokens.toString()), 26);
+      params.initialVirtualTokens ?? new BN(DEFAULT_INITIAL_VIRTUAL_TOKENS.toString());
+    const totalSupply = params.totalSupply ?? initialVirtualTokens;
+
+    const data = this.coder.instruction.encode("initialize", {
+      params: {
+        feeBps: params.feeBps ?? DEFAULT_FEE_BPS,
+        creatorFeeBps: params.creatorFeeBps ?? DEFAULT_CREATOR_FEE_BPS,
+        maxReferralBps: params.maxReferralBps ?? DEFAULT_MAX_REFERRAL_BPS,
+        migrationThreshold:
+          params.migrationThreshold ?? new BN(DEFAULT_MIGRATION_THRESHOLD.toString()),
+        migrationFee: params.migrationFee ?? new BN(DEFAULT_MIGRATION_FEE.toString()),
+        initialVirtualSol:
+          params.initialVirtualSol ?? new BN(DEFAULT_INITIAL_VIRTUAL_SOL.toString()),
+        initialVirtualTokens,
+        tokenDecimals: params.tokenDecimals ?? TOKEN_DECIMALS,
+        totalSupply,
+        curveSupply: params.curveSupply ?? totalSupply,
+        ammConfig: params.ammConfig,
+        allowedCurveKinds: (
+          params.allowedCurveKinds ?? [{ kind: "constantProduct" }]
+        ).map(toIdlCurveKind),
+      },
+    });
 
     return new TransactionInstruction({
       keys: [
@@ -106,79 +136,107 @@ export class OpenClawClient {
     });
   }
 
+  /**
+   * Create a token under `tokenProgram`. An initial buy (`params.initialBuySol`) is
+   * paid to `feeRecipient`, which must then be the config's fee recipient.
+   */
   createTokenInstruction(
     creator: PublicKey,
     mint: Keypair,
-    params: CreateTokenParams
+    feeRecipient: PublicKey,
+    params: CreateTokenParams,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
   ): TransactionInstruction[] {
     const [configPDA] = findGlobalConfigPDA(this.programId);
     const [curvePDA] = findBondingCurvePDA(mint.publicKey, this.programId);
+    const [priceHistory] = findPriceHistoryPDA(mint.publicKey, this.programId);
+    const [solVault] = findSolVaultPDA(mint.publicKey, this.programId);
+    const [migrationVault] = findMigrationVaultPDA(mint.publicKey, this.programId);
     const curveTokenVault = getAssociatedTokenAddressSync(
       mint.publicKey,
       curvePDA,
-      true
+      true,
+      tokenProgram
     );
+    // Token-2022 mints keep their metadata in the mint, SPL Token ones need Metaplex
+    const usesMetaplex = tokenProgram.equals(TOKEN_PROGRAM_ID);
+    const hasInitialBuy = params.initialBuySol !== undefined;
+
+    const data = this.coder.instruction.encode("createToken", {
+      params: {
+        name: params.name,
+        symbol: params.symbol,
+        uri: params.uri,
+        curveKind: toIdlCurveKind(params.curveKind ?? { kind: "constantProduct" }),
+        launchProtection: params.launchProtection ?? null,
+        initialBuySol: params.initialBuySol ?? null,
+        minTokensOut: params.minTokensOut ?? new BN(0),
+      },
+    });
 
-    const instructions: TransactionInstruction[] = [];
-
-    // Instruction data (simplified)
-    const nameBuffer = Buffer.from(params.name);
-    const symbolBuffer = Buffer.from(params.symbol);
-    const uriBuffer = Buffer.from(params.uri);
-
-    const data = Buffer.alloc(
-      8 + 4 + nameBuffer.length + 4 + symbolBuffer.length + 4 + uriBuffer.length
-    );
-    let offset = 0;
-
-    // Discriminator
-    data.write("181ec828051c0777", offset, "hex");
-    offset += 8;
-
-    // Name (length-prefixed string)
-    data.writeUInt32LE(nameBuffer.length, offset);
-    offset += 4;
-    nameBuffer.copy(data, offset);
-    offset += nameBuffer.length;
-
-    // Symbol
-    data.writeUInt32LE(symbolBuffer.length, offset);
-    offset += 4;
-    symbolBuffer.copy(data, offset);
-    offset += symbolBuffer.length;
-
-    // URI
-    data.writeUInt32LE(uriBuffer.length, offset);
-    offset += 4;
-    uriBuffer.copy(data, offset);
-
-    instructions.push(
+    return [
       new TransactionInstruction({
         keys: [
           { pubkey: creator, isSigner: true, isWritable: true },
           { pubkey: configPDA, isSigner: false, isWritable: true },
           { pubkey: mint.publicKey, isSigner: true, isWritable: true },
           { pubkey: curvePDA, isSigner: false, isWritable: true },
+          { pubkey: priceHistory, isSigner: false, isWritable: true },
+          { pubkey: solVault, isSigner: false, isWritable: true },
           { pubkey: curveTokenVault, isSigner: false, isWritable: true },
-          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+          { pubkey: migrationVault, isSigner: false, isWritable: true },
+          usesMetaplex
+            ? {
+                pubkey: findMetadataPDA(mint.publicKey)[0],
+                isSigner: false,
+                isWritable: true,
+              }
+            : this.noAccount(),
+          { pubkey: tokenProgram, isSigner: false, isWritable: false },
           {
             pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
             isSigner: false,
             isWritable: false,
           },
+          usesMetaplex
+            ? {
+                pubkey: TOKEN_METADATA_PROGRAM_ID,
+                isSigner: false,
+                isWritable: false,
+              }
+            : this.noAccount(),
           { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
           {
             pubkey: new PublicKey("SysvarRent111111111111111111111111111111111"),
             isSigner: false,
             isWritable: false,
           },
+          // Only needed for an initial buy
+          ...(hasInitialBuy
+            ? [
+                { pubkey: feeRecipient, isSigner: false, isWritable: true },
+                {
+                  pubkey: getAssociatedTokenAddressSync(
+                    mint.publicKey,
+                    creator,
+                    false,
+                    tokenProgram
+                  ),
+                  isSigner: false,
+                  isWritable: true,
+                },
+                {
+                  pubkey: findPositionPDA(mint.publicKey, creator, this.programId)[0],
+                  isSigner: false,
+                  isWritable: true,
+                },
+              ]
+            : [this.noAccount(), this.noAccount(), this.noAccount()]),
         ],
         programId: this.programId,
         data,
-      })
-    );
-
-    return instructions;
+      }),
+    ];
   }
 
   createBuyInstruction(
@@ -186,53 +244,58 @@ export class OpenClawClient {
     mint: PublicKey,
     feeRecipient: PublicKey,
     solAmount: BN,
-    minTokensOut: BN
-  ): TransactionInstruction[] {
+    minTokensOut: BN,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
+  ): TransactionInstruction {
     const [configPDA] = findGlobalConfigPDA(this.programId);
     const [curvePDA] = findBondingCurvePDA(mint, this.programId);
-    const curveTokenVault = getAssociatedTokenAddressSync(mint, curvePDA, true);
-    const buyerTokenAccount = getAssociatedTokenAddressSync(mint, buyer);
-
-    const instructions: TransactionInstruction[] = [];
-
-    // Create ATA if needed
-    instructions.push(
-      createAssociatedTokenAccountInstruction(
-        buyer,
-        buyerTokenAccount,
-        buyer,
-        mint
-      )
+    const [solVault] = findSolVaultPDA(mint, this.programId);
+    const [priceHistory] = findPriceHistoryPDA(mint, this.programId);
+    const curveTokenVault = getAssociatedTokenAddressSync(
+      mint,
+      curvePDA,
+      true,
+      tokenProgram
     );
-
-    const data = Buffer.alloc(24);
-    data.write("66063d1201daebea", 0, "hex"); // buy discriminator
-    data.writeBigUInt64LE(BigInt(solAmount.toString()), 8);
-    data.writeBigUInt64LE(BigInt(minTokensOut.toString()), 16);
-
-    instructions.push(
-      new TransactionInstruction({
-        keys: [
-          { pubkey: buyer, isSigner: true, isWritable: true },
-          { pubkey: configPDA, isSigner: false, isWritable: true },
-          { pubkey: curvePDA, isSigner: false, isWritable: true },
-          { pubkey: curveTokenVault, isSigner: false, isWritable: true },
-          { pubkey: buyerTokenAccount, isSigner: false, isWritable: true },
-          { pubkey: feeRecipient, isSigner: false, isWritable: true },
-          { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
-          {
-            pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
-            isSigner: false,
-            isWritable: false,
-          },
-          { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
-        ],
-        programId: this.programId,
-        data,
-      })
+    // Created by the program if needed
+    const buyerTokenAccount = getAssociatedTokenAddressSync(
+      mint,
+      buyer,
+      false,
+      tokenProgram
     );
 
-    return instructions;
+    const data = this.coder.instruction.encode("buy", {
+      solAmount,
+      minTokensOut,
+    });
+
+    return new TransactionInstruction({
+      keys: [
+        { pubkey: buyer, isSigner: true, isWritable: true },
+        { pubkey: configPDA, isSigner: false, isWritable: true },
+        { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: solVault, isSigner: false, isWritable: true },
+        { pubkey: curveTokenVault, isSigner: false, isWritable: true },
+        { pubkey: buyerTokenAccount, isSigner: false, isWritable: true },
+        { pubkey: feeRecipient, isSigner: false, isWritable: true },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
+        {
+          pubkey: ASSOCIATED_TOKEN_PROGRAM_ID,
+          isSigner: false,
+          isWritable: false,
+        },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        // No referrer, referrer wallet or position
+        this.noAccount(),
+        this.noAccount(),
+        this.noAccount(),
+        { pubkey: priceHistory, isSigner: false, isWritable: true },
+      ],
+      programId: this.programId,
+      data,
+    });
   }
 
   createSellInstruction(
@@ -240,34 +303,61 @@ export class OpenClawClient {
     mint: PublicKey,
     feeRecipient: PublicKey,
     tokenAmount: BN,
-    minSolOut: BN
+    minSolOut: BN,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
   ): TransactionInstruction {
     const [configPDA] = findGlobalConfigPDA(this.programId);
     const [curvePDA] = findBondingCurvePDA(mint, this.programId);
-    const curveTokenVault = getAssociatedTokenAddressSync(mint, curvePDA, true);
-    const sellerTokenAccount = getAssociatedTokenAddressSync(mint, seller);
+    const [solVault] = findSolVaultPDA(mint, this.programId);
+    const [priceHistory] = findPriceHistoryPDA(mint, this.programId);
+    const curveTokenVault = getAssociatedTokenAddressSync(
+      mint,
+      curvePDA,
+      true,
+      tokenProgram
+    );
+    const sellerTokenAccount = getAssociatedTokenAddressSync(
+      mint,
+      seller,
+      false,
+      tokenProgram
+    );
 
-    const data = Buffer.alloc(24);
-    data.write("b44d7b4f5e4e7a9c", 0, "hex"); // sell discriminator
-    data.writeBigUInt64LE(BigInt(tokenAmount.toString()), 8);
-    data.writeBigUInt64LE(BigInt(minSolOut.toString()), 16);
+    const data = this.coder.instruction.encode("sell", {
+      tokenAmount,
+      minSolOut,
+    });
 
     return new TransactionInstruction({
       keys: [
         { pubkey: seller, isSigner: true, isWritable: true },
         { pubkey: configPDA, isSigner: false, isWritable: true },
         { pubkey: curvePDA, isSigner: false, isWritable: true },
+        { pubkey: mint, isSigner: false, isWritable: false },
+        { pubkey: solVault, isSigner: false, isWritable: true },
         { pubkey: curveTokenVault, isSigner: false, isWritable: true },
         { pubkey: sellerTokenAccount, isSigner: false, isWritable: true },
         { pubkey: feeRecipient, isSigner: false, isWritable: true },
-        { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+        { pubkey: tokenProgram, isSigner: false, isWritable: false },
         { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        // No referrer, referrer wallet or position
+        this.noAccount(),
+        this.noAccount(),
+        this.noAccount(),
+        { pubkey: priceHistory, isSigner: false, isWritable: true },
       ],
       programId: this.programId,
       data,
     });
   }
 
+  /**
+   * An optional account left out, which Anchor takes as the program ID in its slot
+   */
+  private noAccount(): AccountMeta {
+    return { pubkey: this.programId, isSigner: false, isWritable: false };
+  }
+
   // === Quote Functions ===
 
   async quoteBuy(
@@ -281,20 +371,20 @@ export class OpenClawClient {
       throw new Error("Token or config not found");
     }
 
+    const feeBps = totalFeeBps(config.feeBps, curve);
     const { tokensOut, fee } = calculateTokensOut(
+      curve,
       solAmount,
-      curve.virtualSolReserves,
-      curve.virtualTokenReserves,
-      config.feeBps
+      feeBps,
+      config.migrationThreshold
+    );
+    const priceImpact = calculatePriceImpact(
+      curve,
+      solAmount,
+      feeBps,
+      config.migrationThreshold,
+      true
     );
-
-    // Calculate price impact
-    const priceBefore =
-      curve.virtualSolReserves.toNumber() / curve.virtualTokenReserves.toNumber();
-    const newSolReserves = curve.virtualSolReserves.add(solAmount.sub(fee));
-    const newTokenReserves = curve.virtualTokenReserves.sub(tokensOut);
-    const priceAfter = newSolReserves.toNumber() / newTokenReserves.toNumber();
-    const priceImpact = ((priceAfter - priceBefore) / priceBefore) * 100;
 
     return { tokensOut, fee, priceImpact };
   }
@@ -310,125 +400,37 @@ export class OpenClawClient {
       throw new Error("Token or config not found");
     }
 
-    const { solOut, fee } = calculateSolOut(
+    const feeBps = totalFeeBps(config.feeBps, curve);
+    const { solOut, fee } = calculateSolOut(curve, tokenAmount, feeBps);
+    const priceImpact = calculatePriceImpact(
+      curve,
       tokenAmount,
-      curve.virtualSolReserves,
-      curve.virtualTokenReserves,
-      config.feeBps
+      feeBps,
+      config.migrationThreshold,
+      false
     );
 
-    // Calculate price impact
-    const priceBefore =
-      curve.virtualSolReserves.toNumber() / curve.virtualTokenReserves.toNumber();
-    const newSolReserves = curve.virtualSolReserves.sub(solOut.add(fee));
-    const newTokenReserves = curve.virtualTokenReserves.add(tokenAmount);
-    const priceAfter = newSolReserves.toNumber() / newTokenReserves.toNumber();
-    const priceImpact = ((priceBefore - priceAfter) / priceBefore) * 100;
-
     return { solOut, fee, priceImpact };
   }
+}
 
-  // === Parsing Helpers ===
-
-  private parseGlobalConfig(data: Buffer): GlobalConfig {
-    // Skip 8-byte discriminator
-    let offset = 8;
-
-    const authority = new PublicKey(data.subarray(offset, offset + 32));
-    offset += 32;
-
-    const feeRecipient = new PublicKey(data.subarray(offset, offset + 32));
-    offset += 32;
-
-    const feeBps = data.readUInt16LE(offset);
-    offset += 2;
-
-    const migrationThreshold = new BN(
-      data.readBigUInt64LE(offset).toString()
-    );
-    offset += 8;
-
-    const initialVirtualSol = new BN(
-      data.readBigUInt64LE(offset).toString()
-    );
-    offset += 8;
-
-    const initialVirtualTokens = new BN(
-      data.readBigUInt64LE(offset).toString()
-    );
-    offset += 8;
-
-    const totalTokens = new BN(data.readBigUInt64LE(offset).toString());
-    offset += 8;
-
-    const totalVolume = new BN(data.readBigUInt64LE(offset).toString());
-    offset += 8;
-
-    const bump = data.readUInt8(offset);
-
-    return {
-      authority,
-      feeRecipient,
-      feeBps,
-      migrationThreshold,
-      initialVirtualSol,
-      initialVirtualTokens,
-      totalTokens,
-      totalVolume,
-      bump,
-    };
-  }
-
-  private parseBondingCurve(data: Buffer): BondingCurve {
-    // Skip 8-byte discriminator
-    let offset = 8;
-
-    const mint = new PublicKey(data.subarray(offset, offset + 32));
-    offset += 32;
-
-    const creator = new PublicKey(data.subarray(offset, offset + 32));
-    offset += 32;
-
-    const virtualSolReserves = new BN(
-      data.readBigUInt64LE(offset).toString()
-    );
-    offset += 8;
-
-    const virtualTokenReserves = new BN(
-      data.readBigUInt64LE(offset).toString()
-    );
-    offset += 8;
-
-    const realSolReserves = new BN(data.readBigUInt64LE(offset).toString());
-    offset += 8;
-
-    const realTokenReserves = new BN(
-      data.readBigUInt64LE(offset).toString()
-    );
-    offset += 8;
-
-    const tokensSold = new BN(data.readBigUInt64LE(offset).toString());
-    offset += 8;
-
-    const migrated = data.readUInt8(offset) === 1;
-    offset += 1;
-
-    const createdAt = new BN(data.readBigInt64LE(offset).toString());
-    offset += 8;
-
-    const bump = data.readUInt8(offset);
-
-    return {
-      mint,
-      creator,
-      virtualSolReserves,
-      virtualTokenReserves,
-      realSolReserves,
-      realTokenReserves,
-      tokensSold,
-      migrated,
-      createdAt,
-      bump,
-    };
+/**
+ * The Anchor coder's form of a `CurveKind`, keyed by variant
+ */
+function toIdlCurveKind(curveKind: CurveKind): object {
+  switch (curveKind.kind) {
+    case "constantProduct":
+      return { constantProduct: {} };
+    case "linear":
+      return {
+        linear: { startPrice: curveKind.startPrice, endPrice: curveKind.endPrice },
+      };
+    case "exponential":
+      return {
+        exponential: {
+          startPrice: curveKind.startPrice,
+          doublings: curveKind.doublings,
+        },
+      };
   }
 }
@@ -2,7 +2,7 @@ import { PublicKey } from "@solana/web3.js";
 
 // Program ID (update after deployment)
 export const OPENCLAW_PROGRAM_ID = new PublicKey(
-  process.env.OPENCLAW_PROGRAM_ID || "CLAWxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx"
+  process.env.OPENCLAW_PROGRAM_ID || "CLAWMASi45ub7Qe4ZE36UT5G6cU4ud8Fhhe4deS4F3cw"
 );
 
 // Seeds
@@ -10,10 +10,29 @@ export const GLOBAL_CONFIG_SEED = Buffer.from("global_config");
 export const BONDING_CURVE_SEED = Buffer.from("bonding_curve");
 export const CURVE_SOL_VAULT_SEED = Buffer.from("curve_sol_vault");
 export const CURVE_TOKEN_VAULT_SEED = Buffer.from("curve_token_vault");
+export const MIGRATION_VAULT_SEED = Buffer.from("migration_vault");
+export const METADATA_SEED = Buffer.from("metadata");
+export const REFERRER_SEED = Buffer.from("referrer");
+export const POSITION_SEED = Buffer.from("position");
+export const PRICE_HISTORY_SEED = Buffer.from("price_history");
+
+// Metaplex token metadata program, which owns SPL Token mints' metadata
+export const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+// `GlobalConfig.paused` flags
+export const PAUSE_CREATION = 1 << 0;
+export const PAUSE_TRADING = 1 << 1;
+export const PAUSE_MIGRATION = 1 << 2;
+export const PAUSE_ALL = PAUSE_CREATION | PAUSE_TRADING | PAUSE_MIGRATION;
 
 // Default configuration
 export const DEFAULT_FEE_BPS = 100; // 1%
+export const DEFAULT_CREATOR_FEE_BPS = 0;
+export const DEFAULT_MAX_REFERRAL_BPS = 2_000; // 20% of the platform fee
 export const DEFAULT_MIGRATION_THRESHOLD = BigInt(85_000_000_000); // 85 SOL
+export const DEFAULT_MIGRATION_FEE = BigInt(100_000_000); // 0.1 SOL
 export const DEFAULT_INITIAL_VIRTUAL_SOL = BigInt(30_000_000_000); // 30 SOL
 export const DEFAULT_INITIAL_VIRTUAL_TOKENS = BigInt(1_000_000_000_000_000); // 1B with 6 decimals
 
@@ -0,0 +1,62 @@
+import { BorshReader } from "./borsh";
+import { TradeEvent } from "./types";
+
+/** First 8 bytes of sha256("event:Trade") */
+const TRADE_EVENT_DISCRIMINATOR = Buffer.from("18feda98fd2b1251", "hex");
+
+const PROGRAM_DATA = "Program data: ";
+
+/**
+ * Decode a `Trade` event, discriminator included. Returns null for any other event.
+ */
+export function decodeTradeEvent(data: Buffer): TradeEvent | null {
+  if (!data.subarray(0, 8).equals(TRADE_EVENT_DISCRIMINATOR)) return null;
+
+  const reader = new BorshReader(data).skip(8);
+
+  const mint = reader.publicKey();
+  const user = reader.publicKey();
+  const isBuy = reader.bool();
+  const solAmount = reader.u64();
+  const tokenAmount = reader.u64();
+  const fee = reader.u64();
+  const creatorFee = reader.u64();
+  const referrer = reader.option(() => reader.publicKey());
+  const referralFee = reader.u64();
+  const virtualSolReserves = reader.u64();
+  const virtualTokenReserves = reader.u64();
+  const realSolReserves = reader.u64();
+  const realTokenReserves = reader.u64();
+  const slot = reader.u64();
+  const timestamp = reader.i64();
+
+  return {
+    mint,
+    user,
+    isBuy,
+    solAmount,
+    tokenAmount,
+    fee,
+    creatorFee,
+    referrer,
+    referralFee,
+    virtualSolReserves,
+    virtualTokenReserves,
+    realSolReserves,
+    realTokenReserves,
+    slot,
+    timestamp,
+  };
+}
+
+/**
+ * `Trade` events emitted in a transaction's logs, in order
+ */
+export function parseTradeEvents(logs: string[]): TradeEvent[] {
+  return logs
+    .filter((log) => log.startsWith(PROGRAM_DATA))
+    .map((log) =>
+      decodeTradeEvent(Buffer.from(log.slice(PROGRAM_DATA.length), "base64"))
+    )
+    .filter((event): event is TradeEvent => event !== null);
+}
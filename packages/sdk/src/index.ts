@@ -1,5 +1,7 @@
 export * from "./client";
 export * from "./types";
+export * from "./accounts";
+export * from "./events";
 export * from "./constants";
 export * from "./utils";
 export * from "./pda";
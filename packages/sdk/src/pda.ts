@@ -3,6 +3,13 @@ import {
   OPENCLAW_PROGRAM_ID,
   GLOBAL_CONFIG_SEED,
   BONDING_CURVE_SEED,
+  CURVE_SOL_VAULT_SEED,
+  MIGRATION_VAULT_SEED,
+  METADATA_SEED,
+  REFERRER_SEED,
+  POSITION_SEED,
+  PRICE_HISTORY_SEED,
+  TOKEN_METADATA_PROGRAM_ID,
 } from "./constants";
 
 export function findGlobalConfigPDA(
@@ -21,6 +28,65 @@ export function findBondingCurvePDA(
   );
 }
 
+export function findSolVaultPDA(
+  mint: PublicKey,
+  programId: PublicKey = OPENCLAW_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [CURVE_SOL_VAULT_SEED, mint.toBuffer()],
+    programId
+  );
+}
+
+export function findMigrationVaultPDA(
+  mint: PublicKey,
+  programId: PublicKey = OPENCLAW_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [MIGRATION_VAULT_SEED, mint.toBuffer()],
+    programId
+  );
+}
+
+export function findPriceHistoryPDA(
+  mint: PublicKey,
+  programId: PublicKey = OPENCLAW_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [PRICE_HISTORY_SEED, mint.toBuffer()],
+    programId
+  );
+}
+
+export function findPositionPDA(
+  mint: PublicKey,
+  owner: PublicKey,
+  programId: PublicKey = OPENCLAW_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [POSITION_SEED, mint.toBuffer(), owner.toBuffer()],
+    programId
+  );
+}
+
+export function findReferrerPDA(
+  wallet: PublicKey,
+  programId: PublicKey = OPENCLAW_PROGRAM_ID
+): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [REFERRER_SEED, wallet.toBuffer()],
+    programId
+  );
+}
+
+/** Metaplex metadata of an SPL Token mint */
+export function findMetadataPDA(mint: PublicKey): [PublicKey, number] {
+  return PublicKey.findProgramAddressSync(
+    [METADATA_SEED, TOKEN_METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+    TOKEN_METADATA_PROGRAM_ID
+  );
+}
+
 export function findCurveTokenVaultPDA(
   mint: PublicKey,
   bondingCurve: PublicKey
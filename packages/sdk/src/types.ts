@@ -1,16 +1,44 @@
 import { PublicKey } from "@solana/web3.js";
 import BN from "bn.js";
 
+/** Price dynamics of a bonding curve, mirroring the program's `CurveKind` */
+export type CurveKind =
+  | { kind: "constantProduct" }
+  | { kind: "linear"; startPrice: BN; endPrice: BN }
+  | { kind: "exponential"; startPrice: BN; doublings: number };
+
 export interface GlobalConfig {
   authority: PublicKey;
+  pendingAuthority: PublicKey;
+  feeAdmin: PublicKey;
+  pauseAdmin: PublicKey;
   feeRecipient: PublicKey;
   feeBps: number;
+  creatorFeeBps: number;
+  maxReferralBps: number;
   migrationThreshold: BN;
+  migrationFee: BN;
   initialVirtualSol: BN;
   initialVirtualTokens: BN;
   totalTokens: BN;
   totalVolume: BN;
   bump: number;
+  allowedCurveKinds: CurveKind[];
+  /** `PAUSE_*` flags */
+  paused: number;
+  configVersion: number;
+  version: number;
+  /** Zero on configs written before launch supplies, see `launchSupply` */
+  tokenDecimals: number;
+  totalSupply: BN;
+  curveSupply: BN;
+  ammConfig: PublicKey;
+}
+
+export interface LaunchProtection {
+  windowSecs: BN;
+  maxSolPerTx: BN;
+  maxSolPerWallet: BN;
 }
 
 export interface BondingCurve {
@@ -22,21 +50,72 @@ export interface BondingCurve {
   realTokenReserves: BN;
   tokensSold: BN;
   migrated: boolean;
+  complete: boolean;
   createdAt: BN;
   bump: number;
+  solVaultBump: number;
+  curveKind: CurveKind;
+  creatorFeeBps: number;
+  creatorFeesAccrued: BN;
+  creatorFeesClaimed: BN;
+  frozen: boolean;
+  configVersion: number;
+  version: number;
+  launchProtection: LaunchProtection;
+  migrationTokens: BN;
+}
+
+/** Emitted on every buy and sell against a bonding curve */
+export interface TradeEvent {
+  mint: PublicKey;
+  user: PublicKey;
+  isBuy: boolean;
+  /** Paid by the buyer (fees included) or received by the seller (fees excluded) */
+  solAmount: BN;
+  tokenAmount: BN;
+  /** Platform fee, referral share included */
+  fee: BN;
+  creatorFee: BN;
+  referrer: PublicKey | null;
+  referralFee: BN;
+  /** Reserves after the trade */
+  virtualSolReserves: BN;
+  virtualTokenReserves: BN;
+  realSolReserves: BN;
+  realTokenReserves: BN;
+  slot: BN;
+  timestamp: BN;
 }
 
 export interface CreateTokenParams {
   name: string;
   symbol: string;
   uri: string;
+  /** Defaults to a constant-product curve */
+  curveKind?: CurveKind;
+  launchProtection?: LaunchProtection;
+  /** SOL (fee included) the creator spends buying from the new curve */
+  initialBuySol?: BN;
+  /** Slippage limit of the initial buy */
+  minTokensOut?: BN;
 }
 
 export interface InitializeParams {
   feeBps: number;
+  creatorFeeBps: number;
+  maxReferralBps: number;
   migrationThreshold: BN;
+  migrationFee: BN;
   initialVirtualSol: BN;
   initialVirtualTokens: BN;
+  tokenDecimals: number;
+  /** Tokens minted for each new curve */
+  totalSupply: BN;
+  /** Part of `totalSupply` sold on the curve */
+  curveSupply: BN;
+  /** CP-Swap config migrated pools are created under */
+  ammConfig: PublicKey;
+  allowedCurveKinds: CurveKind[];
 }
 
 export interface UpdateConfigParams {
@@ -1,79 +1,171 @@
 import BN from "bn.js";
+import { CurveState } from "openclaw-math-wasm";
 import { LAMPORTS_PER_SOL, TOKEN_DECIMALS } from "./constants";
+import { BondingCurve, GlobalConfig } from "./types";
 
 /**
- * Calculate the current token price based on reserves
+ * Reserves a curve is priced from. All curve math runs in `openclaw-math-wasm`, the
+ * program's own curve math compiled to WASM, so quotes match the chain exactly.
  */
-export function calculatePrice(
-  virtualSolReserves: BN,
-  virtualTokenReserves: BN
+export type CurveReserves = Pick<
+  BondingCurve,
+  | "virtualSolReserves"
+  | "virtualTokenReserves"
+  | "realSolReserves"
+  | "realTokenReserves"
+  | "tokensSold"
+  | "curveKind"
+>;
+
+const toBigInt = (value: BN): bigint => BigInt(value.toString());
+const toBN = (value: bigint): BN => new BN(value.toString());
+
+/**
+ * Run `f` against `curve`'s reserves, priced by its curve kind
+ */
+function withCurve<T>(curve: CurveReserves, f: (state: CurveState) => T): T {
+  let state = new CurveState(
+    toBigInt(curve.virtualSolReserves),
+    toBigInt(curve.virtualTokenReserves),
+    toBigInt(curve.realSolReserves),
+    toBigInt(curve.realTokenReserves),
+    toBigInt(curve.tokensSold)
+  );
+  // `linear` and `exponential` consume the constant-product state
+  const kind = curve.curveKind;
+  if (kind.kind === "linear") {
+    state = state.linear(toBigInt(kind.startPrice), toBigInt(kind.endPrice));
+  } else if (kind.kind === "exponential") {
+    state = state.exponential(toBigInt(kind.startPrice), kind.doublings);
+  }
+  try {
+    return f(state);
+  } finally {
+    state.free();
+  }
+}
+
+/**
+ * Fee charged on a trade against `curve`: the platform's `feeBps` plus the curve's
+ * creator fee, as the program's `BondingCurve::total_fee_bps`
+ */
+export function totalFeeBps(
+  feeBps: number,
+  curve: Pick<BondingCurve, "creatorFeeBps">
 ): number {
-  if (virtualTokenReserves.isZero()) return 0;
+  return feeBps + curve.creatorFeeBps;
+}
 
-  // Price in SOL per token
-  const price =
-    virtualSolReserves.toNumber() /
-    LAMPORTS_PER_SOL /
-    (virtualTokenReserves.toNumber() / Math.pow(10, TOKEN_DECIMALS));
+/**
+ * Decimals, total supply and curve supply of tokens launched under `config`. Configs
+ * written before launch supplies existed launch 6-decimal tokens whose whole supply is
+ * `initialVirtualTokens`, as the program's `GlobalConfig::launch_supply`.
+ */
+export function launchSupply(config: GlobalConfig): {
+  decimals: number;
+  totalSupply: BN;
+  curveSupply: BN;
+} {
+  if (
+    config.tokenDecimals === 0 &&
+    config.totalSupply.isZero() &&
+    config.curveSupply.isZero()
+  ) {
+    return {
+      decimals: TOKEN_DECIMALS,
+      totalSupply: config.initialVirtualTokens,
+      curveSupply: config.initialVirtualTokens,
+    };
+  }
+  return {
+    decimals: config.tokenDecimals,
+    totalSupply: config.totalSupply,
+    curveSupply: config.curveSupply,
+  };
+}
 
-  return price;
+/**
+ * Calculate the current price in SOL per whole token of `decimals` decimals
+ */
+export function calculatePrice(
+  curve: CurveReserves,
+  decimals: number = TOKEN_DECIMALS
+): number {
+  // `spotPrice` is in lamports per base unit, scaled by 10^9
+  return withCurve(
+    curve,
+    (state) =>
+      ((Number(state.spotPrice()) / 1e9) * Math.pow(10, decimals)) /
+      LAMPORTS_PER_SOL
+  );
 }
 
 /**
  * Calculate market cap in SOL
  */
-export function calculateMarketCap(
-  virtualSolReserves: BN,
-  virtualTokenReserves: BN,
-  totalSupply: BN
+export function calculateMarketCap(curve: CurveReserves, totalSupply: BN): number {
+  return withCurve(
+    curve,
+    (state) => Number(state.marketCap(toBigInt(totalSupply))) / LAMPORTS_PER_SOL
+  );
+}
+
+/**
+ * How far the curve is towards migrating, in basis points
+ */
+export function calculateMigrationProgress(
+  curve: CurveReserves,
+  migrationThreshold: BN
 ): number {
-  const price = calculatePrice(virtualSolReserves, virtualTokenReserves);
-  const supply = totalSupply.toNumber() / Math.pow(10, TOKEN_DECIMALS);
-  return price * supply;
+  return withCurve(curve, (state) =>
+    state.migrationProgressBps(toBigInt(migrationThreshold))
+  );
 }
 
 /**
- * Calculate tokens out for a given SOL input
+ * Calculate tokens out for a given SOL input, fee included. Like the program, the buy
+ * stops at the migration threshold, so `solIn` may be less than `solAmount`.
  */
 export function calculateTokensOut(
+  curve: CurveReserves,
   solAmount: BN,
-  virtualSolReserves: BN,
-  virtualTokenReserves: BN,
-  feeBps: number
-): { tokensOut: BN; fee: BN } {
-  // Calculate fee
-  const fee = solAmount.muln(feeBps).divn(10000);
-  const solAfterFee = solAmount.sub(fee);
-
-  // Constant product: k = x * y
-  const k = virtualSolReserves.mul(virtualTokenReserves);
-  const newSol = virtualSolReserves.add(solAfterFee);
-  const newTokens = k.div(newSol);
-  const tokensOut = virtualTokenReserves.sub(newTokens);
-
-  return { tokensOut, fee };
+  feeBps: number,
+  migrationThreshold: BN
+): { solIn: BN; tokensOut: BN; fee: BN } {
+  return withCurve(curve, (state) => {
+    const quote = state.quoteBuy(
+      toBigInt(solAmount),
+      feeBps,
+      toBigInt(migrationThreshold)
+    );
+    try {
+      return {
+        solIn: toBN(quote.sol),
+        tokensOut: toBN(quote.tokens),
+        fee: toBN(quote.fee),
+      };
+    } finally {
+      quote.free();
+    }
+  });
 }
 
 /**
- * Calculate SOL out for a given token input
+ * Calculate SOL out for a given token input, after fees
  */
 export function calculateSolOut(
+  curve: CurveReserves,
   tokenAmount: BN,
-  virtualSolReserves: BN,
-  virtualTokenReserves: BN,
   feeBps: number
 ): { solOut: BN; fee: BN } {
-  // Constant product: k = x * y
-  const k = virtualSolReserves.mul(virtualTokenReserves);
-  const newTokens = virtualTokenReserves.add(tokenAmount);
-  const newSol = k.div(newTokens);
-  const solOutGross = virtualSolReserves.sub(newSol);
-
-  // Calculate fee
-  const fee = solOutGross.muln(feeBps).divn(10000);
-  const solOut = solOutGross.sub(fee);
-
-  return { solOut, fee };
+  return withCurve(curve, (state) => {
+    const quote = state.quoteSell(toBigInt(tokenAmount), feeBps);
+    try {
+      return { solOut: toBN(quote.sol), fee: toBN(quote.fee) };
+    } finally {
+      quote.free();
+    }
+  });
 }
 
 /**
@@ -108,26 +200,35 @@ export function formatTokens(
 }
 
 /**
- * Calculate price impact percentage
+ * Calculate price impact percentage of a buy (`isBuy`) or sell, from the spot price
+ * before and after the trade
  */
 export function calculatePriceImpact(
-  solAmount: BN,
-  virtualSolReserves: BN,
-  virtualTokenReserves: BN,
-  feeBps: number
+  curve: CurveReserves,
+  amount: BN,
+  feeBps: number,
+  migrationThreshold: BN,
+  isBuy: boolean
 ): number {
-  const priceBefore = calculatePrice(virtualSolReserves, virtualTokenReserves);
-
-  const { tokensOut, fee } = calculateTokensOut(
-    solAmount,
-    virtualSolReserves,
-    virtualTokenReserves,
-    feeBps
-  );
-
-  const newSolReserves = virtualSolReserves.add(solAmount.sub(fee));
-  const newTokenReserves = virtualTokenReserves.sub(tokensOut);
-  const priceAfter = calculatePrice(newSolReserves, newTokenReserves);
-
-  return ((priceAfter - priceBefore) / priceBefore) * 100;
+  return withCurve(curve, (state) => {
+    const priceBefore = Number(state.spotPrice());
+    if (priceBefore === 0) return 0;
+
+    const quote = isBuy
+      ? state.quoteBuy(toBigInt(amount), feeBps, toBigInt(migrationThreshold))
+      : state.quoteSell(toBigInt(amount), feeBps);
+    try {
+      const after = isBuy
+        ? state.afterBuy(quote.sol - quote.fee, quote.tokens)
+        : state.afterSell(quote.tokens, quote.sol + quote.fee);
+      try {
+        const priceAfter = Number(after.spotPrice());
+        return (Math.abs(priceAfter - priceBefore) / priceBefore) * 100;
+      } finally {
+        after.free();
+      }
+    } finally {
+      quote.free();
+    }
+  });
 }
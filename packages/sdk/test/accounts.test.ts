@@ -0,0 +1,129 @@
+import { describe, expect, it } from "vitest";
+import { PublicKey } from "@solana/web3.js";
+import { decodeBondingCurve, decodeGlobalConfig } from "../src/accounts";
+import { PAUSE_CREATION } from "../src/constants";
+
+// Accounts read back from the program under solana-program-test: a config with a
+// creator fee, fee and pause admins, a proposed authority and creation paused, and a
+// linear curve after a 1 SOL buy
+const GLOBAL_CONFIG = Buffer.from(
+  "95089ccaa0fcb0d972399bab9aa1ad8bc82d650d836b3a530f9a2f5d983ff277194aa9e058eaa46e" +
+  "00000000000000060000000000000000000000000000000000000000000000000000000000000004" +
+  "00000000000000000000000000000000000000000000000000000000000000050000000000000000" +
+  "00000000000000000000000000000000000000000000000100000000000000000000000000000000" +
+  "000000000000000064003200d00700e40b540200000000e1f5050000000000ac23fc060000000010" +
+  "d847e3cf0300010000000000000000ca9a3b00000000fd030000000001366d0000000000001c4404" +
+  "000000000002366d00000000000008010100000002060010d847e3cf03000010d847e3cf03000707" +
+  "07070707070707070707070707070707070707070707070707070707070700000000000000000000" +
+  "00000000000000000000000000000000000000000000000000000000000000000000000000000000" +
+  "00000000000000000000000000000000000000000000000000000000000000000000000000000000" +
+  "0000000000000000000000000000000000000000000000000000000000",
+  "hex"
+);
+
+const BONDING_CURVE = Buffer.from(
+  "17b7f83760d8ac60750effba4c02c334e791be3cc1913277d5a3f92280fbb6cc0500a5431f6c9652" +
+  "85e0db3f8968447b6104c7f629bafabad4894b67d38157e74f419ef406d468f04094d93607000000" +
+  "0c358d628cb3030040e8b53a000000000c358d628cb30300f4da4ae5561c00000000e451d36a0000" +
+  "0000feff01366d0000000000001c440400000000003200404b4c0000000000000000000000000000" +
+  "00000000020000000000000000000000000000000000000000000000000000000000000000000000" +
+  "0000000000000000000000000000000000000000000000000000000000",
+  "hex"
+);
+
+const LINEAR_START_PRICE = "27958";
+const LINEAR_END_PRICE = "279580";
+const SUPPLY = "1073000000000000";
+
+describe("decodeGlobalConfig", () => {
+  it("decodes a v2 account", () => {
+    const config = decodeGlobalConfig(GLOBAL_CONFIG);
+
+    expect(config.authority).toEqual(
+      new PublicKey("8gtRjWSr43D2FTRFca7ychvqCLtQfbUKbn5FdwY9Y21K")
+    );
+    expect(config.pendingAuthority).toEqual(
+      new PublicKey("11111113R2cuenjG5nFubqX9Wzuukdin2YfGQVzu5")
+    );
+    expect(config.feeAdmin).toEqual(
+      new PublicKey("11111112cMQwSC9qirWGjZM6gLGwW69X22mqwLLGP")
+    );
+    expect(config.pauseAdmin).toEqual(
+      new PublicKey("111111131h1vYVSYuKP6AhS86fbRdMw9XHiZAvAaj")
+    );
+    expect(config.feeRecipient).toEqual(
+      new PublicKey("1111111QLbz7JHiBTspS962RLKV8GndWFwiEaqKM")
+    );
+    expect(config.feeBps).toBe(100);
+    expect(config.creatorFeeBps).toBe(50);
+    expect(config.maxReferralBps).toBe(2_000);
+    expect(config.migrationThreshold.toString()).toBe("10000000000");
+    expect(config.migrationFee.toString()).toBe("100000000");
+    expect(config.initialVirtualSol.toString()).toBe("30000000000");
+    expect(config.initialVirtualTokens.toString()).toBe(SUPPLY);
+    expect(config.totalTokens.toString()).toBe("1");
+    expect(config.totalVolume.toString()).toBe("1000000000");
+    expect(config.bump).toBe(253);
+    expect(config.paused).toBe(PAUSE_CREATION);
+    expect(config.configVersion).toBe(1);
+    expect(config.version).toBe(2);
+    expect(config.tokenDecimals).toBe(6);
+    expect(config.totalSupply.toString()).toBe(SUPPLY);
+    expect(config.curveSupply.toString()).toBe(SUPPLY);
+    expect(config.ammConfig).toEqual(
+      new PublicKey("US517G5965aydkZ46HS38QLi7UQiSojurfbQfKCELFx")
+    );
+  });
+
+  it("decodes every allowed curve kind", () => {
+    const [constantProduct, linear, exponential] =
+      decodeGlobalConfig(GLOBAL_CONFIG).allowedCurveKinds;
+
+    expect(constantProduct).toEqual({ kind: "constantProduct" });
+    expect(linear.kind).toBe("linear");
+    if (linear.kind === "linear") {
+      expect(linear.startPrice.toString()).toBe(LINEAR_START_PRICE);
+      expect(linear.endPrice.toString()).toBe(LINEAR_END_PRICE);
+    }
+    expect(exponential.kind).toBe("exponential");
+    if (exponential.kind === "exponential") {
+      expect(exponential.startPrice.toString()).toBe(LINEAR_START_PRICE);
+      expect(exponential.doublings).toBe(8);
+    }
+  });
+});
+
+describe("decodeBondingCurve", () => {
+  it("decodes a v2 account", () => {
+    const curve = decodeBondingCurve(BONDING_CURVE);
+
+    expect(curve.mint).toEqual(
+      new PublicKey("8swy1rzaV2EEA7Tp7SB7F8VLa98wmbLdNaLTCD4o68ru")
+    );
+    expect(curve.creator).toEqual(
+      new PublicKey("A1c66xCKF3MgCZS6DnyaUAPaHiKEyf3aEh8nK1FmJ1Ww")
+    );
+    expect(curve.virtualSolReserves.toString()).toBe("30985000000");
+    expect(curve.virtualTokenReserves.toString()).toBe("1041840460346636");
+    expect(curve.realSolReserves.toString()).toBe("985000000");
+    expect(curve.realTokenReserves.toString()).toBe("1041840460346636");
+    expect(curve.tokensSold.toString()).toBe("31159539653364");
+    expect(curve.migrated).toBe(false);
+    expect(curve.complete).toBe(false);
+    expect(curve.createdAt.toString()).toBe("1792233956");
+    expect(curve.bump).toBe(254);
+    expect(curve.solVaultBump).toBe(255);
+    expect(curve.curveKind.kind).toBe("linear");
+    if (curve.curveKind.kind === "linear") {
+      expect(curve.curveKind.startPrice.toString()).toBe(LINEAR_START_PRICE);
+      expect(curve.curveKind.endPrice.toString()).toBe(LINEAR_END_PRICE);
+    }
+    expect(curve.creatorFeeBps).toBe(50);
+    expect(curve.creatorFeesAccrued.toString()).toBe("5000000");
+    expect(curve.creatorFeesClaimed.toString()).toBe("0");
+    expect(curve.frozen).toBe(false);
+    expect(curve.version).toBe(2);
+    expect(curve.launchProtection.windowSecs.toString()).toBe("0");
+    expect(curve.migrationTokens.toString()).toBe("0");
+  });
+});
@@ -0,0 +1,48 @@
+import { describe, expect, it } from "vitest";
+import { PublicKey } from "@solana/web3.js";
+import { parseTradeEvents } from "../src/events";
+
+// Logs of a create and a 1 SOL buy under solana-program-test, with a 0.5% creator fee
+const TOKEN_CREATED_DATA =
+  "Program data: 7BMp/4JOk6yTA8ZeoEI5HlQ+fyEe1pG0ZbLCfh9sGpvRKkvauTq7pz38I3BW83Lka8oEtcydY+1p" +
+  "+idOvHx0GhdPzqAFO58Wet0LsQ67UIJSIpYwCtni8ldumYT736P85o1I2efFUYgKAAAAQ2xhdyBUb2tlbgQAAABD" +
+  "TEFXGwAAAGh0dHBzOi8vY2xhdy5mdW4vdG9rZW4uanNvbgbd9uHXZaGT2cvhRs7reawctIXtX1s3kTqM9YV+/wCp" +
+  "ADIAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACsI/wGAAAAABDYR+PPAwAAENhH488DAAAAAAAAAAAABgAQ" +
+  "2EfjzwMAAQAAAAAAAAADWdNqAAAAAA==";
+
+const TRADE_DATA =
+  "Program data: GP7amP0rElGTA8ZeoEI5HlQ+fyEe1pG0ZbLCfh9sGpvRKkvauTq7p3rdC7EOu1CCUiKWMArZ4vJX" +
+  "bpmE+9+j/OaNSNnnxVGIAQDKmjsAAAAAScM55wUfAACAlpgAAAAAAEBLTAAAAAAAAAAAAAAAAAAAQJTZNgcAAAC3" +
+  "TJ5g3bADAEDotToAAAAAt0yeYN2wAwABAAAAAAAAAANZ02oAAAAA";
+
+describe("parseTradeEvents", () => {
+  it("decodes a buy and skips other events and logs", () => {
+    const events = parseTradeEvents([
+      "Program log: Instruction: Buy",
+      TOKEN_CREATED_DATA,
+      TRADE_DATA,
+      "Program log: Buy: 1000000000 lamports -> 34110214619977 tokens " +
+        "(fee: 10000000 lamports, creator fee: 5000000 lamports)",
+    ]);
+
+    expect(events).toHaveLength(1);
+    const [trade] = events;
+    expect(trade.mint).toEqual(
+      new PublicKey("74yePUmvVFi2qpdYkBF7Xd4e2qaiX1XZ7ToqU4THxw7z")
+    );
+    expect(trade.user).toEqual(
+      new PublicKey("HM2YrLNq2nanuUaknR1NHNYsSKnJhNaWw5HrJxjqD3ir")
+    );
+    expect(trade.isBuy).toBe(true);
+    expect(trade.solAmount.toString()).toBe("1000000000");
+    expect(trade.tokenAmount.toString()).toBe("34110214619977");
+    expect(trade.fee.toString()).toBe("10000000");
+    expect(trade.creatorFee.toString()).toBe("5000000");
+    expect(trade.referrer).toBeNull();
+    expect(trade.referralFee.toString()).toBe("0");
+    expect(trade.virtualSolReserves.toString()).toBe("30985000000");
+    expect(trade.virtualTokenReserves.toString()).toBe("1038889785380023");
+    expect(trade.realSolReserves.toString()).toBe("985000000");
+    expect(trade.realTokenReserves.toString()).toBe("1038889785380023");
+  });
+});
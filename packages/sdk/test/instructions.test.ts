@@ -0,0 +1,283 @@
+import { describe, expect, it } from "vitest";
+import {
+  Connection,
+  Keypair,
+  PublicKey,
+  SystemProgram,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getAssociatedTokenAddressSync,
+} from "@solana/spl-token";
+import { BorshCoder, Idl } from "@coral-xyz/anchor";
+import BN from "bn.js";
+import IDL from "../src/idl/openclaw.json";
+import { OpenClawClient } from "../src/client";
+import { OPENCLAW_PROGRAM_ID, TOKEN_METADATA_PROGRAM_ID } from "../src/constants";
+import {
+  findBondingCurvePDA,
+  findGlobalConfigPDA,
+  findMetadataPDA,
+  findMigrationVaultPDA,
+  findPositionPDA,
+  findPriceHistoryPDA,
+  findSolVaultPDA,
+} from "../src/pda";
+
+interface IdlAccount {
+  name: string;
+  isMut: boolean;
+  isSigner: boolean;
+  isOptional?: boolean;
+}
+
+const client = new OpenClawClient(new Connection("http://127.0.0.1:8899"));
+const coder = new BorshCoder(IDL as Idl);
+
+/**
+ * Check `ix` passes `expected` in the order and with the flags of the IDL's account list
+ * for `name`. Optional accounts expected as null must be left out.
+ */
+function expectIdlAccounts(
+  ix: TransactionInstruction,
+  name: string,
+  expected: Record<string, PublicKey | null>
+) {
+  const accounts = IDL.instructions.find((idlIx) => idlIx.name === name)!
+    .accounts as IdlAccount[];
+  expect(Object.keys(expected).sort()).toEqual(
+    accounts.map((account) => account.name).sort()
+  );
+
+  expect(ix.programId).toEqual(OPENCLAW_PROGRAM_ID);
+  expect(ix.keys).toHaveLength(accounts.length);
+  accounts.forEach((account, i) => {
+    const address = expected[account.name];
+    const meta = ix.keys[i];
+    if (address === null) {
+      expect(account.isOptional, account.name).toBe(true);
+      expect(meta, account.name).toEqual({
+        pubkey: OPENCLAW_PROGRAM_ID,
+        isSigner: false,
+        isWritable: false,
+      });
+    } else {
+      expect(meta, account.name).toEqual({
+        pubkey: address,
+        isSigner: account.isSigner,
+        isWritable: account.isMut,
+      });
+    }
+  });
+}
+
+describe("trade instructions", () => {
+  const trader = Keypair.generate().publicKey;
+  const mint = Keypair.generate().publicKey;
+  const feeRecipient = Keypair.generate().publicKey;
+  const [bondingCurve] = findBondingCurvePDA(mint);
+
+  const tradeAccounts = {
+    globalConfig: findGlobalConfigPDA()[0],
+    bondingCurve,
+    mint,
+    solVault: findSolVaultPDA(mint)[0],
+    curveTokenVault: getAssociatedTokenAddressSync(
+      mint,
+      bondingCurve,
+      true,
+      TOKEN_2022_PROGRAM_ID
+    ),
+    feeRecipient,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+    systemProgram: SystemProgram.programId,
+    referrer: null,
+    referrerWallet: null,
+    position: null,
+    priceHistory: findPriceHistoryPDA(mint)[0],
+  };
+  const traderTokenAccount = getAssociatedTokenAddressSync(
+    mint,
+    trader,
+    false,
+    TOKEN_2022_PROGRAM_ID
+  );
+
+  it("builds buy from the IDL", () => {
+    const ix = client.createBuyInstruction(
+      trader,
+      mint,
+      feeRecipient,
+      new BN(1_000_000_000),
+      new BN(5),
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    expectIdlAccounts(ix, "buy", {
+      ...tradeAccounts,
+      buyer: trader,
+      buyerTokenAccount: traderTokenAccount,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+    });
+    const decoded = coder.instruction.decode(ix.data);
+    expect(decoded?.name).toBe("buy");
+    const args = decoded?.data as { solAmount: BN; minTokensOut: BN };
+    expect(args.solAmount.toString()).toBe("1000000000");
+    expect(args.minTokensOut.toString()).toBe("5");
+  });
+
+  it("builds sell from the IDL", () => {
+    const ix = client.createSellInstruction(
+      trader,
+      mint,
+      feeRecipient,
+      new BN(7),
+      new BN(3),
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    expectIdlAccounts(ix, "sell", {
+      ...tradeAccounts,
+      seller: trader,
+      sellerTokenAccount: traderTokenAccount,
+    });
+    const decoded = coder.instruction.decode(ix.data);
+    expect(decoded?.name).toBe("sell");
+    const args = decoded?.data as { tokenAmount: BN; minSolOut: BN };
+    expect(args.tokenAmount.toString()).toBe("7");
+    expect(args.minSolOut.toString()).toBe("3");
+  });
+});
+
+describe("setup instructions", () => {
+  const authority = Keypair.generate().publicKey;
+  const feeRecipient = Keypair.generate().publicKey;
+
+  it("builds initialize from the IDL", () => {
+    const ammConfig = Keypair.generate().publicKey;
+    const ix = client.createInitializeInstruction(authority, feeRecipient, {
+      ammConfig,
+      tokenDecimals: 9,
+      curveSupply: new BN(800),
+      allowedCurveKinds: [
+        { kind: "constantProduct" },
+        { kind: "linear", startPrice: new BN(10), endPrice: new BN(20) },
+      ],
+    });
+
+    expectIdlAccounts(ix, "initialize", {
+      authority,
+      globalConfig: findGlobalConfigPDA()[0],
+      feeRecipient,
+      systemProgram: SystemProgram.programId,
+    });
+    const decoded = coder.instruction.decode(ix.data);
+    expect(decoded?.name).toBe("initialize");
+    const { params } = decoded?.data as { params: Record<string, any> };
+    expect(params.feeBps).toBe(100);
+    expect(params.maxReferralBps).toBe(2_000);
+    expect(params.migrationFee.toString()).toBe("100000000");
+    expect(params.tokenDecimals).toBe(9);
+    expect(params.totalSupply.toString()).toBe("1000000000000000");
+    expect(params.curveSupply.toString()).toBe("800");
+    expect(params.ammConfig).toEqual(ammConfig);
+    expect(params.allowedCurveKinds).toHaveLength(2);
+    expect(params.allowedCurveKinds[0]).toEqual({ constantProduct: {} });
+    expect(params.allowedCurveKinds[1].linear.endPrice.toString()).toBe("20");
+  });
+
+  const creator = Keypair.generate().publicKey;
+  const mint = Keypair.generate();
+
+  function tokenAccounts(tokenProgram: PublicKey) {
+    const [bondingCurve] = findBondingCurvePDA(mint.publicKey);
+    return {
+      creator,
+      globalConfig: findGlobalConfigPDA()[0],
+      mint: mint.publicKey,
+      bondingCurve,
+      priceHistory: findPriceHistoryPDA(mint.publicKey)[0],
+      solVault: findSolVaultPDA(mint.publicKey)[0],
+      curveTokenVault: getAssociatedTokenAddressSync(
+        mint.publicKey,
+        bondingCurve,
+        true,
+        tokenProgram
+      ),
+      migrationVault: findMigrationVaultPDA(mint.publicKey)[0],
+      tokenProgram,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: new PublicKey("SysvarRent111111111111111111111111111111111"),
+    };
+  }
+
+  it("builds createToken for an SPL Token mint from the IDL", () => {
+    const [ix] = client.createTokenInstruction(creator, mint, feeRecipient, {
+      name: "Claw",
+      symbol: "CLAW",
+      uri: "https://example.com/claw.json",
+    });
+
+    expectIdlAccounts(ix, "createToken", {
+      ...tokenAccounts(TOKEN_PROGRAM_ID),
+      metadata: findMetadataPDA(mint.publicKey)[0],
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      feeRecipient: null,
+      creatorTokenAccount: null,
+      creatorPosition: null,
+    });
+    const decoded = coder.instruction.decode(ix.data);
+    expect(decoded?.name).toBe("createToken");
+    const { params } = decoded?.data as { params: Record<string, any> };
+    expect(params.name).toBe("Claw");
+    expect(params.curveKind).toEqual({ constantProduct: {} });
+    expect(params.launchProtection).toBeNull();
+    expect(params.initialBuySol).toBeNull();
+  });
+
+  it("builds createToken with an initial buy on a Token-2022 mint", () => {
+    const [ix] = client.createTokenInstruction(
+      creator,
+      mint,
+      feeRecipient,
+      {
+        name: "Claw",
+        symbol: "CLAW",
+        uri: "",
+        curveKind: { kind: "exponential", startPrice: new BN(5), doublings: 3 },
+        launchProtection: {
+          windowSecs: new BN(60),
+          maxSolPerTx: new BN(1_000),
+          maxSolPerWallet: new BN(2_000),
+        },
+        initialBuySol: new BN(1_000),
+        minTokensOut: new BN(42),
+      },
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    expectIdlAccounts(ix, "createToken", {
+      ...tokenAccounts(TOKEN_2022_PROGRAM_ID),
+      metadata: null,
+      tokenMetadataProgram: null,
+      feeRecipient,
+      creatorTokenAccount: getAssociatedTokenAddressSync(
+        mint.publicKey,
+        creator,
+        false,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      creatorPosition: findPositionPDA(mint.publicKey, creator)[0],
+    });
+    const decoded = coder.instruction.decode(ix.data);
+    const { params } = decoded?.data as { params: Record<string, any> };
+    expect(params.curveKind.exponential.doublings).toBe(3);
+    expect(params.launchProtection.maxSolPerWallet.toString()).toBe("2000");
+    expect(params.initialBuySol.toString()).toBe("1000");
+    expect(params.minTokensOut.toString()).toBe("42");
+  });
+});